@@ -8,6 +8,7 @@ use serde::Deserialize;
 use sqlx::SqlitePool;
 
 use crate::core::models::*;
+use crate::core::scheduling::{attendance, timezone};
 
 // ============================================================================
 // ACTIVITY HANDLERS
@@ -72,6 +73,34 @@ pub async fn log_activity(
 // REMINDER HANDLERS
 // ============================================================================
 
+/// Helper: start of a finalized session, in the recipient's timezone when known
+/// (falls back to the poll's own zone)
+async fn session_time_for(
+    pool: &SqlitePool,
+    session_id: &str,
+    recipient_timezone: Option<&str>,
+) -> Option<String> {
+    let (finalized_time, poll_timezone): (Option<String>, String) =
+        sqlx::query_as("SELECT finalized_time, timezone FROM polls WHERE id = ?")
+            .bind(session_id)
+            .fetch_optional(pool)
+            .await
+            .ok()
+            .flatten()?;
+
+    let (date, time_slot) = attendance::parse_slot_key(&finalized_time?)?;
+    let poll_tz = timezone::parse_timezone(&poll_timezone).ok()?;
+    let viewer_tz = timezone::viewer_timezone(None, recipient_timezone, &poll_timezone).ok()?;
+    timezone::format_slot(&date, &time_slot, poll_tz, viewer_tz)
+}
+
+fn with_session_time(message: &str, session_time: Option<&str>) -> String {
+    match session_time {
+        Some(when) => format!("{}\n\nQuando: {}", message, when),
+        None => message.to_string(),
+    }
+}
+
 /// GET /api/reminder/config
 pub async fn get_reminder_config() -> Json<ReminderConfig> {
     Json(ReminderConfig {
@@ -83,13 +112,26 @@ pub async fn get_reminder_config() -> Json<ReminderConfig> {
 
 /// POST /api/reminder/whatsapp
 pub async fn send_whatsapp_reminder(
+    State(pool): State<SqlitePool>,
     Json(req): Json<WhatsAppReminderRequest>,
 ) -> Result<Json<ReminderResponse>, StatusCode> {
+    // Present the session time in the recipient's zone if the phone belongs to a user
+    let recipient_timezone: Option<String> =
+        sqlx::query_scalar("SELECT timezone FROM users WHERE phone = ?")
+            .bind(&req.phone)
+            .fetch_optional(&pool)
+            .await
+            .unwrap_or(None)
+            .flatten();
+    let session_time =
+        session_time_for(&pool, &req.session_id, recipient_timezone.as_deref()).await;
+    let message = with_session_time(&req.message, session_time.as_deref());
+
     // Use the dedicated WhatsApp service module
     match crate::core::services::whatsapp::send_reminder_whatsapp(
         &req.phone,
         "Sessione D&D", // Could be enhanced to pass actual session name
-        &req.message,
+        &message,
     )
     .await
     {
@@ -109,19 +151,24 @@ pub async fn send_whatsapp_reminder(
 
 /// POST /api/reminder/telegram
 pub async fn send_telegram_reminder(
+    State(pool): State<SqlitePool>,
     Json(req): Json<TelegramReminderRequest>,
 ) -> Result<Json<ReminderResponse>, StatusCode> {
     // Verifica configurazione
     let bot_token =
         std::env::var("TELEGRAM_BOT_TOKEN").map_err(|_| StatusCode::SERVICE_UNAVAILABLE)?;
 
+    // Chat ids aren't linked to accounts: use the poll's own timezone
+    let session_time = session_time_for(&pool, &req.session_id, None).await;
+    let message = with_session_time(&req.message, session_time.as_deref());
+
     // Invia tramite Telegram Bot API
     let client = reqwest::Client::new();
     let url = format!("https://api.telegram.org/bot{}/sendMessage", bot_token);
 
     let params = serde_json::json!({
         "chat_id": req.chat_id,
-        "text": req.message,
+        "text": message,
         "parse_mode": "HTML"
     });
 
@@ -151,8 +198,8 @@ pub async fn send_email_reminder(
     Json(req): Json<EmailReminderRequest>,
 ) -> Result<Json<ReminderResponse>, StatusCode> {
     // 1. Fetch user email and name
-    let (email, name): (String, String) =
-        sqlx::query_as("SELECT email, name FROM users WHERE id = ?")
+    let (email, name, user_timezone): (String, String, Option<String>) =
+        sqlx::query_as("SELECT email, name, timezone FROM users WHERE id = ?")
             .bind(&req.user_id)
             .fetch_optional(&pool)
             .await
@@ -167,9 +214,15 @@ pub async fn send_email_reminder(
         .unwrap_or(Some("Sessione D&D".to_string()))
         .unwrap_or("Sessione D&D".to_string());
 
-    // 3. Send email
-    if let Err(e) =
-        crate::core::services::email::send_reminder_email(&email, &poll_title, &req.message).await
+    // 3. Send email, with the session time in the user's timezone
+    let session_time = session_time_for(&pool, &req.session_id, user_timezone.as_deref()).await;
+    if let Err(e) = crate::core::services::email::send_reminder_email(
+        &email,
+        &poll_title,
+        &req.message,
+        session_time.as_deref(),
+    )
+    .await
     {
         tracing::error!("Failed to send reminder email to {}: {}", email, e);
         return Err(StatusCode::INTERNAL_SERVER_ERROR);
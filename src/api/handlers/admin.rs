@@ -51,11 +51,9 @@ pub async fn get_admin_stats(
             .into_response()
     })?;
 
-    // 3. Active Campaigns (Polls that are active and recent - within last 6 months)
-    let six_months_ago = now - (180 * 24 * 60 * 60);
+    // 3. Active Campaigns
     let active_campaigns: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM polls WHERE status = 'active' AND created_at > ?")
-            .bind(six_months_ago)
+        sqlx::query_scalar("SELECT COUNT(*) FROM campaigns WHERE status = 'active'")
             .fetch_one(&pool)
             .await
             .map_err(|e| {
@@ -0,0 +1,455 @@
+// Campaign Handlers
+// A campaign groups the sessions (polls) of one long-running game under its DM.
+
+use crate::api::handlers::general::{
+    sanitize_string, validate_email, validate_string_length, validate_uuid, MAX_DESCRIPTION_LENGTH,
+    MAX_LOCATION_LENGTH, MAX_TITLE_LENGTH,
+};
+use crate::core::models::{Campaign, CampaignMember, CampaignRequest, Poll, User};
+use crate::core::scheduling::lifecycle::PollStatus;
+use crate::core::scheduling::limits::PollLimits;
+use crate::core::scheduling::{session_time, time_preferences};
+use crate::db::DbPool;
+use crate::security::auth::AuthUser;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+fn db_error(e: sqlx::Error) -> (StatusCode, String) {
+    tracing::error!("Campaign query failed: {}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Database error".to_string(),
+    )
+}
+
+/// The DM and admins may edit a campaign
+pub(crate) fn can_manage(user: &User, campaign: &Campaign) -> bool {
+    user.role == "admin" || user.id == campaign.dm_id
+}
+
+pub(crate) async fn is_member(
+    pool: &DbPool,
+    user: &User,
+    campaign_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let found: Option<i64> = sqlx::query_scalar(
+        "SELECT 1 FROM campaign_members WHERE campaign_id = ? AND (user_id = ? OR email = ?)",
+    )
+    .bind(campaign_id)
+    .bind(&user.id)
+    .bind(&user.email)
+    .fetch_optional(pool)
+    .await?;
+    Ok(found.is_some())
+}
+
+pub(crate) async fn fetch_campaign(
+    pool: &DbPool,
+    campaign_id: &str,
+) -> Result<Campaign, (StatusCode, String)> {
+    validate_uuid(campaign_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    sqlx::query_as("SELECT * FROM campaigns WHERE id = ?")
+        .bind(campaign_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(db_error)?
+        .ok_or((StatusCode::NOT_FOUND, "Campaign not found".to_string()))
+}
+
+pub(crate) async fn campaign_member_emails(
+    pool: &DbPool,
+    campaign_id: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT email FROM campaign_members WHERE campaign_id = ? ORDER BY added_at, email",
+    )
+    .bind(campaign_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Validated, normalized form of a create/update request
+struct CampaignInput {
+    name: String,
+    description: String,
+    default_location: String,
+    default_time_slots: Vec<String>,
+    members: Vec<String>,
+    status: Option<String>,
+}
+
+fn validate_request(
+    payload: CampaignRequest,
+    limits: &PollLimits,
+) -> Result<CampaignInput, String> {
+    validate_string_length(&payload.name, MAX_TITLE_LENGTH, "Name")?;
+    if payload.description.len() > MAX_DESCRIPTION_LENGTH {
+        return Err(format!(
+            "Description exceeds maximum length of {}",
+            MAX_DESCRIPTION_LENGTH
+        ));
+    }
+    if payload.default_location.len() > MAX_LOCATION_LENGTH {
+        return Err(format!(
+            "Default location exceeds maximum length of {}",
+            MAX_LOCATION_LENGTH
+        ));
+    }
+
+    let mut default_time_slots = Vec::new();
+    for slot in &payload.default_time_slots {
+        default_time_slots.push(time_preferences::normalize_slot(slot)?);
+    }
+    default_time_slots.sort();
+    default_time_slots.dedup();
+
+    if payload.members.len() > limits.max_participants {
+        return Err(format!(
+            "Too many members (max: {})",
+            limits.max_participants
+        ));
+    }
+    let mut members = Vec::new();
+    for email in &payload.members {
+        let email = email.trim().to_lowercase();
+        validate_email(&email)?;
+        if !members.contains(&email) {
+            members.push(email);
+        }
+    }
+
+    if let Some(status) = &payload.status {
+        if status != "active" && status != "archived" {
+            return Err("Status must be 'active' or 'archived'".to_string());
+        }
+    }
+
+    Ok(CampaignInput {
+        name: sanitize_string(&payload.name),
+        description: sanitize_string(&payload.description),
+        default_location: sanitize_string(&payload.default_location),
+        default_time_slots,
+        members,
+        status: payload.status,
+    })
+}
+
+/// Replaces the member list, linking emails of registered users
+async fn replace_members(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    campaign_id: &str,
+    members: &[String],
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM campaign_members WHERE campaign_id = ?")
+        .bind(campaign_id)
+        .execute(&mut **tx)
+        .await?;
+
+    let now = Utc::now().timestamp();
+    for email in members {
+        sqlx::query(
+            "INSERT INTO campaign_members (campaign_id, email, user_id, added_at)
+             VALUES (?, ?, (SELECT id FROM users WHERE lower(email) = ?), ?)",
+        )
+        .bind(campaign_id)
+        .bind(email)
+        .bind(email)
+        .bind(now)
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+/// GET /api/campaigns
+/// Campaigns the user runs or plays in (admins see all of them)
+pub async fn list_campaigns(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let user = auth_user.0;
+
+    let campaigns: Vec<Campaign> = sqlx::query_as(
+        "SELECT * FROM campaigns c WHERE ? = 'admin' OR c.dm_id = ?
+            OR EXISTS (SELECT 1 FROM campaign_members m WHERE m.campaign_id = c.id AND (m.user_id = ? OR m.email = ?))
+         ORDER BY c.status, c.name",
+    )
+    .bind(&user.role)
+    .bind(&user.id)
+    .bind(&user.id)
+    .bind(&user.email)
+    .fetch_all(&pool)
+    .await
+    .map_err(db_error)?;
+
+    let mut result = Vec::with_capacity(campaigns.len());
+    for campaign in campaigns {
+        let (member_count, poll_count): (i64, i64) = sqlx::query_as(
+            "SELECT (SELECT COUNT(*) FROM campaign_members WHERE campaign_id = ?),
+                    (SELECT COUNT(*) FROM polls WHERE campaign_id = ?)",
+        )
+        .bind(&campaign.id)
+        .bind(&campaign.id)
+        .fetch_one(&pool)
+        .await
+        .map_err(db_error)?;
+
+        result.push(json!({
+            "campaign": campaign,
+            "memberCount": member_count,
+            "pollCount": poll_count
+        }));
+    }
+
+    Ok(Json(json!({ "campaigns": result })))
+}
+
+/// POST /api/campaigns
+/// The creator becomes the campaign's DM
+pub async fn create_campaign(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+    Json(payload): Json<CampaignRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let user = auth_user.0;
+    let limits = PollLimits::for_role(Some(&user.role));
+    let input = validate_request(payload, &limits).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let campaign_id = Uuid::new_v4().to_string();
+    let slots_json = serde_json::to_string(&input.default_time_slots).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to serialize time slots: {}", e),
+        )
+    })?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    sqlx::query(
+        "INSERT INTO campaigns (id, name, description, dm_id, default_location, default_time_slots, status, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&campaign_id)
+    .bind(&input.name)
+    .bind(&input.description)
+    .bind(&user.id)
+    .bind(&input.default_location)
+    .bind(&slots_json)
+    .bind(input.status.as_deref().unwrap_or("active"))
+    .bind(Utc::now().timestamp())
+    .execute(&mut *tx)
+    .await
+    .map_err(db_error)?;
+
+    replace_members(&mut tx, &campaign_id, &input.members)
+        .await
+        .map_err(db_error)?;
+
+    tx.commit()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(json!({ "id": campaign_id })))
+}
+
+/// GET /api/campaigns/:id
+/// Campaign details: members, open polls, upcoming/past sessions and cancelled ones
+pub async fn get_campaign(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+    Path(campaign_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let user = auth_user.0;
+    let campaign = fetch_campaign(&pool, &campaign_id).await?;
+
+    if !can_manage(&user, &campaign)
+        && !is_member(&pool, &user, &campaign.id)
+            .await
+            .map_err(db_error)?
+    {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "You are not part of this campaign".to_string(),
+        ));
+    }
+
+    let members: Vec<CampaignMember> = sqlx::query_as(
+        "SELECT * FROM campaign_members WHERE campaign_id = ? ORDER BY added_at, email",
+    )
+    .bind(&campaign.id)
+    .fetch_all(&pool)
+    .await
+    .map_err(db_error)?;
+
+    let polls: Vec<Poll> =
+        sqlx::query_as("SELECT * FROM polls WHERE campaign_id = ? ORDER BY created_at")
+            .bind(&campaign.id)
+            .fetch_all(&pool)
+            .await
+            .map_err(db_error)?;
+
+    // Scheduled sessions are split around "now"; polls not decided yet (still voting or
+    // closed) are open, called-off ones are listed apart
+    let now = Utc::now();
+    let mut open_polls = Vec::new();
+    let mut upcoming = Vec::new();
+    let mut past = Vec::new();
+    let mut cancelled = Vec::new();
+    for poll in polls {
+        let session = session_time::from_columns(poll.finalized_start, poll.finalized_end);
+
+        let summary = json!({
+            "id": poll.id,
+            "title": poll.title,
+            "status": poll.status,
+            "location": poll.location,
+            "finalizedTime": poll.finalized_time,
+            "start": session.map(|s| s.start.to_rfc3339()),
+            "end": session.map(|s| s.end.to_rfc3339()),
+            "timezone": poll.timezone,
+            "cancelReason": poll.cancel_reason
+        });
+
+        match (poll.status, session) {
+            (PollStatus::Active | PollStatus::Closed, _) => open_polls.push(summary),
+            (PollStatus::Cancelled, _) => cancelled.push(summary),
+            (_, Some(session)) if session.start >= now => upcoming.push((session.start, summary)),
+            (_, Some(session)) => past.push((session.start, summary)),
+            // Archived before a slot was picked: it never became a session
+            (_, None) => {}
+        }
+    }
+    upcoming.sort_by_key(|(start, _)| *start);
+    past.sort_by_key(|(start, _)| std::cmp::Reverse(*start));
+
+    Ok(Json(json!({
+        "campaign": campaign,
+        "members": members,
+        "openPolls": open_polls,
+        "upcoming": upcoming.into_iter().map(|(_, s)| s).collect::<Vec<_>>(),
+        "past": past.into_iter().map(|(_, s)| s).collect::<Vec<_>>(),
+        "cancelled": cancelled
+    })))
+}
+
+/// PUT /api/campaigns/:id
+pub async fn update_campaign(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+    Path(campaign_id): Path<String>,
+    Json(payload): Json<CampaignRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let user = auth_user.0;
+    let campaign = fetch_campaign(&pool, &campaign_id).await?;
+    if !can_manage(&user, &campaign) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only the campaign's DM can edit it".to_string(),
+        ));
+    }
+
+    let limits = PollLimits::for_role(Some(&user.role));
+    let input = validate_request(payload, &limits).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let slots_json = serde_json::to_string(&input.default_time_slots).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to serialize time slots: {}", e),
+        )
+    })?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    sqlx::query(
+        "UPDATE campaigns SET name = ?, description = ?, default_location = ?, default_time_slots = ?, status = COALESCE(?, status) WHERE id = ?",
+    )
+    .bind(&input.name)
+    .bind(&input.description)
+    .bind(&input.default_location)
+    .bind(&slots_json)
+    .bind(&input.status)
+    .bind(&campaign.id)
+    .execute(&mut *tx)
+    .await
+    .map_err(db_error)?;
+
+    replace_members(&mut tx, &campaign.id, &input.members)
+        .await
+        .map_err(db_error)?;
+
+    tx.commit()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(json!({ "success": true })))
+}
+
+/// DELETE /api/campaigns/:id
+/// Polls of the campaign are kept, just no longer grouped
+pub async fn delete_campaign(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+    Path(campaign_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let user = auth_user.0;
+    let campaign = fetch_campaign(&pool, &campaign_id).await?;
+    if !can_manage(&user, &campaign) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only the campaign's DM can delete it".to_string(),
+        ));
+    }
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    sqlx::query("UPDATE polls SET campaign_id = NULL WHERE campaign_id = ?")
+        .bind(&campaign.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    sqlx::query("DELETE FROM campaign_members WHERE campaign_id = ?")
+        .bind(&campaign.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    for table in ["series_exceptions", "series_occurrences"] {
+        sqlx::query(&format!(
+            "DELETE FROM {} WHERE series_id IN (SELECT id FROM session_series WHERE campaign_id = ?)",
+            table
+        ))
+        .bind(&campaign.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    }
+    sqlx::query("DELETE FROM session_series WHERE campaign_id = ?")
+        .bind(&campaign.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    sqlx::query("DELETE FROM campaigns WHERE id = ?")
+        .bind(&campaign.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+    tx.commit()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(json!({ "success": true })))
+}
@@ -1,11 +1,24 @@
+use crate::api::handlers::campaigns;
+use crate::core::events;
 use crate::core::models;
 use crate::core::models::{
     Availability, CreatePollRequest, JoinPollRequest, Participant, Poll, UpdateAvailabilityRequest,
 };
+use crate::core::scheduling::attendance::{self, ATTENDANCE_OPTIONAL, ATTENDANCE_REQUIRED};
+use crate::core::scheduling::blocks;
+use crate::core::scheduling::lifecycle::{self, PollStatus};
+use crate::core::scheduling::limits::PollLimits;
+use crate::core::scheduling::quorum;
+use crate::core::scheduling::recommendations::{self, TieBreaker, DEFAULT_TENTATIVE_WEIGHT};
+use crate::core::scheduling::reconcile::{self, VoteMoves};
+use crate::core::scheduling::session_time::{self, SessionTime};
+use crate::core::scheduling::time_preferences::TimePreferences;
+use crate::core::scheduling::timezone::{self, DEFAULT_TIMEZONE};
+use crate::core::store::{ConcurrencyError, RedbEventStore};
 use crate::db::DbPool;
 use crate::security::auth::MaybeAuthUser;
 use axum::{
-    extract::{Path, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
     response::{Html, IntoResponse},
     Json,
@@ -13,20 +26,20 @@ use axum::{
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use sqlx::{QueryBuilder, Sqlite};
+use std::collections::BTreeMap;
+use std::sync::Arc;
 use uuid::Uuid;
 
 // Security constants
-const MAX_TITLE_LENGTH: usize = 200;
-const MAX_DESCRIPTION_LENGTH: usize = 2000;
-const MAX_LOCATION_LENGTH: usize = 200;
+pub(crate) const MAX_TITLE_LENGTH: usize = 200;
+pub(crate) const MAX_DESCRIPTION_LENGTH: usize = 2000;
+pub(crate) const MAX_LOCATION_LENGTH: usize = 200;
 const MAX_NAME_LENGTH: usize = 100;
 const MAX_EMAIL_LENGTH: usize = 254; // RFC 5321
-const MAX_PARTICIPANTS: usize = 100;
-const MAX_DATES: usize = 365;
-const MAX_AVAILABILITY_ENTRIES: usize = 1000;
 
 // Input validation helpers
-fn validate_email(email: &str) -> Result<(), String> {
+pub(crate) fn validate_email(email: &str) -> Result<(), String> {
     if email.is_empty() || email.len() > MAX_EMAIL_LENGTH {
         return Err("Invalid email length".to_string());
     }
@@ -44,7 +57,11 @@ fn validate_email(email: &str) -> Result<(), String> {
     Ok(())
 }
 
-fn validate_string_length(s: &str, max_len: usize, field_name: &str) -> Result<(), String> {
+pub(crate) fn validate_string_length(
+    s: &str,
+    max_len: usize,
+    field_name: &str,
+) -> Result<(), String> {
     if s.is_empty() {
         return Err(format!("{} cannot be empty", field_name));
     }
@@ -57,13 +74,34 @@ fn validate_string_length(s: &str, max_len: usize, field_name: &str) -> Result<(
     Ok(())
 }
 
-fn validate_uuid(id: &str) -> Result<(), String> {
+pub(crate) fn validate_uuid(id: &str) -> Result<(), String> {
     Uuid::parse_str(id)
         .map(|_| ())
         .map_err(|_| "Invalid ID format".to_string())
 }
 
-fn sanitize_string(s: &str) -> String {
+// Validates the optional slot ranking settings of a create/update request
+fn validate_recommendation_settings(
+    payload: &CreatePollRequest,
+) -> Result<(Option<f64>, Option<TieBreaker>), String> {
+    if let Some(weight) = payload.tentative_weight {
+        if !(0.0..=1.0).contains(&weight) {
+            return Err("Tentative weight must be between 0 and 1".to_string());
+        }
+    }
+
+    let tie_breaker = match &payload.tie_breaker {
+        Some(value) => Some(TieBreaker::parse(value).ok_or_else(|| {
+            "Tie breaker must be 'earliest', 'latest', 'most_available' or 'fewest_busy'"
+                .to_string()
+        })?),
+        None => None,
+    };
+
+    Ok((payload.tentative_weight, tie_breaker))
+}
+
+pub(crate) fn sanitize_string(s: &str) -> String {
     let mut escaped = String::with_capacity(s.len());
     for c in s.chars() {
         match c {
@@ -82,22 +120,288 @@ fn sanitize_string(s: &str) -> String {
     escaped
 }
 
+/// Inverse of `sanitize_string`, for stored text that goes through validation again
+/// (e.g. a poll copied into a new one)
+pub(crate) fn unsanitize_string(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
+
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+pub const MAX_PAGE_SIZE: i64 = 200;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct ListPollsQuery {
+    pub status: Option<String>,      // One status or a comma-separated list
+    pub organizer: Option<String>,   // organizer_id
+    pub participant: Option<String>, // User id among the participants
+    pub from: Option<String>,        // Polls offering a date on or after (YYYY-MM-DD)
+    pub to: Option<String>,          // ...and on or before
+    pub q: Option<String>,           // Title search
+    pub sort: Option<String>,        // "newest" (default) or "oldest"
+    pub cursor: Option<String>,      // `nextCursor` of the previous page
+    pub limit: Option<i64>,
+}
+
+/// Position after the last poll of a page: "created_at:id"
+fn parse_cursor(cursor: &str) -> Result<(i64, String), String> {
+    cursor
+        .split_once(':')
+        .and_then(|(created_at, id)| Some((created_at.parse().ok()?, id.to_string())))
+        .ok_or_else(|| "Invalid cursor".to_string())
+}
+
+/// Appends the WHERE clause shared by the page and its total count
+fn push_poll_filters(
+    builder: &mut QueryBuilder<'_, Sqlite>,
+    query: &ListPollsQuery,
+    statuses: &[PollStatus],
+) {
+    builder.push(" WHERE 1 = 1");
+    if !statuses.is_empty() {
+        builder.push(" AND status IN (");
+        let mut list = builder.separated(", ");
+        for status in statuses {
+            list.push_bind(status.as_str());
+        }
+        builder.push(")");
+    }
+    if let Some(organizer) = &query.organizer {
+        builder
+            .push(" AND organizer_id = ")
+            .push_bind(organizer.clone());
+    }
+    if let Some(participant) = &query.participant {
+        builder
+            .push(" AND EXISTS (SELECT 1 FROM participants p WHERE p.poll_id = polls.id AND p.user_id = ")
+            .push_bind(participant.clone())
+            .push(")");
+    }
+    if query.from.is_some() || query.to.is_some() {
+        builder.push(" AND EXISTS (SELECT 1 FROM json_each(polls.dates) d WHERE 1 = 1");
+        if let Some(from) = &query.from {
+            builder.push(" AND d.value >= ").push_bind(from.clone());
+        }
+        if let Some(to) = &query.to {
+            builder.push(" AND d.value <= ").push_bind(to.clone());
+        }
+        builder.push(")");
+    }
+    if let Some(search) = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        let escaped = search
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        builder
+            .push(" AND title LIKE ")
+            .push_bind(format!("%{}%", escaped))
+            .push(" ESCAPE '\\'");
+    }
+}
+
+/// GET /api/polls
+/// Newest first by default, `limit` polls per page; pass `nextCursor` back as
+/// `cursor` for the following page.
 pub async fn list_polls(
     State(pool): State<DbPool>,
-) -> Result<Json<Vec<Poll>>, (StatusCode, String)> {
-    let polls: Vec<Poll> = sqlx::query_as("SELECT * FROM polls")
+    Query(query): Query<ListPollsQuery>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let statuses = match query.status.as_deref() {
+        Some(list) => list
+            .split(',')
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| PollStatus::parse(s.trim()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| (StatusCode::BAD_REQUEST, e))?,
+        None => Vec::new(),
+    };
+    for date in [&query.from, &query.to].into_iter().flatten() {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid date format: {}", date),
+            )
+        })?;
+    }
+    if let Some(q) = &query.q {
+        validate_string_length(q, MAX_TITLE_LENGTH, "Search")
+            .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    }
+    let oldest_first = match query.sort.as_deref() {
+        None | Some("newest") => false,
+        Some("oldest") => true,
+        Some(other) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Unknown sort: {} (use 'newest' or 'oldest')", other),
+            ))
+        }
+    };
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(parse_cursor)
+        .transpose()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let mut count = QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM polls");
+    push_poll_filters(&mut count, &query, &statuses);
+    let total: i64 = count
+        .build_query_scalar()
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut page = QueryBuilder::<Sqlite>::new("SELECT * FROM polls");
+    push_poll_filters(&mut page, &query, &statuses);
+    if let Some((created_at, id)) = cursor {
+        page.push(if oldest_first {
+            " AND (created_at, id) > ("
+        } else {
+            " AND (created_at, id) < ("
+        })
+        .push_bind(created_at)
+        .push(", ")
+        .push_bind(id)
+        .push(")");
+    }
+    page.push(if oldest_first {
+        " ORDER BY created_at ASC, id ASC"
+    } else {
+        " ORDER BY created_at DESC, id DESC"
+    })
+    .push(" LIMIT ")
+    .push_bind(limit + 1);
+
+    let mut polls: Vec<Poll> = page
+        .build_query_as()
         .fetch_all(&pool)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    Ok(Json(polls))
+    let next_cursor = if polls.len() as i64 > limit {
+        polls.truncate(limit as usize);
+        polls
+            .last()
+            .map(|last| format!("{}:{}", last.created_at, last.id))
+    } else {
+        None
+    };
+
+    Ok(Json(json!({
+        "polls": polls,
+        "total": total,
+        "limit": limit,
+        "nextCursor": next_cursor
+    })))
+}
+
+/// Limits of an existing poll follow its organizer's role (see `PollLimits`)
+async fn poll_limits(pool: &DbPool, poll_id: &str) -> Result<PollLimits, (StatusCode, String)> {
+    let role: Option<String> = sqlx::query_scalar(
+        "SELECT u.role FROM polls p JOIN users u ON u.id = p.organizer_id WHERE p.id = ?",
+    )
+    .bind(poll_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Database error".to_string(),
+        )
+    })?;
+    Ok(PollLimits::for_role(role.as_deref()))
+}
+
+/// Current status and voting deadline of a poll (404 if it doesn't exist)
+async fn poll_status(
+    pool: &DbPool,
+    poll_id: &str,
+) -> Result<(PollStatus, Option<i64>), (StatusCode, String)> {
+    let row: Option<(String, Option<i64>)> =
+        sqlx::query_as("SELECT status, closes_at FROM polls WHERE id = ?")
+            .bind(poll_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Database error".to_string(),
+                )
+            })?;
+
+    let (status, closes_at) = row.ok_or((StatusCode::NOT_FOUND, "Poll not found".to_string()))?;
+    let status = PollStatus::parse(&status).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    Ok((status, closes_at))
+}
+
+/// Checks that the poll may move to `to`, returning its current status
+async fn check_transition(
+    pool: &DbPool,
+    poll_id: &str,
+    to: PollStatus,
+) -> Result<PollStatus, (StatusCode, String)> {
+    let (from, _) = poll_status(pool, poll_id).await?;
+    from.transition(to).map_err(|e| (StatusCode::CONFLICT, e))?;
+    Ok(from)
+}
+
+/// Error for an UPDATE guarded on the previous status that matched no row
+fn concurrent_change() -> (StatusCode, String) {
+    (
+        StatusCode::CONFLICT,
+        "Poll status changed in the meantime, please retry".to_string(),
+    )
 }
 
 pub async fn create_poll(
     State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
     auth_user: MaybeAuthUser,
-    Json(payload): Json<CreatePollRequest>,
+    Json(mut payload): Json<CreatePollRequest>,
 ) -> Result<Json<Value>, (StatusCode, String)> {
+    // Sessions of a campaign inherit its defaults for whatever the request leaves out
+    if let Some(campaign_id) = payload.campaign_id.clone() {
+        let campaign = campaigns::fetch_campaign(&pool, &campaign_id).await?;
+        let allowed = auth_user
+            .0
+            .as_ref()
+            .is_some_and(|user| campaigns::can_manage(user, &campaign));
+        if !allowed {
+            return Err((
+                StatusCode::FORBIDDEN,
+                "Only the campaign's DM can schedule its sessions".to_string(),
+            ));
+        }
+
+        if payload.description.trim().is_empty() {
+            payload.description = campaign.description.clone();
+        }
+        if payload.location.trim().is_empty() {
+            payload.location = campaign.default_location.clone();
+        }
+        if payload.time_preferences.is_none() && payload.time_range.is_none() {
+            payload.time_preferences = Some(json!(campaign.default_time_slots));
+        }
+        if payload.participants.is_empty() {
+            payload.participants = campaigns::campaign_member_emails(&pool, &campaign.id)
+                .await
+                .map_err(|_| {
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "Database error".to_string(),
+                    )
+                })?;
+        }
+    }
+
     // Validate inputs
     validate_string_length(&payload.title, MAX_TITLE_LENGTH, "Title")
         .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
@@ -107,83 +411,57 @@ pub async fn create_poll(
         .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
 
     // Validate dates
-    if payload.dates.is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            "At least one date is required".to_string(),
-        ));
-    }
-    if payload.dates.len() > MAX_DATES {
+    let limits = PollLimits::for_role(auth_user.0.as_ref().map(|u| u.role.as_str()));
+    limits
+        .validate_dates(&payload.dates, Utc::now().date_naive())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    // Validate participants
+    if payload.participants.len() > limits.max_participants {
         return Err((
             StatusCode::BAD_REQUEST,
-            format!("Too many dates (max: {})", MAX_DATES),
+            format!("Too many participants (max: {})", limits.max_participants),
         ));
     }
 
-    // Story 1.3: Date Logic
-    let today = Utc::now().date_naive();
-    let mut parsed_dates = Vec::new();
-    let mut min_date: Option<chrono::NaiveDate> = None;
-    let mut max_date: Option<chrono::NaiveDate> = None;
-
-    for date_str in &payload.dates {
-        let date = chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d").map_err(|_| {
-            (
-                StatusCode::BAD_REQUEST,
-                format!("Invalid date format: {}", date_str),
-            )
-        })?;
-
-        // Check for past dates
-        if date < today {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                format!("Date cannot be in the past: {}", date_str),
-            ));
-        }
-
-        parsed_dates.push(date);
-
-        match min_date {
-            Some(min) => {
-                if date < min {
-                    min_date = Some(date)
-                }
-            }
-            None => min_date = Some(date),
-        }
-        match max_date {
-            Some(max) => {
-                if date > max {
-                    max_date = Some(date)
-                }
-            }
-            None => max_date = Some(date),
-        }
+    for email in &payload.participants {
+        validate_email(email).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
     }
 
-    // Check date range length (max 14 days)
-    if let (Some(min), Some(max)) = (min_date, max_date) {
-        let duration = max.signed_duration_since(min);
-        if duration.num_days() > 14 {
+    let required_participants = payload.required_participants.clone().unwrap_or_default();
+    for email in &required_participants {
+        if !payload.participants.contains(email) {
             return Err((
                 StatusCode::BAD_REQUEST,
-                "Date range cannot exceed 14 days".to_string(),
+                format!("Required participant is not invited: {}", email),
             ));
         }
     }
 
-    // Validate participants
-    if payload.participants.len() > MAX_PARTICIPANTS {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            format!("Too many participants (max: {})", MAX_PARTICIPANTS),
-        ));
-    }
+    let (tentative_weight, tie_breaker) =
+        validate_recommendation_settings(&payload).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let tentative_weight = tentative_weight.unwrap_or(DEFAULT_TENTATIVE_WEIGHT);
+    let tie_breaker = tie_breaker.unwrap_or_default();
+
+    let poll_timezone = match payload.timezone.as_deref() {
+        Some(tz) if !tz.trim().is_empty() => timezone::parse_timezone(tz)
+            .map_err(|e| (StatusCode::BAD_REQUEST, e))?
+            .name()
+            .to_string(),
+        _ => DEFAULT_TIMEZONE.to_string(),
+    };
 
-    for email in &payload.participants {
-        validate_email(email).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
-    }
+    let quorum_rule = payload.quorum.clone().unwrap_or_default();
+    quorum::validate_quorum_rule(
+        &quorum_rule,
+        limits.max_participants,
+        Utc::now().timestamp(),
+    )
+    .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    lifecycle::validate_closes_at(payload.closes_at, Utc::now().timestamp())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    blocks::validate_session_minutes(payload.session_minutes)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
 
     let poll_id = Uuid::new_v4().to_string();
     let admin_token = Uuid::new_v4().to_string(); // Generate admin token for creator
@@ -201,22 +479,14 @@ pub async fn create_poll(
         )
     })?;
 
-    // Handle time preferences - support both old and new formats
-    let time_range_value = if let Some(time_prefs) = &payload.time_preferences {
-        // New format: per-day time preferences
-        serde_json::to_string(time_prefs).map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Failed to serialize time preferences: {}", e),
-            )
-        })?
-    } else if let Some(legacy_time_range) = &payload.time_range {
-        // Legacy format: global time range
-        legacy_time_range.clone()
-    } else {
-        // Default: empty JSON object
-        "{}".to_string()
-    };
+    // Accepts both the per-day `timePreferences` and the legacy `timeRange` list
+    let time_preferences = TimePreferences::from_request(
+        payload.time_preferences.as_ref(),
+        payload.time_range.as_deref(),
+        &payload.dates,
+    )
+    .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let time_range_value = time_preferences.to_storage();
 
     // Start transaction
     let mut tx = pool
@@ -228,7 +498,7 @@ pub async fn create_poll(
     let organizer_id = auth_user.0.map(|u| u.id);
 
     sqlx::query(
-        "INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status, admin_token, organizer_id) VALUES (?, ?, ?, ?, ?, ?, ?, 'active', ?, ?)",
+        "INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status, admin_token, organizer_id, tentative_weight, tie_breaker, quorum_min_available, quorum_deadline, timezone, campaign_id, closes_at, session_minutes) VALUES (?, ?, ?, ?, ?, ?, ?, 'active', ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(&poll_id)
     .bind(&title)
@@ -239,23 +509,38 @@ pub async fn create_poll(
     .bind(&time_range_value)
     .bind(&admin_token)
     .bind(&organizer_id)
+    .bind(tentative_weight)
+    .bind(tie_breaker.as_str())
+    .bind(quorum_rule.min_available)
+    .bind(quorum_rule.deadline)
+    .bind(&poll_timezone)
+    .bind(&payload.campaign_id)
+    .bind(payload.closes_at)
+    .bind(payload.session_minutes)
     .execute(&mut *tx)
     .await
     .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create poll".to_string()))?;
 
+    let mut invitations = Vec::new();
     for email in &payload.participants {
         let participant_id = Uuid::new_v4().to_string();
         let access_token = Uuid::new_v4().to_string(); // Generate unique access token
                                                        // For now, name is just the email prefix or "Player"
         let name = email.split('@').next().unwrap_or("Player").to_string();
         let sanitized_name = sanitize_string(&name);
+        let attendance = if required_participants.contains(email) {
+            ATTENDANCE_REQUIRED
+        } else {
+            ATTENDANCE_OPTIONAL
+        };
 
-        sqlx::query("INSERT INTO participants (id, poll_id, name, email, access_token) VALUES (?, ?, ?, ?, ?)")
+        sqlx::query("INSERT INTO participants (id, poll_id, name, email, access_token, attendance) VALUES (?, ?, ?, ?, ?, ?)")
             .bind(&participant_id)
             .bind(&poll_id)
             .bind(&sanitized_name)
             .bind(email)
             .bind(&access_token)
+            .bind(attendance)
             .execute(&mut *tx)
             .await
             .map_err(|_| {
@@ -264,6 +549,7 @@ pub async fn create_poll(
                     "Failed to add participant".to_string(),
                 )
             })?;
+        invitations.push((email.clone(), access_token));
     }
 
     // Commit transaction
@@ -282,16 +568,90 @@ pub async fn create_poll(
     )
     .await
     .unwrap_or_else(|e| tracing::error!("Activity log error: {}", e));
+    record_event(
+        &event_store,
+        &events::poll_stream(&poll_id),
+        events::Event::PollCreated(events::PollCreated {
+            poll_id: poll_id.clone(),
+            title: title.clone(),
+            dates: payload.dates.clone(),
+            organizer_id,
+            recorded_at: created_at,
+        }),
+    )
+    .await;
+
+    // Every invitee gets their personal link
+    let invitations_queued = invitations.len();
+    for (email, access_token) in invitations {
+        let title = title.clone();
+        let link = crate::core::services::email::invite_link(&poll_id, &access_token);
+        tokio::spawn(async move {
+            if let Err(e) =
+                crate::core::services::email::send_invitation_email(&email, &title, &link).await
+            {
+                tracing::error!("Failed to send invitation email to {}: {}", email, e);
+            }
+        });
+    }
 
     Ok(Json(json!({
         "id": poll_id,
-        "adminToken": admin_token
+        "adminToken": admin_token,
+        "invitationsQueued": invitations_queued
     })))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TimezoneQuery {
+    pub tz: Option<String>, // IANA zone to present slots in
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PollViewQuery {
+    pub tz: Option<String>, // IANA zone to present slots in
+    pub as_of: Option<i64>, // Unix timestamp to rebuild the votes at
+}
+
+/// Every slot of the poll (offered, voted and finalized) keyed "date_HH:MM" like the
+/// frontend cells, as seen from `viewer`.
+fn localized_slot_times(
+    poll: &Poll,
+    availability: &[Availability],
+    viewer: chrono_tz::Tz,
+) -> serde_json::Map<String, Value> {
+    let poll_tz = timezone::parse_timezone(&poll.timezone).unwrap_or(chrono_tz::Tz::UTC);
+    let dates: Vec<String> = serde_json::from_str(&poll.dates).unwrap_or_default();
+
+    let mut slots = recommendations::offered_slots(&dates, &poll.time_range, availability);
+    slots.extend(
+        availability
+            .iter()
+            .map(|a| (a.date.clone(), a.time_slot.clone())),
+    );
+    if let Some(finalized) = poll
+        .finalized_time
+        .as_deref()
+        .and_then(attendance::parse_slot_key)
+    {
+        slots.push(finalized);
+    }
+
+    let mut times = serde_json::Map::new();
+    for (date, time_slot) in slots {
+        if let Some(local) = timezone::localize_slot(&date, &time_slot, poll_tz, viewer) {
+            times.insert(format!("{}_{}", date, time_slot), json!(local));
+        }
+    }
+    times
+}
+
 pub async fn get_poll(
     State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
+    maybe_user: MaybeAuthUser,
     Path(poll_id): Path<String>,
+    Query(view): Query<PollViewQuery>,
 ) -> Result<Json<Value>, (StatusCode, String)> {
     // Validate poll_id is a valid UUID
     validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
@@ -308,8 +668,9 @@ pub async fn get_poll(
         })?
         .ok_or((StatusCode::NOT_FOUND, "Poll not found".to_string()))?;
 
+    // The organizer's own participation is always reported as required
     let participants: Vec<Participant> =
-        sqlx::query_as("SELECT id, poll_id, name, email, NULL as access_token, user_id FROM participants WHERE poll_id = ?")
+        sqlx::query_as("SELECT p.id, p.poll_id, p.name, p.email, NULL as access_token, p.user_id, CASE WHEN p.user_id IS NOT NULL AND p.user_id = polls.organizer_id THEN 'required' ELSE p.attendance END AS attendance FROM participants p JOIN polls ON polls.id = p.poll_id WHERE p.poll_id = ?")
             .bind(&poll_id)
             .fetch_all(&pool)
             .await
@@ -320,8 +681,26 @@ pub async fn get_poll(
                 )
             })?;
 
-    let availability: Vec<Availability> =
-        sqlx::query_as("SELECT * FROM availability WHERE poll_id = ?")
+    let availability: Vec<Availability> = match view.as_of {
+        // The votes as they stood at that moment, replayed from each participant's history
+        Some(as_of) => {
+            let mut availability = Vec::new();
+            for participant in &participants {
+                let history = vote_history(&event_store, &poll_id, &participant.id).await?;
+                availability.extend(events::votes_as_of(&history, as_of).into_iter().map(
+                    |(date, time_slot, status)| Availability {
+                        id: None,
+                        poll_id: poll_id.clone(),
+                        participant_id: participant.id.clone(),
+                        date,
+                        time_slot,
+                        status,
+                    },
+                ));
+            }
+            availability
+        }
+        None => sqlx::query_as("SELECT * FROM availability WHERE poll_id = ?")
             .bind(&poll_id)
             .fetch_all(&pool)
             .await
@@ -330,34 +709,98 @@ pub async fn get_poll(
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "Database error".to_string(),
                 )
-            })?;
+            })?,
+    };
+
+    let viewer_tz = timezone::viewer_timezone(
+        view.tz.as_deref(),
+        maybe_user.0.as_ref().and_then(|u| u.timezone.as_deref()),
+        &poll.timezone,
+    )
+    .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let slot_times = localized_slot_times(&poll, &availability, viewer_tz);
+    let session = session_time::from_columns(poll.finalized_start, poll.finalized_end)
+        .map(|session| session.to_value(viewer_tz));
 
     Ok(Json(json!({
         "poll": poll,
         "participants": participants,
-        "availability": availability
+        "availability": availability,
+        "viewerTimezone": viewer_tz.name(),
+        "slotTimes": slot_times,
+        "session": session,
+        "votingOpen": lifecycle::voting_open(poll.status, poll.closes_at, Utc::now().timestamp()),
+        "asOf": view.as_of
     })))
 }
 
-pub async fn join_poll(
+#[derive(Debug, Deserialize)]
+pub struct RecommendationsQuery {
+    pub limit: Option<usize>,
+}
+
+/// GET /api/polls/:id/recommendations
+/// Ranks every offered (date, time_slot) of the poll by participant availability.
+pub async fn get_poll_recommendations(
     State(pool): State<DbPool>,
     Path(poll_id): Path<String>,
-    Json(payload): Json<JoinPollRequest>,
+    Query(query): Query<RecommendationsQuery>,
 ) -> Result<Json<Value>, (StatusCode, String)> {
-    // Validate poll_id
     validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
 
-    // Validate name
-    validate_string_length(&payload.name, MAX_NAME_LENGTH, "Name")
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let poll: Poll = sqlx::query_as("SELECT * FROM polls WHERE id = ?")
+        .bind(&poll_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database error".to_string(),
+            )
+        })?
+        .ok_or((StatusCode::NOT_FOUND, "Poll not found".to_string()))?;
 
-    // Validate email if provided
-    if let Some(email) = &payload.email {
-        validate_email(email).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let ranking = recommendations::rank_poll(&pool, &poll)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to rank poll slots: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database error".to_string(),
+            )
+        })?;
+
+    let mut ranked = ranking.ranked;
+    if let Some(limit) = query.limit {
+        ranked.truncate(limit);
     }
 
-    // Verify poll exists
-    let poll_exists: Option<(i64,)> = sqlx::query_as("SELECT 1 FROM polls WHERE id = ?")
+    Ok(Json(json!({
+        "pollId": poll_id,
+        "totalParticipants": ranking.participant_ids.len(),
+        "requiredParticipants": ranking.required_ids,
+        "tentativeWeight": ranking.settings.tentative_weight,
+        "tieBreaker": ranking.settings.tie_breaker.as_str(),
+        "recommendations": ranked
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BlocksQuery {
+    pub minutes: Option<i64>, // Overrides the poll's session length
+}
+
+/// GET /api/polls/:id/blocks
+/// Back-to-back runs of slots long enough for a whole session, best first
+pub async fn get_poll_blocks(
+    State(pool): State<DbPool>,
+    Path(poll_id): Path<String>,
+    Query(query): Query<BlocksQuery>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    blocks::validate_session_minutes(query.minutes).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let poll: Poll = sqlx::query_as("SELECT * FROM polls WHERE id = ?")
         .bind(&poll_id)
         .fetch_optional(&pool)
         .await
@@ -366,12 +809,66 @@ pub async fn join_poll(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Database error".to_string(),
             )
+        })?
+        .ok_or((StatusCode::NOT_FOUND, "Poll not found".to_string()))?;
+
+    let session_minutes = query
+        .minutes
+        .or(poll.session_minutes)
+        .unwrap_or(blocks::DEFAULT_SLOT_MINUTES);
+
+    let ranking = recommendations::rank_poll(&pool, &poll)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load poll votes: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database error".to_string(),
+            )
         })?;
 
-    if poll_exists.is_none() {
-        return Err((StatusCode::NOT_FOUND, "Poll not found".to_string()));
+    let dates: Vec<String> = serde_json::from_str(&poll.dates).unwrap_or_default();
+    let offered = recommendations::offered_slots(&dates, &poll.time_range, &ranking.availability);
+    let found = blocks::contiguous_blocks(
+        &offered,
+        &ranking.participant_ids,
+        &ranking.availability,
+        session_minutes,
+    );
+
+    Ok(Json(json!({
+        "pollId": poll_id,
+        "sessionMinutes": session_minutes,
+        "totalParticipants": ranking.participant_ids.len(),
+        "everyoneAvailable": found.iter().filter(|b| b.everyone_available).count(),
+        "blocks": found
+    })))
+}
+
+pub async fn join_poll(
+    State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
+    maybe_user: MaybeAuthUser,
+    Path(poll_id): Path<String>,
+    Json(payload): Json<JoinPollRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    // Validate poll_id
+    validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    // Validate name
+    validate_string_length(&payload.name, MAX_NAME_LENGTH, "Name")
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    // Validate email if provided
+    if let Some(email) = &payload.email {
+        validate_email(email).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
     }
 
+    // Verify poll exists and still accepts players
+    let (status, closes_at) = poll_status(&pool, &poll_id).await?;
+    lifecycle::ensure_voting_open(status, closes_at, Utc::now().timestamp())
+        .map_err(|e| (StatusCode::CONFLICT, e))?;
+
     // Check if this email exists in the participants table for this poll
 
     // Transactional logic for join
@@ -408,6 +905,17 @@ pub async fn join_poll(
             tx.commit()
                 .await
                 .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            record_event(
+                &event_store,
+                &events::participant_stream(&poll_id, &new_id),
+                events::Event::ParticipantJoined(events::ParticipantJoined {
+                    poll_id: poll_id.clone(),
+                    participant_id: new_id.clone(),
+                    name: sanitized_name.clone(),
+                    recorded_at: Utc::now().timestamp(),
+                }),
+            )
+            .await;
             (new_id, new_token)
         }
         Err(_) => {
@@ -420,9 +928,15 @@ pub async fn join_poll(
 
             if let Some(email) = &payload.email {
                 // CASE 1: EXISTING PARTICIPANT (Fallback)
-                // Fetch existing ID and Token
-                let existing: (String, String) = sqlx::query_as(
-                    "SELECT id, access_token FROM participants WHERE poll_id = ? AND email = ?",
+                // Knowing an address is not enough to vote as its owner, and neither is an
+                // account registered with it: only the account the row is linked to gets the
+                // token back, everyone else is pointed to the emailed link.
+                let (existing_id, existing_token, existing_user): (
+                    String,
+                    Option<String>,
+                    Option<String>,
+                ) = sqlx::query_as(
+                    "SELECT id, access_token, user_id FROM participants WHERE poll_id = ? AND email = ?",
                 )
                 .bind(&poll_id)
                 .bind(email)
@@ -435,20 +949,37 @@ pub async fn join_poll(
                     )
                 })?;
 
+                let signed_in_as_owner = maybe_user
+                    .0
+                    .as_ref()
+                    .is_some_and(|user| existing_user.as_deref() == Some(user.id.as_str()));
+                if !signed_in_as_owner {
+                    return Err((
+                        StatusCode::CONFLICT,
+                        "This email already joined the poll. Use your personal link or request a new one".to_string(),
+                    ));
+                }
+
+                // Participants added with an account may not have a token yet
+                let access_token = existing_token.unwrap_or_else(|| Uuid::new_v4().to_string());
+
                 // Update name
-                sqlx::query("UPDATE participants SET name = ? WHERE id = ?")
-                    .bind(&sanitized_name)
-                    .bind(&existing.0)
-                    .execute(&pool)
-                    .await
-                    .map_err(|_| {
-                        (
-                            StatusCode::INTERNAL_SERVER_ERROR,
-                            "Failed to update participant name".to_string(),
-                        )
-                    })?;
+                sqlx::query(
+                    "UPDATE participants SET name = ?, access_token = COALESCE(access_token, ?) WHERE id = ?",
+                )
+                .bind(&sanitized_name)
+                .bind(&access_token)
+                .bind(&existing_id)
+                .execute(&pool)
+                .await
+                .map_err(|_| {
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "Failed to update participant name".to_string(),
+                    )
+                })?;
 
-                existing
+                (existing_id, access_token)
             } else {
                 return Err((
                     StatusCode::INTERNAL_SERVER_ERROR,
@@ -465,8 +996,261 @@ pub async fn join_poll(
     })))
 }
 
+/// POST /api/polls/:id/recover
+/// Emails an invitee a new personal link. The old token stops working, and the answer
+/// is the same whether or not the address was invited.
+pub async fn recover_link(
+    State(pool): State<DbPool>,
+    Path(poll_id): Path<String>,
+    Json(payload): Json<models::RecoverLinkRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let email = payload.email.trim().to_lowercase();
+    validate_email(&email).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let title: String = sqlx::query_scalar("SELECT title FROM polls WHERE id = ?")
+        .bind(&poll_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database error".to_string(),
+            )
+        })?
+        .ok_or((StatusCode::NOT_FOUND, "Poll not found".to_string()))?;
+
+    let access_token = Uuid::new_v4().to_string();
+    let result = sqlx::query(
+        "UPDATE participants SET access_token = ? WHERE poll_id = ? AND LOWER(email) = ?",
+    )
+    .bind(&access_token)
+    .bind(&poll_id)
+    .bind(&email)
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Database error".to_string(),
+        )
+    })?;
+
+    if result.rows_affected() > 0 {
+        let link = crate::core::services::email::invite_link(&poll_id, &access_token);
+        tokio::spawn(async move {
+            if let Err(e) =
+                crate::core::services::email::send_recovery_email(&email, &title, &link).await
+            {
+                tracing::error!("Failed to send recovery email to {}: {}", email, e);
+            }
+        });
+    }
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "If this email was invited, a new personal link is on its way"
+    })))
+}
+
+/// GET /api/invites/:token
+/// Who an invitation link belongs to, so the page can vote as that participant
+pub async fn get_invite(
+    State(pool): State<DbPool>,
+    Path(token): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    validate_uuid(&token).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let row: Option<(String, String, String, Option<String>)> =
+        sqlx::query_as("SELECT id, poll_id, name, email FROM participants WHERE access_token = ?")
+            .bind(&token)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Database error".to_string(),
+                )
+            })?;
+    let (participant_id, poll_id, name, email) =
+        row.ok_or((StatusCode::NOT_FOUND, "Invitation not found".to_string()))?;
+
+    Ok(Json(json!({
+        "pollId": poll_id,
+        "participantId": participant_id,
+        "name": name,
+        "email": email
+    })))
+}
+
+/// Vote history write failures: a concurrent change of the same participant's votes is
+/// a conflict the client can retry
+fn event_store_error(e: anyhow::Error) -> (StatusCode, String) {
+    if e.downcast_ref::<ConcurrencyError>().is_some() {
+        return (
+            StatusCode::CONFLICT,
+            "Availability was changed at the same time, please retry".to_string(),
+        );
+    }
+    tracing::error!("Event store error: {}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Failed to record vote history".to_string(),
+    )
+}
+
+/// A participant's vote history, oldest first
+async fn vote_history(
+    event_store: &RedbEventStore,
+    poll_id: &str,
+    participant_id: &str,
+) -> Result<Vec<events::Event>, (StatusCode, String)> {
+    fn read_error(e: impl std::fmt::Display) -> (StatusCode, String) {
+        tracing::error!("Failed to read vote history: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to read vote history".to_string(),
+        )
+    }
+
+    let stream_id = events::participant_stream(poll_id, participant_id);
+    event_store
+        .read_stream(&stream_id)
+        .await
+        .map_err(read_error)?
+        .iter()
+        .map(|bytes| events::Event::from_bytes(&stream_id, bytes).map_err(read_error))
+        .collect()
+}
+
+/// Appends an event describing a change already committed to SQL. Best-effort: the SQL
+/// rows stay the source of truth, so like the activity log a failure is only logged and
+/// the request (or job) still succeeds, leaving a gap in the history.
+pub(crate) async fn record_event(
+    event_store: &RedbEventStore,
+    stream_id: &str,
+    event: events::Event,
+) {
+    record_events(event_store, stream_id, vec![event]).await
+}
+
+/// Best-effort append of `events`, in order, to `stream_id` (see `record_event`). They
+/// describe a change that is already committed, so a concurrent write only pushes them
+/// after it instead of refusing them. Participant streams are also versioned by
+/// `update_availability`, which answers 409 if one of these lands in between.
+async fn record_events(event_store: &RedbEventStore, stream_id: &str, events: Vec<events::Event>) {
+    if events.is_empty() {
+        return;
+    }
+    let result = async {
+        let bytes = events
+            .iter()
+            .map(events::Event::to_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
+        let bytes: Vec<&[u8]> = bytes.iter().map(Vec::as_slice).collect();
+        for _ in 0..3 {
+            let version = event_store.current_version(stream_id).await?;
+            match event_store.append_all(stream_id, &bytes, version).await {
+                Err(e) if e.downcast_ref::<ConcurrencyError>().is_some() => continue,
+                other => return other.map(|_| ()),
+            }
+        }
+        Err(anyhow::anyhow!("The stream kept changing"))
+    }
+    .await;
+
+    if let Err(e) = result {
+        tracing::error!("Failed to record events in {}: {}", stream_id, e);
+    }
+}
+
+/// Records votes moved or dropped outside `update_availability` (a poll edit, see
+/// `reconcile_availability`, or a removed participant) in each participant's vote
+/// history: a moved vote is cleared on its old slot and cast again on the new one.
+pub(crate) async fn record_vote_fates(
+    event_store: &RedbEventStore,
+    changes: &[(Availability, reconcile::VoteFate)],
+) {
+    type Votes = Vec<(String, String, String)>;
+    let mut per_participant: BTreeMap<(&str, &str), (Votes, Votes)> = BTreeMap::new();
+    for (vote, fate) in changes {
+        let (before, after) = per_participant
+            .entry((vote.poll_id.as_str(), vote.participant_id.as_str()))
+            .or_default();
+        before.push((
+            vote.date.clone(),
+            vote.time_slot.clone(),
+            vote.status.clone(),
+        ));
+        if let reconcile::VoteFate::Moved { date, time_slot } = fate {
+            after.push((date.clone(), time_slot.clone(), vote.status.clone()));
+        }
+    }
+
+    let recorded_at = Utc::now().timestamp();
+    for ((poll_id, participant_id), (before, after)) in per_participant {
+        let stream_id = events::participant_stream(poll_id, participant_id);
+        match events::vote_changes(poll_id, participant_id, &before, &after, recorded_at) {
+            Ok(changes) => record_events(event_store, &stream_id, changes).await,
+            Err(e) => tracing::error!("Failed to record vote events in {}: {}", stream_id, e),
+        }
+    }
+}
+
+/// GET /api/polls/:id/history
+/// Every vote change of every participant, oldest first, to settle "I was available
+/// when you finalized" disputes
+pub async fn get_poll_history(
+    State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
+    Path(poll_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    poll_status(&pool, &poll_id).await?;
+
+    let participants: Vec<(String, String)> =
+        sqlx::query_as("SELECT id, name FROM participants WHERE poll_id = ? ORDER BY rowid")
+            .bind(&poll_id)
+            .fetch_all(&pool)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Database error".to_string(),
+                )
+            })?;
+
+    let mut history = Vec::with_capacity(participants.len());
+    for (participant_id, name) in participants {
+        let changes: Vec<Value> = vote_history(&event_store, &poll_id, &participant_id)
+            .await?
+            .into_iter()
+            .filter_map(|event| match event {
+                events::Event::VoteCast(vote) => Some(json!({
+                    "date": vote.date,
+                    "timeSlot": vote.slot,
+                    "status": vote.vote.status(),
+                    "recordedAt": vote.recorded_at
+                })),
+                _ => None,
+            })
+            .collect();
+        history.push(json!({
+            "participantId": participant_id,
+            "name": name,
+            "changes": changes
+        }));
+    }
+
+    Ok(Json(json!({
+        "pollId": poll_id,
+        "participants": history
+    })))
+}
+
 pub async fn update_availability(
     State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
     maybe_user: crate::auth::MaybeAuthUser,
     Path((poll_id, participant_id)): Path<(String, String)>,
     Json(payload): Json<UpdateAvailabilityRequest>,
@@ -476,12 +1260,13 @@ pub async fn update_availability(
     validate_uuid(&participant_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
 
     // Validate availability entries count
-    if payload.availability.len() > MAX_AVAILABILITY_ENTRIES {
+    let limits = poll_limits(&pool, &poll_id).await?;
+    if payload.availability.len() > limits.max_availability_entries {
         return Err((
             StatusCode::BAD_REQUEST,
             format!(
                 "Too many availability entries (max: {})",
-                MAX_AVAILABILITY_ENTRIES
+                limits.max_availability_entries
             ),
         ));
     }
@@ -502,8 +1287,8 @@ pub async fn update_availability(
         return Err((StatusCode::NOT_FOUND, "Poll not found".to_string()));
     }
 
-    let participant_exists: Option<(i64,)> =
-        sqlx::query_as("SELECT 1 FROM participants WHERE id = ? AND poll_id = ?")
+    let participant_exists: Option<i64> =
+        sqlx::query_scalar("SELECT 1 FROM participants WHERE id = ? AND poll_id = ?")
             .bind(&participant_id)
             .bind(&poll_id)
             .fetch_optional(&pool)
@@ -574,17 +1359,23 @@ pub async fn update_availability(
         ));
     }
 
-    // Validate dates against poll dates to prevent junk data injection
-    let poll_dates_json: String = sqlx::query_scalar("SELECT dates FROM polls WHERE id = ?")
-        .bind(&poll_id)
-        .fetch_one(&pool)
-        .await
-        .map_err(|_| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Database error".to_string(),
-            )
-        })?;
+    // Validate dates and slots against the poll to prevent junk data injection
+    let (poll_dates_json, time_range): (String, String) =
+        sqlx::query_as("SELECT dates, time_range FROM polls WHERE id = ?")
+            .bind(&poll_id)
+            .fetch_one(&pool)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Database error".to_string(),
+                )
+            })?;
+    // Votes are frozen once the poll is closed or its deadline has passed
+    let (status, closes_at) = poll_status(&pool, &poll_id).await?;
+    lifecycle::ensure_voting_open(status, closes_at, Utc::now().timestamp())
+        .map_err(|e| (StatusCode::CONFLICT, e))?;
+    let time_preferences = TimePreferences::from_stored(&time_range);
 
     let valid_dates: Vec<String> = serde_json::from_str(&poll_dates_json).map_err(|_| {
         (
@@ -599,6 +1390,27 @@ pub async fn update_availability(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
+    // The votes being replaced, and the version of the participant's vote history they
+    // correspond to
+    let stream_id = events::participant_stream(&poll_id, &participant_id);
+    let stream_version = event_store
+        .current_version(&stream_id)
+        .await
+        .map_err(event_store_error)?;
+    let previous_votes: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT date, time_slot, status FROM availability WHERE poll_id = ? AND participant_id = ? ORDER BY id",
+    )
+    .bind(&poll_id)
+    .bind(&participant_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Database error".to_string(),
+        )
+    })?;
+
     // Clear existing availability for this participant in this poll
     sqlx::query("DELETE FROM availability WHERE poll_id = ? AND participant_id = ?")
         .bind(&poll_id)
@@ -612,6 +1424,7 @@ pub async fn update_availability(
             )
         })?;
 
+    let mut new_votes = Vec::with_capacity(payload.availability.len());
     for entry in payload.availability {
         // Validate each entry
         validate_string_length(&entry.date, 50, "Date")
@@ -620,6 +1433,12 @@ pub async fn update_availability(
             .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
         validate_string_length(&entry.status, 20, "Status")
             .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        if events::Vote::from_status(&entry.status).is_none() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Invalid status: {}", entry.status),
+            ));
+        }
 
         // Check if date is valid for this poll
         if !valid_dates.contains(&entry.date) {
@@ -629,6 +1448,11 @@ pub async fn update_availability(
             ));
         }
 
+        // ...and that the slot is one the poll offers on that date
+        time_preferences
+            .validate_slot(&entry.date, &entry.time_slot)
+            .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
         sqlx::query(
             "INSERT INTO availability (poll_id, participant_id, date, time_slot, status) VALUES (?, ?, ?, ?, ?)",
         )
@@ -640,12 +1464,49 @@ pub async fn update_availability(
         .execute(&mut *tx)
         .await
         .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Failed to update availability".to_string()))?;
+        new_votes.push((entry.date, entry.time_slot, entry.status));
     }
 
-    // Commit transaction
-    tx.commit()
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    // Record every changed vote before committing: if another request wrote to the
+    // history since we read it, this one is refused and the SQL change rolled back
+    let changes = events::vote_changes(
+        &poll_id,
+        &participant_id,
+        &previous_votes,
+        &new_votes,
+        Utc::now().timestamp(),
+    )
+    .map_err(|e| (StatusCode::BAD_REQUEST, e))?
+    .iter()
+    .map(events::Event::to_bytes)
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| event_store_error(e.into()))?;
+    let recorded = !changes.is_empty();
+    if recorded {
+        let changes: Vec<&[u8]> = changes.iter().map(Vec::as_slice).collect();
+        event_store
+            .append_all(&stream_id, &changes, stream_version)
+            .await
+            .map_err(event_store_error)?;
+    }
+
+    // Commit transaction. If that fails the votes recorded above never took effect, so
+    // they are taken back in the history too.
+    if let Err(e) = tx.commit().await {
+        if recorded {
+            match events::vote_changes(
+                &poll_id,
+                &participant_id,
+                &new_votes,
+                &previous_votes,
+                Utc::now().timestamp(),
+            ) {
+                Ok(reverts) => record_events(&event_store, &stream_id, reverts).await,
+                Err(e) => tracing::error!("Failed to revert vote events in {}: {}", stream_id, e),
+            }
+        }
+        return Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()));
+    }
 
     // Log activity: response submitted
     // Fetch participant and poll info for logging
@@ -682,8 +1543,13 @@ mod tests {
     use crate::core::models::{AvailabilityEntry, User};
     use axum::extract::State;
     use sqlx::sqlite::SqlitePoolOptions;
+    use std::collections::HashMap;
     use uuid::Uuid;
 
+    fn event_store() -> Extension<Arc<RedbEventStore>> {
+        Extension(Arc::new(RedbEventStore::in_memory().unwrap()))
+    }
+
     async fn setup_test_db() -> DbPool {
         let pool = SqlitePoolOptions::new()
             .max_connections(1)
@@ -692,13 +1558,13 @@ mod tests {
             .expect("Failed to connect to memory db");
 
         // Execute migrations (simplified for this test context)
-        sqlx::query("CREATE TABLE polls (id TEXT PRIMARY KEY, title TEXT NOT NULL, description TEXT NOT NULL, location TEXT NOT NULL, created_at INTEGER NOT NULL, dates TEXT NOT NULL, time_range TEXT NOT NULL, status TEXT NOT NULL DEFAULT 'active', finalized_at INTEGER, finalized_time TEXT, notes TEXT, admin_token TEXT, organizer_id TEXT)")
+        sqlx::query("CREATE TABLE polls (id TEXT PRIMARY KEY, title TEXT NOT NULL, description TEXT NOT NULL, location TEXT NOT NULL, created_at INTEGER NOT NULL, dates TEXT NOT NULL, time_range TEXT NOT NULL, status TEXT NOT NULL DEFAULT 'active', finalized_at INTEGER, finalized_time TEXT, finalized_start INTEGER, finalized_end INTEGER, notes TEXT, admin_token TEXT, organizer_id TEXT, tentative_weight REAL NOT NULL DEFAULT 0.5, tie_breaker TEXT NOT NULL DEFAULT 'earliest', quorum_min_available INTEGER, quorum_deadline INTEGER, timezone TEXT NOT NULL DEFAULT 'UTC', campaign_id TEXT, closes_at INTEGER, cancel_reason TEXT, session_minutes INTEGER)")
             .execute(&pool).await.unwrap();
-        sqlx::query("CREATE TABLE participants (id TEXT PRIMARY KEY, poll_id TEXT NOT NULL, name TEXT NOT NULL, email TEXT, access_token TEXT UNIQUE, user_id TEXT, FOREIGN KEY (poll_id) REFERENCES polls (id))")
+        sqlx::query("CREATE TABLE participants (id TEXT PRIMARY KEY, poll_id TEXT NOT NULL, name TEXT NOT NULL, email TEXT, access_token TEXT UNIQUE, user_id TEXT, attendance TEXT NOT NULL DEFAULT 'optional', FOREIGN KEY (poll_id) REFERENCES polls (id))")
             .execute(&pool).await.unwrap();
         sqlx::query("CREATE TABLE availability (id INTEGER PRIMARY KEY AUTOINCREMENT, poll_id TEXT NOT NULL, participant_id TEXT NOT NULL, date TEXT NOT NULL, time_slot TEXT NOT NULL, status TEXT NOT NULL, FOREIGN KEY (poll_id) REFERENCES polls (id), FOREIGN KEY (participant_id) REFERENCES participants (id))")
             .execute(&pool).await.unwrap();
-        sqlx::query("CREATE TABLE users (id TEXT PRIMARY KEY, email TEXT NOT NULL UNIQUE, password_hash TEXT NOT NULL, name TEXT NOT NULL, role TEXT NOT NULL DEFAULT 'player', created_at INTEGER NOT NULL, last_login INTEGER, phone TEXT)")
+        sqlx::query("CREATE TABLE users (id TEXT PRIMARY KEY, email TEXT NOT NULL UNIQUE, password_hash TEXT NOT NULL, name TEXT NOT NULL, role TEXT NOT NULL DEFAULT 'player', created_at INTEGER NOT NULL, last_login INTEGER, phone TEXT, timezone TEXT)")
             .execute(&pool).await.unwrap();
         sqlx::query("CREATE TABLE user_sessions (id TEXT PRIMARY KEY, user_id TEXT NOT NULL, token TEXT NOT NULL UNIQUE, expires_at INTEGER NOT NULL, created_at INTEGER NOT NULL, FOREIGN KEY (user_id) REFERENCES users (id))")
             .execute(&pool).await.unwrap();
@@ -723,9 +1589,16 @@ mod tests {
             time_range: None,
             time_preferences: None,
             participants: vec!["p1@test.com".to_string()],
+            ..Default::default()
         };
 
-        let res = create_poll(State(pool.clone()), MaybeAuthUser(None), Json(req)).await;
+        let res = create_poll(
+            State(pool.clone()),
+            event_store(),
+            MaybeAuthUser(None),
+            Json(req),
+        )
+        .await;
         assert!(res.is_ok());
 
         let json_val = res.unwrap().0;
@@ -764,8 +1637,15 @@ mod tests {
             time_range: None,
             time_preferences: None,
             participants: vec![],
+            ..Default::default()
         };
-        let res = create_poll(State(pool.clone()), MaybeAuthUser(None), Json(req)).await;
+        let res = create_poll(
+            State(pool.clone()),
+            event_store(),
+            MaybeAuthUser(None),
+            Json(req),
+        )
+        .await;
         assert!(res.is_ok());
 
         // 2. Past date
@@ -778,8 +1658,15 @@ mod tests {
             time_range: None,
             time_preferences: None,
             participants: vec![],
+            ..Default::default()
         };
-        let res_past = create_poll(State(pool.clone()), MaybeAuthUser(None), Json(req_past)).await;
+        let res_past = create_poll(
+            State(pool.clone()),
+            event_store(),
+            MaybeAuthUser(None),
+            Json(req_past),
+        )
+        .await;
         assert!(res_past.is_err());
         assert_eq!(res_past.err().unwrap().0, StatusCode::BAD_REQUEST);
 
@@ -794,8 +1681,15 @@ mod tests {
             time_range: None,
             time_preferences: None,
             participants: vec![],
+            ..Default::default()
         };
-        let res_long = create_poll(State(pool.clone()), MaybeAuthUser(None), Json(req_long)).await;
+        let res_long = create_poll(
+            State(pool.clone()),
+            event_store(),
+            MaybeAuthUser(None),
+            Json(req_long),
+        )
+        .await;
         assert!(res_long.is_err());
         assert_eq!(res_long.err().unwrap().0, StatusCode::BAD_REQUEST);
     }
@@ -828,11 +1722,17 @@ mod tests {
             time_range: None,
             time_preferences: None,
             participants: vec![],
+            ..Default::default()
         };
 
-        let poll_res_json = create_poll(State(pool.clone()), MaybeAuthUser(None), Json(create_req))
-            .await
-            .unwrap();
+        let poll_res_json = create_poll(
+            State(pool.clone()),
+            event_store(),
+            MaybeAuthUser(None),
+            Json(create_req),
+        )
+        .await
+        .unwrap();
         let poll_id = poll_res_json
             .0
             .get("id")
@@ -870,6 +1770,7 @@ mod tests {
             created_at: 0,
             last_login: None,
             phone: None,
+            timezone: None,
         });
 
         // 4. Join Poll
@@ -884,9 +1785,15 @@ mod tests {
             email: Some("p@test.com".to_string()),
         };
 
-        let join_res = join_poll(State(pool.clone()), Path(poll_id.clone()), Json(join_req))
-            .await
-            .unwrap();
+        let join_res = join_poll(
+            State(pool.clone()),
+            event_store(),
+            MaybeAuthUser(None),
+            Path(poll_id.clone()),
+            Json(join_req),
+        )
+        .await
+        .unwrap();
 
         // join_res is Json<Participant> or similar?
         // extract access_token from response.
@@ -912,6 +1819,7 @@ mod tests {
 
         let update_res = update_availability(
             State(pool.clone()),
+            event_store(),
             crate::auth::MaybeAuthUser(None),
             Path((poll_id.clone(), participant_id.clone())),
             Json(avail_req),
@@ -920,9 +1828,18 @@ mod tests {
         assert!(update_res.is_ok());
 
         // 6. Verify Availability
-        let get_res = get_poll(State(pool.clone()), Path(poll_id.clone()))
-            .await
-            .unwrap();
+        let get_res = get_poll(
+            State(pool.clone()),
+            event_store(),
+            crate::auth::MaybeAuthUser(None),
+            Path(poll_id.clone()),
+            Query(PollViewQuery {
+                tz: None,
+                as_of: None,
+            }),
+        )
+        .await
+        .unwrap();
         let get_val = get_res.0;
         let availability_arr = get_val.get("availability").unwrap().as_array().unwrap();
 
@@ -938,31 +1855,29 @@ mod tests {
 
         // 1. Create Poll
         let poll_id = Uuid::new_v4().to_string();
-        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status) VALUES (?, 'Title', 'Desc', 'Loc', 0, '[]', '{}', 'active')")
+        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status) VALUES (?, 'Title', 'Desc', 'Loc', 0, '[\"2026-01-01\"]', '{}', 'active')")
             .bind(&poll_id)
             .execute(&pool).await.unwrap();
 
-        // 2. Mock Admin
-        let admin = crate::core::models::Admin {
-            id: "admin1".to_string(),
-            username: "admin".to_string(),
-            password_hash: "hash".to_string(),
-            email: Some("admin@example.com".to_string()),
-            role: "admin".to_string(),
-            created_at: 0,
+        // 2. Organizer holding the poll's admin token
+        let organizer = crate::auth::PollOrganizer {
+            poll_id: poll_id.clone(),
+            user: None,
         };
-        let admin_user = crate::auth::AdminUser(admin);
 
         // 3. Finalize Poll
         let req = models::FinalizePollRequest {
-            finalized_time: "2026-01-01 20:00".to_string(),
+            start: Some("2026-01-01 20:00".to_string()),
             notes: Some("Let's play!".to_string()),
+            ..Default::default()
         };
 
         let res = finalize_poll(
             State(pool.clone()),
-            admin_user,
+            event_store(),
+            organizer,
             Path(poll_id.clone()),
+            Query(TimezoneQuery { tz: None }),
             Json(req),
         )
         .await;
@@ -979,24 +1894,23 @@ mod tests {
         assert_eq!(status, "finalized");
 
         // 5. Try to finalize again (should fail)
-        let admin_user_2 = crate::auth::AdminUser(crate::core::models::Admin {
-            id: "admin1".to_string(),
-            username: "admin".to_string(),
-            password_hash: "hash".to_string(),
-            email: Some("admin@example.com".to_string()),
-            role: "admin".to_string(),
-            created_at: 0,
-        });
+        let organizer_2 = crate::auth::PollOrganizer {
+            poll_id: poll_id.clone(),
+            user: None,
+        };
 
         let req_2 = models::FinalizePollRequest {
-            finalized_time: "2026-01-02 20:00".to_string(),
+            start: Some("2026-01-02 20:00".to_string()),
             notes: None,
+            ..Default::default()
         };
 
         let res_2 = finalize_poll(
             State(pool.clone()),
-            admin_user_2,
+            event_store(),
+            organizer_2,
             Path(poll_id.clone()),
+            Query(TimezoneQuery { tz: None }),
             Json(req_2),
         )
         .await;
@@ -1005,77 +1919,1078 @@ mod tests {
         assert_eq!(res_2.err().unwrap().0, StatusCode::CONFLICT);
     }
 
-    #[test]
-    fn test_sanitize_string_xss() {
-        let input = "<script>alert('XSS')</script>";
-        let expected = "&lt;script&gt;alert(&#x27;XSS&#x27;)&lt;/script&gt;";
-        assert_eq!(sanitize_string(input), expected);
-
-        let input_quotes = "User \"Name\"";
-        let expected_quotes = "User &quot;Name&quot;";
-        assert_eq!(sanitize_string(input_quotes), expected_quotes);
-    }
-
     #[tokio::test]
-    async fn test_update_availability_owner() {
+    async fn test_finalize_poll_rejects_busy_required_participant() {
         let pool = setup_test_db().await;
 
-        // 1. Create Poll
+        // Poll organized by the DM, who also joined as a participant
         let poll_id = Uuid::new_v4().to_string();
-        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status) VALUES (?, 'Title', 'Desc', 'Loc', 0, '[\"2023-12-01\"]', '{}', 'active')")
+        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status, organizer_id) VALUES (?, 'Title', 'Desc', 'Loc', 0, '[\"2026-01-01\"]', '{}', 'active', 'dm-user')")
             .bind(&poll_id)
             .execute(&pool).await.unwrap();
-
-        // 2. Create User
-        let user_id = Uuid::new_v4().to_string();
-        sqlx::query("INSERT INTO users (id, email, password_hash, name, role, created_at) VALUES (?, 'owner@test.com', 'hash', 'Owner', 'player', 0)")
-            .bind(&user_id)
+        sqlx::query("INSERT INTO participants (id, poll_id, name, user_id) VALUES ('dm-part', ?, 'DM', 'dm-user')")
+            .bind(&poll_id)
             .execute(&pool).await.unwrap();
-
-        // 3. Create Participant LINKED to User
-        let participant_id = Uuid::new_v4().to_string();
-        sqlx::query("INSERT INTO participants (id, poll_id, name, email, user_id) VALUES (?, ?, 'Owner P', 'owner@test.com', ?)")
-            .bind(&participant_id)
+        sqlx::query("INSERT INTO participants (id, poll_id, name, attendance) VALUES ('p1', ?, 'Player', 'optional')")
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO availability (poll_id, participant_id, date, time_slot, status) VALUES (?, 'dm-part', '2026-01-01', '20:00', 'busy'), (?, 'p1', '2026-01-01', '21:00', 'busy')")
+            .bind(&poll_id)
             .bind(&poll_id)
-            .bind(&user_id)
             .execute(&pool).await.unwrap();
 
-        // 4. Update Availability as User (WITHOUT Token)
-        let avail_req = models::UpdateAvailabilityRequest {
-            availability: vec![models::AvailabilityEntry {
-                date: "2023-12-01".to_string(),
-                time_slot: "18:00".to_string(),
-                status: "available".to_string(),
-            }],
-            access_token: None, // No token provided!
+        let organizer = || crate::auth::PollOrganizer {
+            poll_id: poll_id.clone(),
+            user: None,
         };
 
-        // Mock AuthUser
-        let user = crate::core::models::User {
-            id: user_id.clone(),
-            email: "owner@test.com".to_string(),
-            password_hash: "hash".to_string(),
-            name: "Owner".to_string(),
-            role: "player".to_string(),
-            created_at: 0,
-            last_login: None,
-            phone: None,
-        };
-        let maybe_user = crate::auth::MaybeAuthUser(Some(user));
+        // DM is implicitly required and busy at 20:00
+        let res = finalize_poll(
+            State(pool.clone()),
+            event_store(),
+            organizer(),
+            Path(poll_id.clone()),
+            Query(TimezoneQuery { tz: None }),
+            Json(models::FinalizePollRequest {
+                start: Some("2026-01-01_20:00".to_string()),
+                notes: None,
+                ..Default::default()
+            }),
+        )
+        .await;
+        assert_eq!(res.err().unwrap().0, StatusCode::BAD_REQUEST);
 
-        let update_res = update_availability(
+        // An optional participant being busy does not block finalization
+        let res = finalize_poll(
             State(pool.clone()),
-            maybe_user,
-            Path((poll_id.clone(), participant_id.clone())),
-            Json(avail_req),
+            event_store(),
+            organizer(),
+            Path(poll_id.clone()),
+            Query(TimezoneQuery { tz: None }),
+            Json(models::FinalizePollRequest {
+                start: Some("2026-01-01_21:00".to_string()),
+                notes: None,
+                ..Default::default()
+            }),
         )
         .await;
+        assert!(res.is_ok());
+    }
 
-        // Assert SUCCESS
-        assert!(
-            update_res.is_ok(),
-            "Should allow Owner to update without token"
+    #[tokio::test]
+    async fn test_finalize_poll_structured_session() {
+        let pool = setup_test_db().await;
+
+        let poll_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status, organizer_id, timezone) VALUES (?, 'Title', 'Desc', 'Loc', 0, '[\"2030-01-10\"]', '[\"19:00\", \"20:00\", \"21:00\", \"22:00\"]', 'active', 'dm-user', 'Europe/Rome')")
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO participants (id, poll_id, name, user_id) VALUES ('dm-part', ?, 'DM', 'dm-user')")
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO availability (poll_id, participant_id, date, time_slot, status) VALUES (?, 'dm-part', '2030-01-10', '22:00', 'busy')")
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+
+        let finalize = |request: models::FinalizePollRequest| {
+            finalize_poll(
+                State(pool.clone()),
+                event_store(),
+                crate::auth::PollOrganizer {
+                    poll_id: poll_id.clone(),
+                    user: None,
+                },
+                Path(poll_id.clone()),
+                Query(TimezoneQuery { tz: None }),
+                Json(request),
+            )
+        };
+
+        // Not an offered date, nor an offered slot
+        for start in ["2030-01-11T20:00", "2030-01-10T20:30"] {
+            let res = finalize(models::FinalizePollRequest {
+                start: Some(start.to_string()),
+                ..Default::default()
+            })
+            .await;
+            assert_eq!(res.unwrap_err().0, StatusCode::BAD_REQUEST);
+        }
+
+        // Four hours from 19:00 run into the DM's busy 22:00 slot
+        let (status, message) = finalize(models::FinalizePollRequest {
+            start: Some("2030-01-10T19:00".to_string()),
+            duration_minutes: Some(240),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(message, "Required participants are busy at this time: DM");
+
+        // Start given in London time: 18:00 there is 19:00 in Rome
+        let res = finalize(models::FinalizePollRequest {
+            start: Some("2030-01-10T18:00".to_string()),
+            end: Some("2030-01-10T21:00".to_string()),
+            timezone: Some("Europe/London".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .0;
+        assert_eq!(res["finalizedTime"], "2030-01-10_19:00");
+        assert_eq!(res["session"]["start"], "2030-01-10T19:00:00+01:00");
+        assert_eq!(res["session"]["durationMinutes"], 180);
+
+        let stored: (String, i64, i64) = sqlx::query_as(
+            "SELECT finalized_time, finalized_start, finalized_end FROM polls WHERE id = ?",
+        )
+        .bind(&poll_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(stored.0, "2030-01-10_19:00");
+        assert_eq!(stored.2 - stored.1, 3 * 3600);
+    }
+
+    #[tokio::test]
+    async fn test_poll_blocks_use_session_length() {
+        let pool = setup_test_db().await;
+
+        let poll_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status, session_minutes) VALUES (?, 'Title', 'Desc', 'Loc', 0, '[\"2030-01-10\"]', '[\"19:00\", \"20:00\", \"21:00\", \"22:00\"]', 'active', 180)")
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+        for participant in ["p1", "p2"] {
+            sqlx::query("INSERT INTO participants (id, poll_id, name) VALUES (?, ?, 'Player')")
+                .bind(participant)
+                .bind(&poll_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        sqlx::query("INSERT INTO availability (poll_id, participant_id, date, time_slot, status) VALUES (?, 'p1', '2030-01-10', '19:00', 'busy'), (?, 'p1', '2030-01-10', '20:00', 'available'), (?, 'p1', '2030-01-10', '21:00', 'available'), (?, 'p1', '2030-01-10', '22:00', 'available'), (?, 'p2', '2030-01-10', '20:00', 'available'), (?, 'p2', '2030-01-10', '21:00', 'available'), (?, 'p2', '2030-01-10', '22:00', 'available')")
+            .bind(&poll_id)
+            .bind(&poll_id)
+            .bind(&poll_id)
+            .bind(&poll_id)
+            .bind(&poll_id)
+            .bind(&poll_id)
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+
+        let res = get_poll_blocks(
+            State(pool.clone()),
+            Path(poll_id.clone()),
+            Query(BlocksQuery { minutes: None }),
+        )
+        .await
+        .unwrap()
+        .0;
+        assert_eq!(res["sessionMinutes"], 180);
+        assert_eq!(res["everyoneAvailable"], 1);
+        assert_eq!(res["blocks"][0]["start"], "20:00");
+        assert_eq!(res["blocks"][0]["end"], "23:00");
+        assert_eq!(res["blocks"].as_array().unwrap().len(), 2);
+
+        // Nobody can do five hours in a four-hour evening
+        let res = get_poll_blocks(
+            State(pool.clone()),
+            Path(poll_id.clone()),
+            Query(BlocksQuery { minutes: Some(300) }),
+        )
+        .await
+        .unwrap()
+        .0;
+        assert!(res["blocks"].as_array().unwrap().is_empty());
+
+        // Finalizing without an end books the whole session length
+        let res = finalize_poll(
+            State(pool.clone()),
+            event_store(),
+            crate::auth::PollOrganizer {
+                poll_id: poll_id.clone(),
+                user: None,
+            },
+            Path(poll_id.clone()),
+            Query(TimezoneQuery { tz: None }),
+            Json(models::FinalizePollRequest {
+                start: Some("2030-01-10T20:00".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap()
+        .0;
+        assert_eq!(res["session"]["durationMinutes"], 180);
+    }
+
+    #[tokio::test]
+    async fn test_list_polls_filters_and_pagination() {
+        let pool = setup_test_db().await;
+
+        for (i, (title, status, date, organizer)) in [
+            ("Dragon Heist", "active", "2030-01-10", Some("dm-1")),
+            ("Curse of Strahd", "finalized", "2030-01-12", Some("dm-1")),
+            ("Tomb of Annihilation", "archived", "2030-02-01", None),
+            ("Dragon of Icespire", "active", "2030-03-01", Some("dm-2")),
+            ("100% Dungeon_crawl", "active", "2030-03-02", None),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status, organizer_id) VALUES (?, ?, 'Desc', 'Loc', ?, ?, '{}', ?, ?)")
+                .bind(format!("poll-{}", i))
+                .bind(title)
+                .bind(i as i64)
+                .bind(format!("[\"{}\"]", date))
+                .bind(status)
+                .bind(organizer)
+                .execute(&pool).await.unwrap();
+        }
+        sqlx::query("INSERT INTO participants (id, poll_id, name, user_id) VALUES ('p1', 'poll-3', 'Player', 'user-1')")
+            .execute(&pool).await.unwrap();
+
+        let list = |query: ListPollsQuery| list_polls(State(pool.clone()), Query(query));
+        let ids = |res: &Value| -> Vec<String> {
+            res["polls"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|p| p["id"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        // Newest first, two per page
+        let first = list(ListPollsQuery {
+            limit: Some(2),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .0;
+        assert_eq!(first["total"], 5);
+        assert_eq!(ids(&first), vec!["poll-4", "poll-3"]);
+        let second = list(ListPollsQuery {
+            limit: Some(2),
+            cursor: first["nextCursor"].as_str().map(|c| c.to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .0;
+        assert_eq!(ids(&second), vec!["poll-2", "poll-1"]);
+
+        let filtered = |query: ListPollsQuery| async { ids(&list(query).await.unwrap().0) };
+        assert_eq!(
+            filtered(ListPollsQuery {
+                status: Some("active,finalized".to_string()),
+                organizer: Some("dm-1".to_string()),
+                sort: Some("oldest".to_string()),
+                ..Default::default()
+            })
+            .await,
+            vec!["poll-0", "poll-1"]
+        );
+        assert_eq!(
+            filtered(ListPollsQuery {
+                participant: Some("user-1".to_string()),
+                ..Default::default()
+            })
+            .await,
+            vec!["poll-3"]
+        );
+        assert_eq!(
+            filtered(ListPollsQuery {
+                from: Some("2030-01-11".to_string()),
+                to: Some("2030-02-28".to_string()),
+                ..Default::default()
+            })
+            .await,
+            vec!["poll-2", "poll-1"]
+        );
+        assert_eq!(
+            filtered(ListPollsQuery {
+                q: Some("dragon".to_string()),
+                ..Default::default()
+            })
+            .await,
+            vec!["poll-3", "poll-0"]
+        );
+        // LIKE wildcards in the search are literal
+        assert_eq!(
+            filtered(ListPollsQuery {
+                q: Some("0% D".to_string()),
+                ..Default::default()
+            })
+            .await,
+            vec!["poll-4"]
+        );
+
+        let bad = list(ListPollsQuery {
+            status: Some("reopened".to_string()),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(bad.unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_sanitize_string_xss() {
+        let input = "<script>alert('XSS')</script>";
+        let expected = "&lt;script&gt;alert(&#x27;XSS&#x27;)&lt;/script&gt;";
+        assert_eq!(sanitize_string(input), expected);
+
+        let input_quotes = "User \"Name\"";
+        let expected_quotes = "User &quot;Name&quot;";
+        assert_eq!(sanitize_string(input_quotes), expected_quotes);
+    }
+
+    #[tokio::test]
+    async fn test_update_availability_owner() {
+        let pool = setup_test_db().await;
+
+        // 1. Create Poll
+        let poll_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status) VALUES (?, 'Title', 'Desc', 'Loc', 0, '[\"2023-12-01\"]', '{}', 'active')")
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+
+        // 2. Create User
+        let user_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO users (id, email, password_hash, name, role, created_at) VALUES (?, 'owner@test.com', 'hash', 'Owner', 'player', 0)")
+            .bind(&user_id)
+            .execute(&pool).await.unwrap();
+
+        // 3. Create Participant LINKED to User
+        let participant_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO participants (id, poll_id, name, email, user_id) VALUES (?, ?, 'Owner P', 'owner@test.com', ?)")
+            .bind(&participant_id)
+            .bind(&poll_id)
+            .bind(&user_id)
+            .execute(&pool).await.unwrap();
+
+        // 4. Update Availability as User (WITHOUT Token)
+        let avail_req = models::UpdateAvailabilityRequest {
+            availability: vec![models::AvailabilityEntry {
+                date: "2023-12-01".to_string(),
+                time_slot: "18:00".to_string(),
+                status: "available".to_string(),
+            }],
+            access_token: None, // No token provided!
+        };
+
+        // Mock AuthUser
+        let user = crate::core::models::User {
+            id: user_id.clone(),
+            email: "owner@test.com".to_string(),
+            password_hash: "hash".to_string(),
+            name: "Owner".to_string(),
+            role: "player".to_string(),
+            created_at: 0,
+            last_login: None,
+            phone: None,
+            timezone: None,
+        };
+        let maybe_user = crate::auth::MaybeAuthUser(Some(user));
+
+        let update_res = update_availability(
+            State(pool.clone()),
+            event_store(),
+            maybe_user,
+            Path((poll_id.clone(), participant_id.clone())),
+            Json(avail_req),
+        )
+        .await;
+
+        // Assert SUCCESS
+        assert!(
+            update_res.is_ok(),
+            "Should allow Owner to update without token"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_availability_rejects_unoffered_slot() {
+        let pool = setup_test_db().await;
+
+        let poll_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status) VALUES (?, 'Title', 'Desc', 'Loc', 0, '[\"2030-01-01\"]', '{\"2030-01-01\": [\"20:00\"]}', 'active')")
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+
+        let participant_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO participants (id, poll_id, name, access_token) VALUES (?, ?, 'Player', 'token')")
+            .bind(&participant_id)
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+
+        for (slot, expected_ok) in [("18:00", false), ("junk", false), ("20:00", true)] {
+            let avail_req = UpdateAvailabilityRequest {
+                availability: vec![AvailabilityEntry {
+                    date: "2030-01-01".to_string(),
+                    time_slot: slot.to_string(),
+                    status: "available".to_string(),
+                }],
+                access_token: Some("token".to_string()),
+            };
+
+            let res = update_availability(
+                State(pool.clone()),
+                event_store(),
+                crate::auth::MaybeAuthUser(None),
+                Path((poll_id.clone(), participant_id.clone())),
+                Json(avail_req),
+            )
+            .await;
+            assert_eq!(res.is_ok(), expected_ok, "slot {}", slot);
+            if !expected_ok {
+                assert_eq!(res.unwrap_err().0, StatusCode::BAD_REQUEST);
+            }
+        }
+
+        // get_poll returns the typed preferences, not a JSON string
+        let poll = get_poll(
+            State(pool.clone()),
+            event_store(),
+            crate::auth::MaybeAuthUser(None),
+            Path(poll_id),
+            Query(PollViewQuery {
+                tz: None,
+                as_of: None,
+            }),
+        )
+        .await
+        .unwrap()
+        .0;
+        assert_eq!(poll["poll"]["time_range"], json!({"2030-01-01": ["20:00"]}));
+    }
+
+    #[tokio::test]
+    async fn test_update_availability_records_vote_history() {
+        let pool = setup_test_db().await;
+        let store = event_store();
+
+        let poll_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status) VALUES (?, 'Title', 'Desc', 'Loc', 0, '[\"2030-01-01\"]', '{}', 'active')")
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+
+        let participant_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO participants (id, poll_id, name, email, access_token) VALUES (?, ?, 'Player', 'p@test.com', 'token')")
+            .bind(&participant_id)
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+
+        let submissions = [
+            vec![("18:00", "available"), ("20:00", "tentative")],
+            vec![("18:00", "busy")],
+        ];
+        for entries in submissions {
+            let avail_req = UpdateAvailabilityRequest {
+                availability: entries
+                    .iter()
+                    .map(|(slot, status)| AvailabilityEntry {
+                        date: "2030-01-01".to_string(),
+                        time_slot: slot.to_string(),
+                        status: status.to_string(),
+                    })
+                    .collect(),
+                access_token: Some("token".to_string()),
+            };
+            update_availability(
+                State(pool.clone()),
+                store.clone(),
+                crate::auth::MaybeAuthUser(None),
+                Path((poll_id.clone(), participant_id.clone())),
+                Json(avail_req),
+            )
+            .await
+            .unwrap();
+        }
+
+        let stream_id = events::participant_stream(&poll_id, &participant_id);
+        let recorded: Vec<(String, String)> = store
+            .0
+            .read_stream(&stream_id)
+            .await
+            .unwrap()
+            .iter()
+            .map(
+                |bytes| match events::Event::from_bytes(&stream_id, bytes).unwrap() {
+                    events::Event::VoteCast(vote) => {
+                        assert_eq!(vote.participant_id, participant_id);
+                        assert!(vote.recorded_at.is_some());
+                        (vote.slot, vote.vote.status().to_string())
+                    }
+                    other => panic!("Unexpected event {:?}", other),
+                },
+            )
+            .collect();
+        let expected = [
+            ("18:00", "available"),
+            ("20:00", "tentative"),
+            ("18:00", "busy"),
+            ("20:00", "cleared"),
+        ];
+        assert_eq!(
+            recorded,
+            expected
+                .iter()
+                .map(|(slot, vote)| (slot.to_string(), vote.to_string()))
+                .collect::<Vec<_>>()
+        );
+
+        // Removing the participant clears the votes they had left
+        let deleted = delete_participant(
+            State(pool.clone()),
+            store.clone(),
+            crate::auth::PollOrganizer {
+                poll_id: poll_id.clone(),
+                user: None,
+            },
+            Path(participant_id.clone()),
+        )
+        .await;
+        assert!(deleted.is_ok());
+        let history = vote_history(&store.0, &poll_id, &participant_id)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), expected.len() + 1);
+        assert!(events::votes_as_of(&history, i64::MAX).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_poll_and_join_events_are_recorded() {
+        let pool = setup_test_db().await;
+        let store = event_store();
+
+        let future_date = (Utc::now() + chrono::Duration::days(30)).date_naive();
+        let create_req = CreatePollRequest {
+            title: "Event Poll".to_string(),
+            description: "Desc".to_string(),
+            location: "Loc".to_string(),
+            dates: vec![future_date.to_string()],
+            ..Default::default()
+        };
+        let created = create_poll(
+            State(pool.clone()),
+            store.clone(),
+            MaybeAuthUser(None),
+            Json(create_req),
+        )
+        .await
+        .unwrap();
+        let poll_id = created.0["id"].as_str().unwrap().to_string();
+
+        let joined = join_poll(
+            State(pool.clone()),
+            store.clone(),
+            MaybeAuthUser(None),
+            Path(poll_id.clone()),
+            Json(JoinPollRequest {
+                name: "Player".to_string(),
+                email: None,
+            }),
+        )
+        .await
+        .unwrap();
+        let participant_id = joined.0["id"].as_str().unwrap().to_string();
+
+        let read = |stream_id: String| {
+            let store = store.0.clone();
+            async move {
+                store
+                    .read_stream(&stream_id)
+                    .await
+                    .unwrap()
+                    .iter()
+                    .map(|bytes| events::Event::from_bytes(&stream_id, bytes).unwrap())
+                    .collect::<Vec<_>>()
+            }
+        };
+        match read(events::poll_stream(&poll_id)).await.as_slice() {
+            [events::Event::PollCreated(created)] => {
+                assert_eq!(created.title, "Event Poll");
+                assert_eq!(created.dates, vec![future_date.to_string()]);
+                assert_eq!(created.organizer_id, None);
+            }
+            other => panic!("Unexpected poll events {:?}", other),
+        }
+        match read(events::participant_stream(&poll_id, &participant_id))
+            .await
+            .as_slice()
+        {
+            [events::Event::ParticipantJoined(joined)] => assert_eq!(joined.name, "Player"),
+            other => panic!("Unexpected participant events {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_closed_poll_rejects_joins_and_votes() {
+        let pool = setup_test_db().await;
+
+        let poll_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status, closes_at) VALUES (?, 'Title', 'Desc', 'Loc', 0, '[\"2030-01-01\"]', '{}', 'active', 1)")
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+
+        let participant_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO participants (id, poll_id, name, access_token) VALUES (?, ?, 'Player', 'token')")
+            .bind(&participant_id)
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+
+        let vote = || {
+            update_availability(
+                State(pool.clone()),
+                event_store(),
+                crate::auth::MaybeAuthUser(None),
+                Path((poll_id.clone(), participant_id.clone())),
+                Json(UpdateAvailabilityRequest {
+                    availability: vec![AvailabilityEntry {
+                        date: "2030-01-01".to_string(),
+                        time_slot: "20:00".to_string(),
+                        status: "available".to_string(),
+                    }],
+                    access_token: Some("token".to_string()),
+                }),
+            )
+        };
+
+        // The deadline has passed: both joining and voting are refused
+        let join = join_poll(
+            State(pool.clone()),
+            event_store(),
+            MaybeAuthUser(None),
+            Path(poll_id.clone()),
+            Json(JoinPollRequest {
+                name: "Late".to_string(),
+                email: None,
+            }),
+        )
+        .await;
+        assert_eq!(join.unwrap_err().0, StatusCode::CONFLICT);
+        let (status, message) = vote().await.unwrap_err();
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert!(message.starts_with("Voting closed on"), "{}", message);
+
+        let organizer = || crate::auth::PollOrganizer {
+            poll_id: poll_id.clone(),
+            user: None,
+        };
+
+        // Closing keeps the earlier deadline; reopening clears it
+        let closed = close_poll(
+            State(pool.clone()),
+            event_store(),
+            organizer(),
+            Path(poll_id.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(closed.0["status"], "closed");
+        let (status, closes_at): (String, Option<i64>) =
+            sqlx::query_as("SELECT status, closes_at FROM polls WHERE id = ?")
+                .bind(&poll_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!((status.as_str(), closes_at), ("closed", Some(1)));
+        assert_eq!(
+            close_poll(
+                State(pool.clone()),
+                event_store(),
+                organizer(),
+                Path(poll_id.clone())
+            )
+            .await
+            .unwrap_err()
+            .0,
+            StatusCode::CONFLICT
+        );
+
+        let reopened = reopen_poll(
+            State(pool.clone()),
+            event_store(),
+            organizer(),
+            Path(poll_id.clone()),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(reopened.0["status"], "active");
+        assert!(vote().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_update_poll_reconciles_votes() {
+        let pool = setup_test_db().await;
+
+        let poll_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status) VALUES (?, 'Title', 'Desc', 'Loc', 0, '[\"2030-01-10\", \"2030-01-11\"]', '[\"20:00\", \"21:00\"]', 'active')")
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO participants (id, poll_id, name, email) VALUES ('p1', ?, 'Alice', 'alice@test.com'), ('p2', ?, 'Bob', NULL)")
+            .bind(&poll_id)
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO availability (poll_id, participant_id, date, time_slot, status) VALUES (?, 'p1', '2030-01-10', '20:00', 'available'), (?, 'p1', '2030-01-10', '21:00', 'busy'), (?, 'p2', '2030-01-11', '20:00', 'available')")
+            .bind(&poll_id)
+            .bind(&poll_id)
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+
+        // Edits go through the same date rules as a new poll
+        for dates in [vec!["2020-01-10"], vec!["2030-01-10", "2030-03-01"]] {
+            let err = update_poll(
+                State(pool.clone()),
+                event_store(),
+                crate::auth::PollOrganizer {
+                    poll_id: poll_id.clone(),
+                    user: None,
+                },
+                Path(poll_id.clone()),
+                Json(CreatePollRequest {
+                    title: "Title".to_string(),
+                    description: "Desc".to_string(),
+                    location: "Loc".to_string(),
+                    dates: dates.into_iter().map(String::from).collect(),
+                    time_range: Some("[\"20:00\"]".to_string()),
+                    ..Default::default()
+                }),
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(err.0, StatusCode::BAD_REQUEST);
+        }
+
+        // The 10th becomes the 12th and the 21:00 slot is dropped
+        let store = event_store();
+        let mut date_moves = HashMap::new();
+        date_moves.insert("2030-01-10".to_string(), "2030-01-12".to_string());
+        let res = update_poll(
+            State(pool.clone()),
+            store.clone(),
+            crate::auth::PollOrganizer {
+                poll_id: poll_id.clone(),
+                user: None,
+            },
+            Path(poll_id.clone()),
+            Json(CreatePollRequest {
+                title: "Title".to_string(),
+                description: "Desc".to_string(),
+                location: "Loc".to_string(),
+                dates: vec!["2030-01-11".to_string(), "2030-01-12".to_string()],
+                time_range: Some("[\"20:00\"]".to_string()),
+                date_moves: Some(date_moves),
+                notify_affected: Some(true),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(res["votes"], json!({ "kept": 1, "moved": 1, "dropped": 1 }));
+        assert_eq!(
+            res["affectedParticipants"],
+            json!([{ "participantId": "p1", "name": "Alice", "moved": 1, "dropped": 1 }])
+        );
+        assert_eq!(res["notified"], 1);
+
+        let votes: Vec<(String, String, String)> = sqlx::query_as(
+            "SELECT participant_id, date, time_slot FROM availability WHERE poll_id = ? ORDER BY participant_id",
+        )
+        .bind(&poll_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            votes,
+            vec![
+                (
+                    "p1".to_string(),
+                    "2030-01-12".to_string(),
+                    "20:00".to_string()
+                ),
+                (
+                    "p2".to_string(),
+                    "2030-01-11".to_string(),
+                    "20:00".to_string()
+                )
+            ]
+        );
+
+        // The vote history follows the moved and dropped votes
+        let history = vote_history(&store.0, &poll_id, "p1").await.unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(
+            events::votes_as_of(&history, i64::MAX),
+            vec![(
+                "2030-01-12".to_string(),
+                "20:00".to_string(),
+                "available".to_string()
+            )]
+        );
+        assert!(vote_history(&store.0, &poll_id, "p2")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_poll_lifecycle_cancel_unfinalize_reschedule() {
+        let pool = setup_test_db().await;
+        let store = event_store();
+        let dates = ["2030-01-10", "2030-01-11"];
+
+        let poll_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status) VALUES (?, 'Title', 'Desc', 'Loc', 0, ?, '[\"20:00\", \"21:00\"]', 'active')")
+            .bind(&poll_id)
+            .bind(serde_json::to_string(&dates).unwrap())
+            .execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO participants (id, poll_id, name) VALUES ('p1', ?, 'Player')")
+            .bind(&poll_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO availability (poll_id, participant_id, date, time_slot, status) VALUES (?, 'p1', '2030-01-10', '20:00', 'available'), (?, 'p1', '2030-01-11', '21:00', 'available')")
+            .bind(&poll_id)
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+
+        let organizer = || crate::auth::PollOrganizer {
+            poll_id: poll_id.clone(),
+            user: None,
+        };
+        let status = || async {
+            sqlx::query_scalar::<_, String>("SELECT status FROM polls WHERE id = ?")
+                .bind(&poll_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+        };
+        let finalize = || {
+            finalize_poll(
+                State(pool.clone()),
+                store.clone(),
+                organizer(),
+                Path(poll_id.clone()),
+                Query(TimezoneQuery { tz: None }),
+                Json(models::FinalizePollRequest {
+                    start: Some("2030-01-10_20:00".to_string()),
+                    notes: None,
+                    ..Default::default()
+                }),
+            )
+        };
+
+        // Cancelled polls can't be finalized until reopened
+        let cancelled = cancel_poll(
+            State(pool.clone()),
+            store.clone(),
+            organizer(),
+            Path(poll_id.clone()),
+            Json(models::CancelPollRequest {
+                reason: "DM is sick".to_string(),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(cancelled.0["status"], "cancelled");
+        let (code, message) = finalize().await.unwrap_err();
+        assert_eq!(code, StatusCode::CONFLICT);
+        assert_eq!(message, "Cannot move a poll from cancelled to finalized");
+
+        assert!(reopen_poll(
+            State(pool.clone()),
+            store.clone(),
+            organizer(),
+            Path(poll_id.clone()),
+            None
+        )
+        .await
+        .is_ok());
+        let reason: Option<String> =
+            sqlx::query_scalar("SELECT cancel_reason FROM polls WHERE id = ?")
+                .bind(&poll_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!((status().await, reason), ("active".to_string(), None));
+
+        // Un-finalizing keeps every vote
+        assert!(finalize().await.is_ok());
+        assert_eq!(
+            reopen_poll(
+                State(pool.clone()),
+                store.clone(),
+                organizer(),
+                Path(poll_id.clone()),
+                None
+            )
+            .await
+            .unwrap_err()
+            .0,
+            StatusCode::CONFLICT
+        );
+        assert!(unfinalize_poll(
+            State(pool.clone()),
+            store.clone(),
+            organizer(),
+            Path(poll_id.clone())
+        )
+        .await
+        .is_ok());
+        assert_eq!(status().await, "active");
+
+        // Rescheduling drops the votes on dates no longer offered
+        assert!(finalize().await.is_ok());
+        let rescheduled = reschedule_poll(
+            State(pool.clone()),
+            store.clone(),
+            organizer(),
+            Path(poll_id.clone()),
+            Json(models::ReschedulePollRequest {
+                dates: Some(vec!["2030-01-10".to_string(), "2030-01-12".to_string()]),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(rescheduled.0["carriedVotes"], 1);
+        assert_eq!(rescheduled.0["droppedVotes"], 1);
+
+        let (finalized_time, poll_dates): (Option<String>, String) =
+            sqlx::query_as("SELECT finalized_time, dates FROM polls WHERE id = ?")
+                .bind(&poll_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(status().await, "active");
+        assert_eq!(finalized_time, None);
+        assert_eq!(poll_dates, r#"["2030-01-10","2030-01-12"]"#);
+
+        // Only finalized sessions can be rescheduled
+        let res = reschedule_poll(
+            State(pool.clone()),
+            store.clone(),
+            organizer(),
+            Path(poll_id.clone()),
+            Json(models::ReschedulePollRequest::default()),
+        )
+        .await;
+        assert_eq!(res.unwrap_err().0, StatusCode::CONFLICT);
+
+        // Every transition is in the poll's lifecycle stream
+        let stream_id = events::poll_stream(&poll_id);
+        let recorded: Vec<events::Event> = store
+            .0
+            .read_stream(&stream_id)
+            .await
+            .unwrap()
+            .iter()
+            .map(|bytes| events::Event::from_bytes(&stream_id, bytes).unwrap())
+            .collect();
+        assert!(matches!(
+            recorded.as_slice(),
+            [
+                events::Event::PollCancelled(cancelled),
+                events::Event::PollReopened(_),
+                events::Event::PollFinalized(_),
+                events::Event::PollUnfinalized(_),
+                events::Event::PollFinalized(_),
+                events::Event::PollRescheduled(rescheduled),
+            ] if cancelled.reason == "DM is sick" && rescheduled.dates.len() == 2
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_poll_viewer_timezone() {
+        let pool = setup_test_db().await;
+
+        let poll_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status, timezone) VALUES (?, 'Title', 'Desc', 'Loc', 0, '[\"2030-01-10\"]', '[\"20:00\"]', 'active', 'Europe/Rome')")
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+
+        let fetch = |tz: Option<&str>| {
+            get_poll(
+                State(pool.clone()),
+                event_store(),
+                crate::auth::MaybeAuthUser(None),
+                Path(poll_id.clone()),
+                Query(PollViewQuery {
+                    tz: tz.map(|t| t.to_string()),
+                    as_of: None,
+                }),
+            )
+        };
+
+        // Defaults to the poll's own zone
+        let poll = fetch(None).await.unwrap().0;
+        assert_eq!(poll["viewerTimezone"], "Europe/Rome");
+        assert_eq!(poll["slotTimes"]["2030-01-10_20:00"]["timeSlot"], "20:00");
+
+        let poll = fetch(Some("Europe/London")).await.unwrap().0;
+        assert_eq!(poll["slotTimes"]["2030-01-10_20:00"]["timeSlot"], "19:00");
+        assert_eq!(
+            poll["slotTimes"]["2030-01-10_20:00"]["utc"],
+            "2030-01-10T19:00:00+00:00"
         );
+
+        let err = fetch(Some("Middle/Earth")).await.unwrap_err();
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_poll_recommendations() {
+        let pool = setup_test_db().await;
+
+        let poll_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status, tentative_weight, tie_breaker) VALUES (?, 'Title', 'Desc', 'Loc', 0, '[\"2030-01-01\"]', '{\"2030-01-01\": [\"18:00\", \"19:00\", \"20:00\"]}', 'active', 0.5, 'earliest')")
+            .bind(&poll_id)
+            .execute(&pool).await.unwrap();
+
+        for (participant_id, slot, status) in [
+            ("p1", "18:00", "available"),
+            ("p2", "18:00", "busy"),
+            ("p1", "19:00", "available"),
+            ("p2", "19:00", "tentative"),
+        ] {
+            sqlx::query("INSERT OR IGNORE INTO participants (id, poll_id, name) VALUES (?, ?, ?)")
+                .bind(participant_id)
+                .bind(&poll_id)
+                .bind(participant_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+            sqlx::query("INSERT INTO availability (poll_id, participant_id, date, time_slot, status) VALUES (?, ?, '2030-01-01', ?, ?)")
+                .bind(&poll_id)
+                .bind(participant_id)
+                .bind(slot)
+                .bind(status)
+                .execute(&pool).await.unwrap();
+        }
+
+        let res = get_poll_recommendations(
+            State(pool.clone()),
+            Path(poll_id.clone()),
+            Query(RecommendationsQuery { limit: None }),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        assert_eq!(res["totalParticipants"], 2);
+        let ranked = res["recommendations"].as_array().unwrap();
+        assert_eq!(ranked.len(), 3);
+        assert_eq!(ranked[0]["timeSlot"], "19:00");
+        assert_eq!(ranked[0]["score"], 1.5);
+        assert_eq!(ranked[1]["timeSlot"], "18:00");
+        assert_eq!(ranked[1]["busy"], 1);
+        assert_eq!(ranked[2]["timeSlot"], "20:00");
+        assert_eq!(ranked[2]["noResponse"], 2);
     }
 }
 
@@ -1199,13 +3114,13 @@ pub async fn google_login(
         existing
     } else {
         // More secure domain validation
-        let allowed_domains = vec!["ddscheduler.com", "example.com"];
+        let allowed_domains = ["ddscheduler.com", "example.com"];
         let email_domain = email.split('@').nth(1).unwrap_or("");
         let default_admin_email = std::env::var("DEFAULT_ADMIN_EMAIL")
             .unwrap_or_else(|_| "admin@example.com".to_string());
 
         // Allow if domain matches OR it's the specific default admin email
-        if !allowed_domains.iter().any(|d| email_domain == *d) && email != default_admin_email {
+        if !allowed_domains.contains(&email_domain) && email != default_admin_email {
             return Err((
                 StatusCode::UNAUTHORIZED,
                 "Email domain not authorized".to_string(),
@@ -1273,6 +3188,8 @@ pub async fn google_login(
 
 pub async fn update_poll(
     State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
+    organizer: crate::auth::PollOrganizer,
     Path(poll_id): Path<String>,
     Json(payload): Json<CreatePollRequest>,
 ) -> Result<Json<Value>, (StatusCode, String)> {
@@ -1287,17 +3204,43 @@ pub async fn update_poll(
     validate_string_length(&payload.location, MAX_LOCATION_LENGTH, "Location")
         .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
 
-    if payload.dates.is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            "At least one date is required".to_string(),
-        ));
-    }
-    if payload.dates.len() > MAX_DATES {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            format!("Too many dates (max: {})", MAX_DATES),
-        ));
+    let limits = poll_limits(&pool, &poll_id).await?;
+    limits
+        .validate_dates(&payload.dates, Utc::now().date_naive())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    // Omitted ranking settings keep their current value
+    let (tentative_weight, tie_breaker) =
+        validate_recommendation_settings(&payload).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let poll_timezone = match payload.timezone.as_deref() {
+        Some(tz) => Some(
+            timezone::parse_timezone(tz)
+                .map_err(|e| (StatusCode::BAD_REQUEST, e))?
+                .name()
+                .to_string(),
+        ),
+        None => None,
+    };
+
+    lifecycle::validate_closes_at(payload.closes_at, Utc::now().timestamp())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    blocks::validate_session_minutes(payload.session_minutes)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    // Moving a session into a campaign takes the same rights as scheduling it there
+    if let Some(campaign_id) = &payload.campaign_id {
+        let campaign = campaigns::fetch_campaign(&pool, campaign_id).await?;
+        let allowed = organizer
+            .user
+            .as_ref()
+            .is_some_and(|user| campaigns::can_manage(user, &campaign));
+        if !allowed {
+            return Err((
+                StatusCode::FORBIDDEN,
+                "Only the campaign's DM can schedule its sessions".to_string(),
+            ));
+        }
     }
 
     // Sanitize inputs
@@ -1312,33 +3255,36 @@ pub async fn update_poll(
         )
     })?;
 
-    // Handle time preferences - support both old and new formats
-    let time_range_value = if let Some(time_prefs) = &payload.time_preferences {
-        // New format: per-day time preferences
-        serde_json::to_string(time_prefs).map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Failed to serialize time preferences: {}", e),
-            )
-        })?
-    } else if let Some(legacy_time_range) = &payload.time_range {
-        // Legacy format: global time range
-        legacy_time_range.clone()
-    } else {
-        // Default: empty JSON object
-        "{}".to_string()
-    };
+    // Accepts both the per-day `timePreferences` and the legacy `timeRange` list
+    let time_preferences = TimePreferences::from_request(
+        payload.time_preferences.as_ref(),
+        payload.time_range.as_deref(),
+        &payload.dates,
+    )
+    .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let time_range_value = time_preferences.to_storage();
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     let result = sqlx::query(
-        "UPDATE polls SET title = ?, description = ?, location = ?, dates = ?, time_range = ? WHERE id = ?",
+        "UPDATE polls SET title = ?, description = ?, location = ?, dates = ?, time_range = ?, tentative_weight = COALESCE(?, tentative_weight), tie_breaker = COALESCE(?, tie_breaker), timezone = COALESCE(?, timezone), campaign_id = COALESCE(?, campaign_id), closes_at = COALESCE(?, closes_at), session_minutes = COALESCE(?, session_minutes) WHERE id = ?",
     )
     .bind(&title)
     .bind(&description)
     .bind(&location)
     .bind(&dates_json)
     .bind(&time_range_value)
+    .bind(tentative_weight)
+    .bind(tie_breaker.map(|t| t.as_str()))
+    .bind(&poll_timezone)
+    .bind(&payload.campaign_id)
+    .bind(payload.closes_at)
+    .bind(payload.session_minutes)
     .bind(&poll_id)
-    .execute(&pool)
+    .execute(&mut *tx)
     .await
     .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Failed to update poll".to_string()))?;
 
@@ -1346,31 +3292,159 @@ pub async fn update_poll(
         return Err((StatusCode::NOT_FOUND, "Poll not found".to_string()));
     }
 
-    Ok(Json(json!({ "success": true })))
+    // Votes on dates/slots that are gone follow the requested moves or are dropped
+    let moves = VoteMoves {
+        dates: payload.date_moves.unwrap_or_default(),
+        slots: payload.slot_moves.unwrap_or_default(),
+    };
+    let report = reconcile::reconcile_availability(
+        &mut tx,
+        &poll_id,
+        &payload.dates,
+        &time_preferences,
+        &moves,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to reconcile votes of poll {}: {}", poll_id, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to update poll".to_string(),
+        )
+    })?;
+
+    tx.commit()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    record_event(
+        &event_store,
+        &events::poll_stream(&poll_id),
+        events::Event::PollEdited(events::PollEdited {
+            poll_id: poll_id.clone(),
+            title: title.clone(),
+            dates: payload.dates.clone(),
+            recorded_at: Utc::now().timestamp(),
+        }),
+    )
+    .await;
+    record_vote_fates(&event_store, &report.changes).await;
+
+    let notified = if payload.notify_affected.unwrap_or(false) {
+        notify_affected_participants(&title, &report)
+    } else {
+        0
+    };
+
+    Ok(Json(json!({
+        "success": true,
+        "votes": {
+            "kept": report.kept,
+            "moved": report.moved,
+            "dropped": report.dropped
+        },
+        "affectedParticipants": report.affected,
+        "notified": notified
+    })))
+}
+
+/// Emails the players whose votes were moved or dropped (fire and forget).
+/// Returns how many of them have an address.
+fn notify_affected_participants(poll_title: &str, report: &reconcile::ReconcileReport) -> usize {
+    let mut notified = 0;
+    for participant in &report.affected {
+        let Some(email) = participant.email.clone() else {
+            continue;
+        };
+        notified += 1;
+        let title = poll_title.to_string();
+        let (moved, dropped) = (participant.moved, participant.dropped);
+        tokio::spawn(async move {
+            if let Err(e) = crate::core::services::email::send_poll_changed_email(
+                &email, &title, moved, dropped,
+            )
+            .await
+            {
+                tracing::error!("Failed to send poll change email to {}: {}", email, e);
+            }
+        });
+    }
+    notified
 }
 
 pub async fn finalize_poll(
     State(pool): State<DbPool>,
-    // Require Admin authentication
-    _admin_user: crate::auth::AdminUser,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
+    // Organizer (admin token or session) or site admin
+    _organizer: crate::auth::PollOrganizer,
     Path(poll_id): Path<String>,
+    Query(tz_query): Query<TimezoneQuery>,
     Json(payload): Json<models::FinalizePollRequest>,
 ) -> Result<Json<Value>, (StatusCode, String)> {
     // Validate poll_id
     validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
 
-    if payload.finalized_time.trim().is_empty() {
-        return Err((
+    let poll: Poll = sqlx::query_as("SELECT * FROM polls WHERE id = ?")
+        .bind(&poll_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database error".to_string(),
+            )
+        })?
+        .ok_or((StatusCode::NOT_FOUND, "Poll not found".to_string()))?;
+
+    let poll_tz = timezone::parse_timezone(&poll.timezone).unwrap_or(chrono_tz::Tz::UTC);
+    let input_tz = match payload.timezone.as_deref() {
+        Some(tz) => timezone::parse_timezone(tz).map_err(|e| (StatusCode::BAD_REQUEST, e))?,
+        None => poll_tz,
+    };
+    let start = payload
+        .start
+        .as_deref()
+        .or(payload.finalized_time.as_deref())
+        .filter(|start| !start.trim().is_empty())
+        .ok_or((
             StatusCode::BAD_REQUEST,
-            "Finalized time cannot be empty".to_string(),
-        ));
-    }
+            "Session start cannot be empty".to_string(),
+        ))?;
+    // Without an explicit end, sessions last as long as the poll asked for
+    let session = SessionTime::new(
+        start,
+        payload.end.as_deref(),
+        payload.duration_minutes.or(poll.session_minutes),
+        input_tz,
+    )
+    .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    // Only open or closed polls can be finalized
+    let from = poll.status;
+    from.transition(PollStatus::Finalized)
+        .map_err(|e| (StatusCode::CONFLICT, e))?;
 
-    // Check current status
-    let current_status: Option<String> =
-        sqlx::query_scalar("SELECT status FROM polls WHERE id = ?")
+    // The session must start on a date and slot the poll actually offered
+    let dates: Vec<String> = serde_json::from_str(&poll.dates).unwrap_or_default();
+    session
+        .validate_offered(&dates, &poll.time_range, poll_tz)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    // Refuse a session where a required participant (e.g. the DM) voted busy on any
+    // of the slots it covers
+    let required_ids = attendance::required_participant_ids(&pool, &poll_id)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database error".to_string(),
+            )
+        })?;
+    let (date, _) = session.start_slot(poll_tz);
+    let day_votes: Vec<Availability> =
+        sqlx::query_as("SELECT * FROM availability WHERE poll_id = ? AND date = ?")
             .bind(&poll_id)
-            .fetch_optional(&pool)
+            .bind(&date)
+            .fetch_all(&pool)
             .await
             .map_err(|_| {
                 (
@@ -1379,64 +3453,100 @@ pub async fn finalize_poll(
                 )
             })?;
 
-    if let Some(status) = current_status {
-        if status == "finalized" {
-            return Err((
-                StatusCode::CONFLICT,
-                "Poll is already finalized".to_string(),
-            ));
+    let mut busy: Vec<&String> = Vec::new();
+    for (date, time_slot) in session.covered_slots(&poll.time_range, poll_tz) {
+        for participant_id in
+            attendance::busy_required_participants(&required_ids, &day_votes, &date, &time_slot)
+        {
+            if !busy.contains(&participant_id) {
+                busy.push(participant_id);
+            }
         }
-    } else {
-        return Err((StatusCode::NOT_FOUND, "Poll not found".to_string()));
+    }
+    if !busy.is_empty() {
+        let mut names = Vec::new();
+        for participant_id in busy {
+            let name: Option<String> =
+                sqlx::query_scalar("SELECT name FROM participants WHERE id = ?")
+                    .bind(participant_id)
+                    .fetch_optional(&pool)
+                    .await
+                    .unwrap_or(None);
+            names.push(name.unwrap_or_else(|| participant_id.clone()));
+        }
+
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Required participants are busy at this time: {}",
+                names.join(", ")
+            ),
+        ));
     }
 
     let now = Utc::now().timestamp();
 
     let result = sqlx::query(
-        "UPDATE polls SET status = 'finalized', finalized_at = ?, finalized_time = ?, notes = ? WHERE id = ?",
+        "UPDATE polls SET status = ?, finalized_at = ?, finalized_time = ?, finalized_start = ?, finalized_end = ?, notes = ? WHERE id = ? AND status = ?",
     )
+    .bind(PollStatus::Finalized.as_str())
     .bind(now)
-    .bind(&payload.finalized_time)
+    .bind(session.slot_key(poll_tz))
+    .bind(session.start.timestamp())
+    .bind(session.end.timestamp())
     .bind(&payload.notes)
     .bind(&poll_id)
+    .bind(from.as_str())
     .execute(&pool)
     .await
     .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Failed to finalize poll".to_string()))?;
 
     if result.rows_affected() == 0 {
-        return Err((StatusCode::NOT_FOUND, "Poll not found".to_string()));
+        return Err(concurrent_change());
     }
 
     // Log activity: poll finalized
-    // We need to fetch poll title first for better logging, but for efficiency we can skip or do it quickly
-    // Let's do a quick fetch for title
-    let title: Option<String> = sqlx::query_scalar("SELECT title FROM polls WHERE id = ?")
-        .bind(&poll_id)
-        .fetch_optional(&pool)
-        .await
-        .unwrap_or(None);
-
     crate::activity_handlers::log_activity(
         &pool,
         "poll_finalized",
         "system".to_string(),
         "Organizzatore".to_string(),
-        Some(poll_id),
-        title,
+        Some(poll_id.clone()),
+        Some(poll.title),
     )
     .await
     .unwrap_or_else(|e| tracing::error!("Activity log error: {}", e));
+    record_event(
+        &event_store,
+        &events::poll_stream(&poll_id),
+        events::Event::PollFinalized(events::PollFinalized {
+            poll_id,
+            start: session.start.timestamp(),
+            end: session.end.timestamp(),
+            recorded_at: now,
+        }),
+    )
+    .await;
+
+    // Echo the chosen session in the requested zone (default: the poll's)
+    let viewer_tz = timezone::viewer_timezone(tz_query.tz.as_deref(), None, &poll.timezone)
+        .unwrap_or(chrono_tz::Tz::UTC);
+    let (date, time_slot) = session.start_slot(poll_tz);
+    let finalized_slot = timezone::localize_slot(&date, &time_slot, poll_tz, viewer_tz);
 
     Ok(Json(json!({
         "success": true,
         "status": "finalized",
-        "finalizedAt": now
+        "finalizedAt": now,
+        "finalizedTime": session.slot_key(poll_tz),
+        "finalizedSlot": finalized_slot,
+        "session": session.to_value(viewer_tz)
     })))
 }
 
 pub async fn delete_poll(
     State(pool): State<DbPool>,
-    _admin: crate::auth::AdminUser,
+    _organizer: crate::auth::PollOrganizer,
     Path(poll_id): Path<String>,
 ) -> Result<Json<Value>, (StatusCode, String)> {
     // Validate poll_id
@@ -1478,50 +3588,213 @@ pub async fn delete_poll(
             )
         })?;
 
-    if result.rows_affected() == 0 {
-        return Err((StatusCode::NOT_FOUND, "Poll not found".to_string()));
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, "Poll not found".to_string()));
+    }
+
+    Ok(Json(json!({ "success": true })))
+}
+
+pub async fn delete_participant(
+    State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
+    _organizer: crate::auth::PollOrganizer,
+    Path(participant_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    // Validate participant_id
+    validate_uuid(&participant_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    // The votes going away, to clear them in the participant's vote history
+    let votes: Vec<Availability> =
+        sqlx::query_as("SELECT * FROM availability WHERE participant_id = ? ORDER BY id")
+            .bind(&participant_id)
+            .fetch_all(&pool)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Database error".to_string(),
+                )
+            })?;
+
+    // Delete availability first (foreign key constraint)
+    sqlx::query("DELETE FROM availability WHERE participant_id = ?")
+        .bind(&participant_id)
+        .execute(&pool)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database error".to_string(),
+            )
+        })?;
+
+    // Delete the participant
+    let result = sqlx::query("DELETE FROM participants WHERE id = ?")
+        .bind(&participant_id)
+        .execute(&pool)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database error".to_string(),
+            )
+        })?;
+
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, "Participant not found".to_string()));
+    }
+
+    let dropped: Vec<_> = votes
+        .into_iter()
+        .map(|vote| (vote, reconcile::VoteFate::Dropped))
+        .collect();
+    record_vote_fates(&event_store, &dropped).await;
+
+    Ok(Json(json!({ "success": true })))
+}
+
+/// PUT /api/polls/:id/participants/:participant_id/attendance
+pub async fn update_participant_attendance(
+    State(pool): State<DbPool>,
+    _organizer: crate::auth::PollOrganizer,
+    Path((poll_id, participant_id)): Path<(String, String)>,
+    Json(payload): Json<models::UpdateAttendanceRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    validate_uuid(&participant_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    attendance::validate_attendance(&payload.attendance)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let result = sqlx::query("UPDATE participants SET attendance = ? WHERE id = ? AND poll_id = ?")
+        .bind(&payload.attendance)
+        .bind(&participant_id)
+        .bind(&poll_id)
+        .execute(&pool)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to update attendance".to_string(),
+            )
+        })?;
+
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, "Participant not found".to_string()));
+    }
+
+    Ok(Json(json!({
+        "success": true,
+        "attendance": payload.attendance
+    })))
+}
+
+/// POST /api/polls/:id/participants/:participant_id/invite
+/// Sends the participant their personal link again
+pub async fn resend_invite(
+    State(pool): State<DbPool>,
+    _organizer: crate::auth::PollOrganizer,
+    Path((poll_id, participant_id)): Path<(String, String)>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    validate_uuid(&participant_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let row: Option<(Option<String>, Option<String>, String)> = sqlx::query_as(
+        "SELECT p.email, p.access_token, polls.title FROM participants p JOIN polls ON polls.id = p.poll_id WHERE p.id = ? AND p.poll_id = ?",
+    )
+    .bind(&participant_id)
+    .bind(&poll_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Database error".to_string(),
+        )
+    })?;
+    let (email, access_token, title) =
+        row.ok_or((StatusCode::NOT_FOUND, "Participant not found".to_string()))?;
+    let email = email.ok_or((
+        StatusCode::BAD_REQUEST,
+        "Participant has no email address".to_string(),
+    ))?;
+
+    // Participants who joined with an account may not have a token yet
+    let access_token = match access_token {
+        Some(token) => token,
+        None => {
+            let token = Uuid::new_v4().to_string();
+            sqlx::query("UPDATE participants SET access_token = ? WHERE id = ?")
+                .bind(&token)
+                .bind(&participant_id)
+                .execute(&pool)
+                .await
+                .map_err(|_| {
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "Database error".to_string(),
+                    )
+                })?;
+            token
+        }
+    };
+
+    let link = crate::core::services::email::invite_link(&poll_id, &access_token);
+    if let Err(e) = crate::core::services::email::send_invitation_email(&email, &title, &link).await
+    {
+        tracing::error!("Failed to send invitation email to {}: {}", email, e);
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to send invitation email".to_string(),
+        ));
     }
 
-    Ok(Json(json!({ "success": true })))
+    Ok(Json(json!({
+        "success": true,
+        "email": email
+    })))
 }
 
-pub async fn delete_participant(
+/// PUT /api/polls/:id/quorum
+/// Sets (or clears, with an empty body) the rule used to auto-finalize the poll.
+pub async fn update_poll_quorum(
     State(pool): State<DbPool>,
-    _admin: crate::auth::AdminUser,
-    Path(participant_id): Path<String>,
+    _organizer: crate::auth::PollOrganizer,
+    Path(poll_id): Path<String>,
+    Json(payload): Json<models::QuorumRule>,
 ) -> Result<Json<Value>, (StatusCode, String)> {
-    // Validate participant_id
-    validate_uuid(&participant_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
-
-    // Delete availability first (foreign key constraint)
-    sqlx::query("DELETE FROM availability WHERE participant_id = ?")
-        .bind(&participant_id)
-        .execute(&pool)
-        .await
-        .map_err(|_| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Database error".to_string(),
-            )
-        })?;
+    validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let limits = poll_limits(&pool, &poll_id).await?;
+    quorum::validate_quorum_rule(&payload, limits.max_participants, Utc::now().timestamp())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
 
-    // Delete the participant
-    let result = sqlx::query("DELETE FROM participants WHERE id = ?")
-        .bind(&participant_id)
-        .execute(&pool)
-        .await
-        .map_err(|_| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Database error".to_string(),
-            )
-        })?;
+    let result = sqlx::query(
+        "UPDATE polls SET quorum_min_available = ?, quorum_deadline = ? WHERE id = ? AND status IN ('active', 'closed')",
+    )
+    .bind(payload.min_available)
+    .bind(payload.deadline)
+    .bind(&poll_id)
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to update quorum".to_string(),
+        )
+    })?;
 
     if result.rows_affected() == 0 {
-        return Err((StatusCode::NOT_FOUND, "Participant not found".to_string()));
+        return Err((
+            StatusCode::NOT_FOUND,
+            "Poll not found or not active".to_string(),
+        ));
     }
 
-    Ok(Json(json!({ "success": true })))
+    Ok(Json(json!({
+        "success": true,
+        "quorum": payload,
+        "autoFinalize": !quorum::is_empty(&payload)
+    })))
 }
 
 #[derive(Debug, Deserialize)]
@@ -1762,3 +4035,359 @@ pub async fn serve_poll_page(
         }
     }
 }
+
+/// PUT /api/polls/:id/close
+/// Freezes joins and votes without picking a slot yet
+pub async fn close_poll(
+    State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
+    _organizer: crate::auth::PollOrganizer,
+    Path(poll_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let from = check_transition(&pool, &poll_id, PollStatus::Closed).await?;
+    let now = Utc::now().timestamp();
+
+    // Keep an earlier deadline, otherwise record when voting actually stopped
+    let result = sqlx::query(
+        "UPDATE polls SET status = ?, closes_at = MIN(COALESCE(closes_at, ?), ?) WHERE id = ? AND status = ?",
+    )
+    .bind(PollStatus::Closed.as_str())
+    .bind(now)
+    .bind(now)
+    .bind(&poll_id)
+    .bind(from.as_str())
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to close poll".to_string(),
+        )
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err(concurrent_change());
+    }
+    record_event(
+        &event_store,
+        &events::poll_stream(&poll_id),
+        events::Event::PollClosed(events::PollClosed {
+            poll_id: poll_id.clone(),
+            recorded_at: now,
+        }),
+    )
+    .await;
+
+    Ok(Json(json!({
+        "success": true,
+        "status": PollStatus::Closed
+    })))
+}
+
+/// PUT /api/polls/:id/reopen
+/// Lets players vote again on a closed or cancelled poll, optionally until a new
+/// deadline. Finalized polls go through `unfinalize` or `reschedule` instead.
+pub async fn reopen_poll(
+    State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
+    _organizer: crate::auth::PollOrganizer,
+    Path(poll_id): Path<String>,
+    payload: Option<Json<models::ReopenPollRequest>>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let Json(payload) = payload.unwrap_or_default();
+    lifecycle::validate_closes_at(payload.closes_at, Utc::now().timestamp())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let from = check_transition(&pool, &poll_id, PollStatus::Active).await?;
+    if from == PollStatus::Finalized {
+        return Err((
+            StatusCode::CONFLICT,
+            "Poll is finalized; un-finalize or reschedule it instead".to_string(),
+        ));
+    }
+
+    let result = sqlx::query(
+        "UPDATE polls SET status = ?, closes_at = ?, cancel_reason = NULL WHERE id = ? AND status = ?",
+    )
+    .bind(PollStatus::Active.as_str())
+    .bind(payload.closes_at)
+    .bind(&poll_id)
+    .bind(from.as_str())
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to reopen poll".to_string(),
+        )
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err(concurrent_change());
+    }
+    record_event(
+        &event_store,
+        &events::poll_stream(&poll_id),
+        events::Event::PollReopened(events::PollReopened {
+            poll_id: poll_id.clone(),
+            closes_at: payload.closes_at,
+            recorded_at: Utc::now().timestamp(),
+        }),
+    )
+    .await;
+
+    Ok(Json(json!({
+        "success": true,
+        "status": PollStatus::Active,
+        "closesAt": payload.closes_at
+    })))
+}
+
+/// PUT /api/polls/:id/cancel
+/// Calls the session off; the reason is shown to players
+pub async fn cancel_poll(
+    State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
+    _organizer: crate::auth::PollOrganizer,
+    Path(poll_id): Path<String>,
+    Json(payload): Json<models::CancelPollRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    validate_string_length(payload.reason.trim(), MAX_DESCRIPTION_LENGTH, "Reason")
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let reason = sanitize_string(payload.reason.trim());
+
+    let from = check_transition(&pool, &poll_id, PollStatus::Cancelled).await?;
+
+    let result =
+        sqlx::query("UPDATE polls SET status = ?, cancel_reason = ? WHERE id = ? AND status = ?")
+            .bind(PollStatus::Cancelled.as_str())
+            .bind(&reason)
+            .bind(&poll_id)
+            .bind(from.as_str())
+            .execute(&pool)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to cancel poll".to_string(),
+                )
+            })?;
+
+    if result.rows_affected() == 0 {
+        return Err(concurrent_change());
+    }
+    record_event(
+        &event_store,
+        &events::poll_stream(&poll_id),
+        events::Event::PollCancelled(events::PollCancelled {
+            poll_id: poll_id.clone(),
+            reason: reason.clone(),
+            recorded_at: Utc::now().timestamp(),
+        }),
+    )
+    .await;
+
+    let title: Option<String> = sqlx::query_scalar("SELECT title FROM polls WHERE id = ?")
+        .bind(&poll_id)
+        .fetch_optional(&pool)
+        .await
+        .unwrap_or(None);
+    crate::activity_handlers::log_activity(
+        &pool,
+        "poll_cancelled",
+        "system".to_string(),
+        "Organizzatore".to_string(),
+        Some(poll_id),
+        title,
+    )
+    .await
+    .unwrap_or_else(|e| tracing::error!("Activity log error: {}", e));
+
+    Ok(Json(json!({
+        "success": true,
+        "status": PollStatus::Cancelled,
+        "reason": reason
+    })))
+}
+
+/// PUT /api/polls/:id/unfinalize
+/// Takes back a finalization: the poll is open for voting again with all its votes
+pub async fn unfinalize_poll(
+    State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
+    _organizer: crate::auth::PollOrganizer,
+    Path(poll_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let (from, _) = poll_status(&pool, &poll_id).await?;
+    if from != PollStatus::Finalized {
+        return Err((StatusCode::CONFLICT, "Poll is not finalized".to_string()));
+    }
+
+    // The old deadline has usually passed by now: drop it rather than closing right away
+    let result = sqlx::query(
+        "UPDATE polls SET status = ?, finalized_at = NULL, finalized_time = NULL, finalized_start = NULL, finalized_end = NULL, closes_at = NULL WHERE id = ? AND status = ?",
+    )
+    .bind(PollStatus::Active.as_str())
+    .bind(&poll_id)
+    .bind(from.as_str())
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to un-finalize poll".to_string(),
+        )
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err(concurrent_change());
+    }
+    record_event(
+        &event_store,
+        &events::poll_stream(&poll_id),
+        events::Event::PollUnfinalized(events::PollUnfinalized {
+            poll_id: poll_id.clone(),
+            recorded_at: Utc::now().timestamp(),
+        }),
+    )
+    .await;
+
+    Ok(Json(json!({
+        "success": true,
+        "status": PollStatus::Active
+    })))
+}
+
+/// PUT /api/polls/:id/reschedule
+/// Reopens a finalized session on new dates/slots. Votes on slots that are still
+/// offered carry over; the others are dropped.
+pub async fn reschedule_poll(
+    State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
+    _organizer: crate::auth::PollOrganizer,
+    Path(poll_id): Path<String>,
+    Json(payload): Json<models::ReschedulePollRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    lifecycle::validate_closes_at(payload.closes_at, Utc::now().timestamp())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let (from, _) = poll_status(&pool, &poll_id).await?;
+    if from != PollStatus::Finalized {
+        return Err((
+            StatusCode::CONFLICT,
+            "Only finalized sessions can be rescheduled".to_string(),
+        ));
+    }
+
+    let (current_dates, current_time_range): (String, String) =
+        sqlx::query_as("SELECT dates, time_range FROM polls WHERE id = ?")
+            .bind(&poll_id)
+            .fetch_one(&pool)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Database error".to_string(),
+                )
+            })?;
+
+    let dates = match payload.dates {
+        Some(dates) => {
+            poll_limits(&pool, &poll_id)
+                .await?
+                .validate_dates(&dates, Utc::now().date_naive())
+                .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+            dates
+        }
+        None => serde_json::from_str(&current_dates).unwrap_or_default(),
+    };
+    let time_preferences = if payload.time_preferences.is_none() && payload.time_range.is_none() {
+        TimePreferences::from_stored(&current_time_range)
+    } else {
+        TimePreferences::from_request(
+            payload.time_preferences.as_ref(),
+            payload.time_range.as_deref(),
+            &dates,
+        )
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?
+    };
+
+    let dates_json = serde_json::to_string(&dates).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to serialize dates: {}", e),
+        )
+    })?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let result = sqlx::query(
+        "UPDATE polls SET status = ?, dates = ?, time_range = ?, closes_at = ?, finalized_at = NULL, finalized_time = NULL, finalized_start = NULL, finalized_end = NULL WHERE id = ? AND status = ?",
+    )
+    .bind(PollStatus::Active.as_str())
+    .bind(&dates_json)
+    .bind(time_preferences.to_storage())
+    .bind(payload.closes_at)
+    .bind(&poll_id)
+    .bind(from.as_str())
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to reschedule poll".to_string(),
+        )
+    })?;
+    if result.rows_affected() == 0 {
+        return Err(concurrent_change());
+    }
+
+    // Carry over the votes on slots the new schedule still offers
+    let report = reconcile::reconcile_availability(
+        &mut tx,
+        &poll_id,
+        &dates,
+        &time_preferences,
+        &VoteMoves::default(),
+    )
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Database error".to_string(),
+        )
+    })?;
+
+    tx.commit()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    record_event(
+        &event_store,
+        &events::poll_stream(&poll_id),
+        events::Event::PollRescheduled(events::PollRescheduled {
+            poll_id: poll_id.clone(),
+            dates,
+            closes_at: payload.closes_at,
+            recorded_at: Utc::now().timestamp(),
+        }),
+    )
+    .await;
+    record_vote_fates(&event_store, &report.changes).await;
+
+    Ok(Json(json!({
+        "success": true,
+        "status": PollStatus::Active,
+        "carriedVotes": report.kept,
+        "droppedVotes": report.dropped,
+        "affectedParticipants": report.affected
+    })))
+}
@@ -0,0 +1,211 @@
+// Current User Handlers
+// Views scoped to the logged-in user, so the dashboard doesn't have to piece them
+// together from the global poll list.
+
+use crate::core::models::{ClaimParticipationsRequest, Poll};
+use crate::core::scheduling::{claims, lifecycle, session_time, timezone};
+use crate::db::DbPool;
+use crate::security::auth::AuthUser;
+use axum::{extract::State, http::StatusCode, Json};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn db_error(e: sqlx::Error) -> (StatusCode, String) {
+    tracing::error!("My polls query failed: {}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Database error".to_string(),
+    )
+}
+
+/// GET /api/me/polls
+/// Polls the user organizes or takes part in through their account, each with the
+/// user's own response: "responded" once they voted, "pending" otherwise. Invitations
+/// sent to the account's email only show up once claimed (see `claim_participations`),
+/// since that address is not verified.
+pub async fn list_my_polls(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let user = auth_user.0;
+
+    let polls: Vec<Poll> = sqlx::query_as(
+        "SELECT * FROM polls WHERE organizer_id = ?
+            OR EXISTS (SELECT 1 FROM participants p WHERE p.poll_id = polls.id AND p.user_id = ?)
+         ORDER BY created_at DESC, id DESC",
+    )
+    .bind(&user.id)
+    .bind(&user.id)
+    .fetch_all(&pool)
+    .await
+    .map_err(db_error)?;
+
+    // The user's participation in each poll, with their vote counts per status
+    let rows: Vec<(String, String, Option<String>, Option<i64>)> = sqlx::query_as(
+        "SELECT p.poll_id, p.id, a.status, COUNT(a.id) FROM participants p
+         LEFT JOIN availability a ON a.participant_id = p.id
+         WHERE p.user_id = ?
+         GROUP BY p.poll_id, p.id, a.status",
+    )
+    .bind(&user.id)
+    .fetch_all(&pool)
+    .await
+    .map_err(db_error)?;
+
+    let mut participations: HashMap<String, (String, HashMap<String, i64>)> = HashMap::new();
+    for (poll_id, participant_id, status, count) in rows {
+        let entry = participations
+            .entry(poll_id)
+            .or_insert_with(|| (participant_id, HashMap::new()));
+        if let Some(status) = status {
+            *entry.1.entry(status).or_default() += count.unwrap_or(0);
+        }
+    }
+
+    let participant_counts: HashMap<String, i64> = sqlx::query_as(
+        "SELECT poll_id, COUNT(*) FROM participants WHERE poll_id IN (
+            SELECT id FROM polls WHERE organizer_id = ?
+            UNION SELECT poll_id FROM participants WHERE user_id = ?)
+         GROUP BY poll_id",
+    )
+    .bind(&user.id)
+    .bind(&user.id)
+    .fetch_all(&pool)
+    .await
+    .map_err(db_error)?
+    .into_iter()
+    .collect();
+
+    let now = Utc::now().timestamp();
+    let mut organized = 0;
+    let mut pending = 0;
+    let mut result = Vec::with_capacity(polls.len());
+    for poll in polls {
+        let is_organizer = poll.organizer_id.as_deref() == Some(user.id.as_str());
+        let participation = participations.get(&poll.id);
+        let voting_open = lifecycle::voting_open(poll.status, poll.closes_at, now);
+
+        let my_response = participation.map(|(participant_id, votes)| {
+            let count = |status: &str| votes.get(status).copied().unwrap_or(0);
+            let status = if votes.is_empty() {
+                "pending"
+            } else {
+                "responded"
+            };
+            if status == "pending" && voting_open {
+                pending += 1;
+            }
+            json!({
+                "participantId": participant_id,
+                "status": status,
+                "available": count("available"),
+                "tentative": count("tentative"),
+                "busy": count("busy")
+            })
+        });
+        if is_organizer {
+            organized += 1;
+        }
+
+        let poll_tz = timezone::parse_timezone(&poll.timezone).unwrap_or(chrono_tz::Tz::UTC);
+        let session = session_time::from_columns(poll.finalized_start, poll.finalized_end)
+            .map(|session| session.to_value(poll_tz));
+        let dates: Vec<String> = serde_json::from_str(&poll.dates).unwrap_or_default();
+
+        result.push(json!({
+            "id": poll.id,
+            "title": poll.title,
+            "description": poll.description,
+            "status": poll.status,
+            "location": poll.location,
+            "dates": dates,
+            "timezone": poll.timezone,
+            "closesAt": poll.closes_at,
+            "votingOpen": voting_open,
+            "finalizedTime": poll.finalized_time,
+            "session": session,
+            "campaignId": poll.campaign_id,
+            "participantCount": participant_counts.get(&poll.id).copied().unwrap_or(0),
+            "isOrganizer": is_organizer,
+            "myResponse": my_response
+        }));
+    }
+
+    Ok(Json(json!({
+        "polls": result,
+        "total": result.len(),
+        "organized": organized,
+        "awaitingResponse": pending
+    })))
+}
+
+/// GET /api/me/participations/claimable
+/// Participations made without an account that carry the user's email
+pub async fn list_claimable_participations(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let participations = claims::claimable_by_email(&pool, &auth_user.0)
+        .await
+        .map_err(db_error)?;
+    Ok(Json(json!({ "participations": participations })))
+}
+
+/// POST /api/me/participations/claim
+/// Links participations to the account by their access tokens. Without tokens, emails the
+/// account address a confirmation link for each participation matching it.
+pub async fn claim_participations(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+    Json(payload): Json<ClaimParticipationsRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let user = auth_user.0;
+
+    let mut confirmation_sent = 0;
+    if payload.participant_ids.is_some() || payload.access_tokens.is_empty() {
+        let tokens = claims::confirmation_tokens(&pool, &user, payload.participant_ids.as_deref())
+            .await
+            .map_err(db_error)?;
+        confirmation_sent = tokens.len();
+        if !tokens.is_empty() {
+            let email = user.email.clone();
+            let sessions: Vec<(String, String)> = tokens
+                .into_iter()
+                .map(|(participation, token)| {
+                    let link =
+                        crate::core::services::email::claim_link(&participation.poll_id, &token);
+                    (participation.poll_title, link)
+                })
+                .collect();
+            tokio::spawn(async move {
+                if let Err(e) =
+                    crate::core::services::email::send_claim_confirmation_email(&email, &sessions)
+                        .await
+                {
+                    tracing::error!(
+                        "Failed to send claim confirmation email to {}: {}",
+                        email,
+                        e
+                    );
+                }
+            });
+        }
+    }
+
+    let mut claimed = Vec::new();
+    for token in &payload.access_tokens {
+        if let Some(id) = claims::claim_by_token(&pool, &user, token)
+            .await
+            .map_err(db_error)?
+        {
+            claimed.push(id);
+        }
+    }
+
+    Ok(Json(json!({
+        "claimed": claimed,
+        "count": claimed.len(),
+        "confirmationSent": confirmation_sent
+    })))
+}
@@ -1,3 +1,7 @@
 pub mod activity;
 pub mod admin;
+pub mod campaigns;
 pub mod general;
+pub mod me;
+pub mod series;
+pub mod templates;
@@ -0,0 +1,471 @@
+// Session Series Handlers
+// Recurring sessions of a campaign (e.g. every other Friday 20:00-23:00). The
+// recurring_sessions job turns upcoming occurrences into polls; exceptions skip or
+// move single occurrences before they are generated.
+
+use crate::api::handlers::campaigns::{can_manage, fetch_campaign, is_member};
+use crate::core::jobs::recurring_sessions;
+use crate::core::models::{
+    Campaign, SeriesException, SeriesExceptionRequest, SessionSeries, SessionSeriesRequest, User,
+};
+use crate::core::scheduling::recurrence::{
+    self, DEFAULT_LEAD_WEEKS, EXCEPTION_MOVE, EXCEPTION_SKIP, MAX_INTERVAL_WEEKS, MAX_LEAD_WEEKS,
+    MODE_POLL, MODE_SESSION,
+};
+use crate::core::scheduling::timezone::{self, DEFAULT_TIMEZONE};
+use crate::core::store::RedbEventStore;
+use crate::db::DbPool;
+use crate::security::auth::AuthUser;
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Occurrences listed per series by `list_series`
+const PREVIEW_OCCURRENCES: usize = 6;
+
+fn db_error(e: sqlx::Error) -> (StatusCode, String) {
+    tracing::error!("Session series query failed: {}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Database error".to_string(),
+    )
+}
+
+/// Validated, normalized form of a create/update request
+struct SeriesInput {
+    first_date: String,
+    interval_weeks: i64,
+    start_time: String,
+    end_time: String,
+    timezone: String,
+    mode: String,
+    lead_weeks: i64,
+    status: String,
+}
+
+fn validate_request(payload: SessionSeriesRequest) -> Result<SeriesInput, String> {
+    let first_date = recurrence::parse_date(&payload.first_date)?;
+    let start_time = recurrence::parse_time(&payload.start_time)?;
+    let end_time = recurrence::parse_time(&payload.end_time)?;
+    recurrence::session_duration(start_time, end_time)?;
+
+    let interval_weeks = payload.interval_weeks.unwrap_or(1);
+    if !(1..=MAX_INTERVAL_WEEKS).contains(&interval_weeks) {
+        return Err(format!(
+            "Interval must be between 1 and {} weeks",
+            MAX_INTERVAL_WEEKS
+        ));
+    }
+    let lead_weeks = payload.lead_weeks.unwrap_or(DEFAULT_LEAD_WEEKS);
+    if !(0..=MAX_LEAD_WEEKS).contains(&lead_weeks) {
+        return Err(format!(
+            "Lead time must be between 0 and {} weeks",
+            MAX_LEAD_WEEKS
+        ));
+    }
+
+    let mode = payload.mode.unwrap_or_else(|| MODE_POLL.to_string());
+    if mode != MODE_POLL && mode != MODE_SESSION {
+        return Err("Mode must be 'poll' or 'session'".to_string());
+    }
+    let status = payload.status.unwrap_or_else(|| "active".to_string());
+    if status != "active" && status != "paused" {
+        return Err("Status must be 'active' or 'paused'".to_string());
+    }
+
+    let timezone = match payload.timezone.as_deref() {
+        Some(tz) if !tz.trim().is_empty() => timezone::parse_timezone(tz)?.name().to_string(),
+        _ => DEFAULT_TIMEZONE.to_string(),
+    };
+
+    Ok(SeriesInput {
+        first_date: first_date.format("%Y-%m-%d").to_string(),
+        interval_weeks,
+        start_time: start_time.format("%H:%M").to_string(),
+        end_time: end_time.format("%H:%M").to_string(),
+        timezone,
+        mode,
+        lead_weeks,
+        status,
+    })
+}
+
+async fn fetch_series(
+    pool: &DbPool,
+    series_id: &str,
+) -> Result<SessionSeries, (StatusCode, String)> {
+    sqlx::query_as("SELECT * FROM session_series WHERE id = ?")
+        .bind(series_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(db_error)?
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            "Session series not found".to_string(),
+        ))
+}
+
+/// Loads a series and its campaign, requiring the user to run the campaign
+async fn fetch_managed_series(
+    pool: &DbPool,
+    user: &User,
+    series_id: &str,
+) -> Result<(SessionSeries, Campaign), (StatusCode, String)> {
+    let series = fetch_series(pool, series_id).await?;
+    let campaign = fetch_campaign(pool, &series.campaign_id).await?;
+    if !can_manage(user, &campaign) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only the campaign's DM can change its recurring sessions".to_string(),
+        ));
+    }
+    Ok((series, campaign))
+}
+
+/// Generates whatever the new rules make due right away instead of waiting for the job
+async fn generate_now(
+    pool: &DbPool,
+    event_store: &RedbEventStore,
+    series_id: &str,
+) -> Result<u64, (StatusCode, String)> {
+    let series = fetch_series(pool, series_id).await?;
+    if series.status != "active" {
+        return Ok(0);
+    }
+    recurring_sessions::generate_for_series(pool, event_store, &series, Utc::now())
+        .await
+        .map_err(db_error)
+}
+
+/// Next occurrences of a series with exceptions applied and the poll generated for each
+async fn upcoming_occurrences(
+    pool: &DbPool,
+    series: &SessionSeries,
+) -> Result<Vec<Value>, (StatusCode, String)> {
+    let exceptions: HashMap<String, SeriesException> =
+        sqlx::query_as::<_, SeriesException>("SELECT * FROM series_exceptions WHERE series_id = ?")
+            .bind(&series.id)
+            .fetch_all(pool)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|e| (e.occurrence_date.clone(), e))
+            .collect();
+    let generated: HashMap<String, Option<String>> = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT occurrence_date, poll_id FROM series_occurrences WHERE series_id = ?",
+    )
+    .bind(&series.id)
+    .fetch_all(pool)
+    .await
+    .map_err(db_error)?
+    .into_iter()
+    .collect();
+
+    let (Ok(first_date), Ok(start_time), Ok(end_time)) = (
+        recurrence::parse_date(&series.first_date),
+        recurrence::parse_time(&series.start_time),
+        recurrence::parse_time(&series.end_time),
+    ) else {
+        return Ok(Vec::new());
+    };
+    let duration = end_time - start_time;
+
+    let tz = timezone::parse_timezone(&series.timezone).unwrap_or(chrono_tz::Tz::UTC);
+    let today = Utc::now().with_timezone(&tz).date_naive();
+    let until = today + Duration::weeks(series.interval_weeks * PREVIEW_OCCURRENCES as i64);
+
+    Ok(
+        recurrence::occurrences_between(first_date, series.interval_weeks, today, until)
+            .into_iter()
+            .take(PREVIEW_OCCURRENCES)
+            .map(|date| {
+                let key = date.format("%Y-%m-%d").to_string();
+                let exception = exceptions.get(&key);
+                let resolved = recurrence::resolve(date, start_time, duration, exception);
+                json!({
+                    "occurrenceDate": key,
+                    "date": resolved.as_ref().map(|o| o.date.format("%Y-%m-%d").to_string()),
+                    "startTime": resolved.as_ref().map(|o| o.start_time.format("%H:%M").to_string()),
+                    "exception": exception.map(|e| e.action.clone()),
+                    "pollId": generated.get(&key).cloned().flatten()
+                })
+            })
+            .collect(),
+    )
+}
+
+/// GET /api/campaigns/:id/series
+pub async fn list_series(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+    Path(campaign_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let user = auth_user.0;
+    let campaign = fetch_campaign(&pool, &campaign_id).await?;
+    if !can_manage(&user, &campaign)
+        && !is_member(&pool, &user, &campaign.id)
+            .await
+            .map_err(db_error)?
+    {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "You are not part of this campaign".to_string(),
+        ));
+    }
+
+    let series: Vec<SessionSeries> =
+        sqlx::query_as("SELECT * FROM session_series WHERE campaign_id = ? ORDER BY created_at")
+            .bind(&campaign.id)
+            .fetch_all(&pool)
+            .await
+            .map_err(db_error)?;
+
+    let mut result = Vec::new();
+    for s in series {
+        let upcoming = upcoming_occurrences(&pool, &s).await?;
+        result.push(json!({ "series": s, "upcoming": upcoming }));
+    }
+
+    Ok(Json(json!(result)))
+}
+
+/// POST /api/campaigns/:id/series
+pub async fn create_series(
+    State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
+    auth_user: AuthUser,
+    Path(campaign_id): Path<String>,
+    Json(payload): Json<SessionSeriesRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let user = auth_user.0;
+    let campaign = fetch_campaign(&pool, &campaign_id).await?;
+    if !can_manage(&user, &campaign) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only the campaign's DM can schedule its sessions".to_string(),
+        ));
+    }
+
+    let input = validate_request(payload).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let series_id = Uuid::new_v4().to_string();
+
+    sqlx::query(
+        "INSERT INTO session_series (id, campaign_id, first_date, interval_weeks, start_time, end_time, timezone, mode, lead_weeks, status, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&series_id)
+    .bind(&campaign.id)
+    .bind(&input.first_date)
+    .bind(input.interval_weeks)
+    .bind(&input.start_time)
+    .bind(&input.end_time)
+    .bind(&input.timezone)
+    .bind(&input.mode)
+    .bind(input.lead_weeks)
+    .bind(&input.status)
+    .bind(Utc::now().timestamp())
+    .execute(&pool)
+    .await
+    .map_err(db_error)?;
+
+    let generated = generate_now(&pool, &event_store, &series_id).await?;
+
+    Ok(Json(json!({
+        "id": series_id,
+        "generated": generated
+    })))
+}
+
+/// PUT /api/series/:id
+/// Already generated occurrences are kept as they are
+pub async fn update_series(
+    State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
+    auth_user: AuthUser,
+    Path(series_id): Path<String>,
+    Json(payload): Json<SessionSeriesRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let (series, _) = fetch_managed_series(&pool, &auth_user.0, &series_id).await?;
+    let input = validate_request(payload).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    sqlx::query(
+        "UPDATE session_series SET first_date = ?, interval_weeks = ?, start_time = ?, end_time = ?, timezone = ?, mode = ?, lead_weeks = ?, status = ? WHERE id = ?",
+    )
+    .bind(&input.first_date)
+    .bind(input.interval_weeks)
+    .bind(&input.start_time)
+    .bind(&input.end_time)
+    .bind(&input.timezone)
+    .bind(&input.mode)
+    .bind(input.lead_weeks)
+    .bind(&input.status)
+    .bind(&series.id)
+    .execute(&pool)
+    .await
+    .map_err(db_error)?;
+
+    let generated = generate_now(&pool, &event_store, &series.id).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "generated": generated
+    })))
+}
+
+/// DELETE /api/series/:id
+/// Stops the recurrence; polls generated so far are kept
+pub async fn delete_series(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+    Path(series_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let (series, _) = fetch_managed_series(&pool, &auth_user.0, &series_id).await?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    for query in [
+        "DELETE FROM series_exceptions WHERE series_id = ?",
+        "DELETE FROM series_occurrences WHERE series_id = ?",
+        "DELETE FROM session_series WHERE id = ?",
+    ] {
+        sqlx::query(query)
+            .bind(&series.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(json!({ "success": true })))
+}
+
+/// PUT /api/series/:id/exceptions/:date
+/// Skips or moves one occurrence, identified by the date it would normally fall on
+pub async fn set_series_exception(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+    Path((series_id, occurrence_date)): Path<(String, String)>,
+    Json(payload): Json<SeriesExceptionRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let (series, _) = fetch_managed_series(&pool, &auth_user.0, &series_id).await?;
+
+    let date =
+        recurrence::parse_date(&occurrence_date).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let first_date = recurrence::parse_date(&series.first_date)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if !recurrence::is_occurrence(first_date, series.interval_weeks, date) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("{} is not an occurrence of this series", occurrence_date),
+        ));
+    }
+
+    let (moved_date, moved_start_time) = match payload.action.as_str() {
+        EXCEPTION_SKIP => (None, None),
+        EXCEPTION_MOVE => {
+            let moved_date = payload
+                .moved_date
+                .as_deref()
+                .map(recurrence::parse_date)
+                .transpose()
+                .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+            let moved_start_time = payload
+                .moved_start_time
+                .as_deref()
+                .map(recurrence::parse_time)
+                .transpose()
+                .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+            if moved_date.is_none() && moved_start_time.is_none() {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "A moved occurrence needs a new date or start time".to_string(),
+                ));
+            }
+
+            let tz = timezone::parse_timezone(&series.timezone).unwrap_or(chrono_tz::Tz::UTC);
+            if moved_date.is_some_and(|d| d < Utc::now().with_timezone(&tz).date_naive()) {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "Cannot move an occurrence into the past".to_string(),
+                ));
+            }
+            (
+                moved_date.map(|d| d.format("%Y-%m-%d").to_string()),
+                moved_start_time.map(|t| t.format("%H:%M").to_string()),
+            )
+        }
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Action must be 'skip' or 'move'".to_string(),
+            ))
+        }
+    };
+
+    let generated: Option<i64> = sqlx::query_scalar(
+        "SELECT 1 FROM series_occurrences WHERE series_id = ? AND occurrence_date = ?",
+    )
+    .bind(&series.id)
+    .bind(&occurrence_date)
+    .fetch_optional(&pool)
+    .await
+    .map_err(db_error)?;
+    if generated.is_some() {
+        return Err((
+            StatusCode::CONFLICT,
+            "This occurrence was already generated; edit or delete its poll instead".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        "INSERT INTO series_exceptions (series_id, occurrence_date, action, moved_date, moved_start_time, created_at) VALUES (?, ?, ?, ?, ?, ?)
+         ON CONFLICT(series_id, occurrence_date) DO UPDATE SET action = excluded.action, moved_date = excluded.moved_date, moved_start_time = excluded.moved_start_time",
+    )
+    .bind(&series.id)
+    .bind(&occurrence_date)
+    .bind(&payload.action)
+    .bind(&moved_date)
+    .bind(&moved_start_time)
+    .bind(Utc::now().timestamp())
+    .execute(&pool)
+    .await
+    .map_err(db_error)?;
+
+    Ok(Json(json!({ "success": true })))
+}
+
+/// DELETE /api/series/:id/exceptions/:date
+pub async fn delete_series_exception(
+    State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
+    auth_user: AuthUser,
+    Path((series_id, occurrence_date)): Path<(String, String)>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let (series, _) = fetch_managed_series(&pool, &auth_user.0, &series_id).await?;
+
+    sqlx::query("DELETE FROM series_exceptions WHERE series_id = ? AND occurrence_date = ?")
+        .bind(&series.id)
+        .bind(&occurrence_date)
+        .execute(&pool)
+        .await
+        .map_err(db_error)?;
+
+    let generated = generate_now(&pool, &event_store, &series.id).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "generated": generated
+    })))
+}
@@ -0,0 +1,395 @@
+// Poll Template Handlers
+// Weekly games reuse the same setup: templates keep a poll's text, slots and invitees,
+// and new polls (from a template or by duplicating a poll) shift its dates forward.
+
+use crate::api::handlers::general::{
+    create_poll, sanitize_string, unsanitize_string, validate_email, validate_string_length,
+    validate_uuid, MAX_DESCRIPTION_LENGTH, MAX_LOCATION_LENGTH, MAX_TITLE_LENGTH,
+};
+use crate::core::models::{
+    CopyPollRequest, CreatePollRequest, Poll, PollTemplate, PollTemplateRequest, User,
+};
+use crate::core::scheduling::limits::PollLimits;
+use crate::core::scheduling::time_preferences::{self, TimePreferences};
+use crate::core::scheduling::{attendance, blocks, timezone};
+use crate::core::store::RedbEventStore;
+use crate::db::DbPool;
+use crate::security::auth::{AuthUser, MaybeAuthUser, PollOrganizer};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::{NaiveDate, Utc};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Ten years either way is plenty for a copy
+const MAX_OFFSET_DAYS: i64 = 3650;
+
+fn db_error(e: sqlx::Error) -> (StatusCode, String) {
+    tracing::error!("Template query failed: {}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Database error".to_string(),
+    )
+}
+
+fn can_use(user: &User, template: &PollTemplate) -> bool {
+    user.role == "admin" || user.id == template.owner_id
+}
+
+async fn fetch_template(
+    pool: &DbPool,
+    template_id: &str,
+    user: &User,
+) -> Result<PollTemplate, (StatusCode, String)> {
+    validate_uuid(template_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let template: PollTemplate = sqlx::query_as("SELECT * FROM poll_templates WHERE id = ?")
+        .bind(template_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(db_error)?
+        .ok_or((StatusCode::NOT_FOUND, "Template not found".to_string()))?;
+    if !can_use(user, &template) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only the template's owner can use it".to_string(),
+        ));
+    }
+    Ok(template)
+}
+
+/// A poll's setup as an (unsaved) template: its text, slots and invitees
+async fn template_from_poll(
+    pool: &DbPool,
+    poll: &Poll,
+    owner_id: &str,
+    name: &str,
+) -> Result<PollTemplate, sqlx::Error> {
+    let invitees: Vec<(String, String)> = sqlx::query_as(
+        "SELECT email, attendance FROM participants WHERE poll_id = ? AND email IS NOT NULL ORDER BY rowid",
+    )
+    .bind(&poll.id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PollTemplate {
+        id: Uuid::new_v4().to_string(),
+        owner_id: owner_id.to_string(),
+        name: name.to_string(),
+        title: poll.title.clone(),
+        description: poll.description.clone(),
+        location: poll.location.clone(),
+        dates: serde_json::from_str(&poll.dates).unwrap_or_default(),
+        time_preferences: poll.time_range.clone(),
+        required_participants: invitees
+            .iter()
+            .filter(|(_, status)| status == attendance::ATTENDANCE_REQUIRED)
+            .map(|(email, _)| email.clone())
+            .collect(),
+        participants: invitees.into_iter().map(|(email, _)| email).collect(),
+        timezone: poll.timezone.clone(),
+        session_minutes: poll.session_minutes,
+        created_at: Utc::now().timestamp(),
+    })
+}
+
+/// Days to move the template's dates by: `offsetDays`, or enough for the first date to
+/// land on `startDate`
+fn copy_offset(copy: &CopyPollRequest, dates: &[String]) -> Result<i64, String> {
+    let offset = match (&copy.start_date, copy.offset_days) {
+        (Some(start), _) => {
+            let start = NaiveDate::parse_from_str(start, "%Y-%m-%d")
+                .map_err(|_| format!("Invalid start date: {}", start))?;
+            let first = dates
+                .iter()
+                .filter_map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+                .min()
+                .ok_or_else(|| "The original has no dates to shift".to_string())?;
+            (start - first).num_days()
+        }
+        (None, Some(days)) => days,
+        (None, None) => return Err("Either offsetDays or startDate is required".to_string()),
+    };
+    if offset.abs() > MAX_OFFSET_DAYS {
+        return Err(format!(
+            "Dates can be shifted by at most {} days",
+            MAX_OFFSET_DAYS
+        ));
+    }
+    Ok(offset)
+}
+
+/// The create request for a new poll from `template`; `create_poll` validates it like
+/// any other (e.g. shifted dates must not be in the past)
+fn copy_request(
+    template: &PollTemplate,
+    copy: &CopyPollRequest,
+) -> Result<CreatePollRequest, String> {
+    let offset = copy_offset(copy, &template.dates)?;
+    let dates = template
+        .dates
+        .iter()
+        .map(|date| time_preferences::shift_date(date, offset))
+        .collect::<Result<Vec<_>, _>>()?;
+    // Free text from old clients can't be validated again, so it isn't carried over
+    let time_preferences = match template.time_preferences.shifted(offset)? {
+        TimePreferences::Legacy(_) => None,
+        prefs => Some(prefs.to_value()),
+    };
+
+    Ok(CreatePollRequest {
+        title: copy
+            .title
+            .clone()
+            .unwrap_or_else(|| unsanitize_string(&template.title)),
+        description: unsanitize_string(&template.description),
+        location: unsanitize_string(&template.location),
+        dates,
+        time_preferences,
+        participants: template.participants.clone(),
+        required_participants: Some(template.required_participants.clone()),
+        timezone: Some(template.timezone.clone()),
+        closes_at: copy.closes_at,
+        session_minutes: template.session_minutes,
+        ..Default::default()
+    })
+}
+
+/// Checks a template built from request fields (not from a poll)
+fn validate_template_fields(
+    payload: &PollTemplateRequest,
+    limits: &PollLimits,
+) -> Result<TimePreferences, String> {
+    // Same rules as a poll, so every template can be instantiated
+    validate_string_length(&payload.title, MAX_TITLE_LENGTH, "Title")?;
+    validate_string_length(&payload.description, MAX_DESCRIPTION_LENGTH, "Description")?;
+    validate_string_length(&payload.location, MAX_LOCATION_LENGTH, "Location")?;
+
+    // Past dates are fine here: they are shifted when the template is used
+    PollLimits {
+        allow_past_dates: true,
+        ..limits.clone()
+    }
+    .validate_dates(&payload.dates, Utc::now().date_naive())?;
+
+    if payload.participants.len() > limits.max_participants {
+        return Err(format!(
+            "Too many participants (max: {})",
+            limits.max_participants
+        ));
+    }
+    for email in &payload.participants {
+        validate_email(email)?;
+    }
+    for email in &payload.required_participants {
+        if !payload.participants.contains(email) {
+            return Err(format!("Required participant is not invited: {}", email));
+        }
+    }
+
+    if let Some(tz) = &payload.timezone {
+        timezone::parse_timezone(tz)?;
+    }
+    blocks::validate_session_minutes(payload.session_minutes)?;
+
+    TimePreferences::from_request(payload.time_preferences.as_ref(), None, &payload.dates)
+}
+
+/// GET /api/templates
+pub async fn list_templates(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let templates: Vec<PollTemplate> =
+        sqlx::query_as("SELECT * FROM poll_templates WHERE owner_id = ? ORDER BY name, created_at")
+            .bind(&auth_user.0.id)
+            .fetch_all(&pool)
+            .await
+            .map_err(db_error)?;
+
+    Ok(Json(json!({ "templates": templates })))
+}
+
+/// POST /api/templates
+/// Saves a template from one of the user's polls (`pollId`) or from the given fields
+pub async fn create_template(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+    Json(payload): Json<PollTemplateRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let user = auth_user.0;
+    validate_string_length(&payload.name, MAX_TITLE_LENGTH, "Name")
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let name = sanitize_string(&payload.name);
+
+    let template = match &payload.poll_id {
+        Some(poll_id) => {
+            validate_uuid(poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+            let poll: Poll = sqlx::query_as("SELECT * FROM polls WHERE id = ?")
+                .bind(poll_id)
+                .fetch_optional(&pool)
+                .await
+                .map_err(db_error)?
+                .ok_or((StatusCode::NOT_FOUND, "Poll not found".to_string()))?;
+            if user.role != "admin" && poll.organizer_id.as_deref() != Some(user.id.as_str()) {
+                return Err((
+                    StatusCode::FORBIDDEN,
+                    "Only the poll organizer can save it as a template".to_string(),
+                ));
+            }
+            template_from_poll(&pool, &poll, &user.id, &name)
+                .await
+                .map_err(db_error)?
+        }
+        None => {
+            let time_preferences =
+                validate_template_fields(&payload, &PollLimits::for_role(Some(&user.role)))
+                    .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+            PollTemplate {
+                id: Uuid::new_v4().to_string(),
+                owner_id: user.id.clone(),
+                name,
+                title: sanitize_string(&payload.title),
+                description: sanitize_string(&payload.description),
+                location: sanitize_string(&payload.location),
+                dates: payload.dates.clone(),
+                time_preferences,
+                participants: payload.participants.clone(),
+                required_participants: payload.required_participants.clone(),
+                timezone: payload
+                    .timezone
+                    .as_deref()
+                    .and_then(|tz| timezone::parse_timezone(tz).ok())
+                    .map(|tz| tz.name().to_string())
+                    .unwrap_or_else(|| timezone::DEFAULT_TIMEZONE.to_string()),
+                session_minutes: payload.session_minutes,
+                created_at: Utc::now().timestamp(),
+            }
+        }
+    };
+
+    let to_json = |value: &Vec<String>| serde_json::to_string(value).unwrap_or_default();
+    sqlx::query(
+        "INSERT INTO poll_templates (id, owner_id, name, title, description, location, dates, time_preferences, participants, required_participants, timezone, session_minutes, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&template.id)
+    .bind(&template.owner_id)
+    .bind(&template.name)
+    .bind(&template.title)
+    .bind(&template.description)
+    .bind(&template.location)
+    .bind(to_json(&template.dates))
+    .bind(template.time_preferences.to_storage())
+    .bind(to_json(&template.participants))
+    .bind(to_json(&template.required_participants))
+    .bind(&template.timezone)
+    .bind(template.session_minutes)
+    .bind(template.created_at)
+    .execute(&pool)
+    .await
+    .map_err(db_error)?;
+
+    Ok(Json(json!({ "id": template.id, "template": template })))
+}
+
+/// GET /api/templates/:id
+pub async fn get_template(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+    Path(template_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let template = fetch_template(&pool, &template_id, &auth_user.0).await?;
+    Ok(Json(json!({ "template": template })))
+}
+
+/// DELETE /api/templates/:id
+/// Polls created from the template are kept
+pub async fn delete_template(
+    State(pool): State<DbPool>,
+    auth_user: AuthUser,
+    Path(template_id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    fetch_template(&pool, &template_id, &auth_user.0).await?;
+
+    sqlx::query("DELETE FROM poll_templates WHERE id = ?")
+        .bind(&template_id)
+        .execute(&pool)
+        .await
+        .map_err(db_error)?;
+
+    Ok(Json(json!({ "success": true })))
+}
+
+/// POST /api/polls/from-template/:template_id
+/// New poll with the template's setup, its dates shifted by the requested offset
+pub async fn create_poll_from_template(
+    State(pool): State<DbPool>,
+    event_store: Extension<Arc<RedbEventStore>>,
+    auth_user: AuthUser,
+    Path(template_id): Path<String>,
+    Json(copy): Json<CopyPollRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let user = auth_user.0;
+    let template = fetch_template(&pool, &template_id, &user).await?;
+    let request = copy_request(&template, &copy).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let Json(mut created) = create_poll(
+        State(pool),
+        event_store,
+        MaybeAuthUser(Some(user)),
+        Json(request),
+    )
+    .await?;
+    created["templateId"] = json!(template.id);
+    Ok(Json(created))
+}
+
+/// POST /api/polls/:id/duplicate
+/// Copies a poll (text, slots, invitees, ranking settings) onto shifted dates. Votes are
+/// not copied. The copy stays in the campaign only when an account made it, since
+/// scheduling campaign sessions needs the DM.
+pub async fn duplicate_poll(
+    State(pool): State<DbPool>,
+    event_store: Extension<Arc<RedbEventStore>>,
+    organizer: PollOrganizer,
+    Path(poll_id): Path<String>,
+    Json(copy): Json<CopyPollRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    validate_uuid(&poll_id).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let poll: Poll = sqlx::query_as("SELECT * FROM polls WHERE id = ?")
+        .bind(&poll_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(db_error)?
+        .ok_or((StatusCode::NOT_FOUND, "Poll not found".to_string()))?;
+
+    let owner_id = organizer
+        .user
+        .as_ref()
+        .map(|u| u.id.clone())
+        .unwrap_or_default();
+    let template = template_from_poll(&pool, &poll, &owner_id, &poll.title)
+        .await
+        .map_err(db_error)?;
+    let mut request = copy_request(&template, &copy).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    request.tentative_weight = Some(poll.tentative_weight);
+    request.tie_breaker = Some(poll.tie_breaker.clone());
+    if organizer.user.is_some() {
+        request.campaign_id = poll.campaign_id.clone();
+    }
+
+    let Json(mut created) = create_poll(
+        State(pool),
+        event_store,
+        MaybeAuthUser(organizer.user),
+        Json(request),
+    )
+    .await?;
+    created["sourcePollId"] = json!(poll_id);
+    Ok(Json(created))
+}
@@ -1,17 +1,455 @@
+// Event Schemas
+// Events are stored with bincode, which only reads back exactly the shape it wrote. Every
+// payload is therefore written as a `StoredEvent` variant naming its schema version, and
+// shapes that were ever written are kept frozen below. To change a field: freeze the
+// current `Event` as `legacy::EventV<n>`, add `StoredEvent::V<n+1>` for the new shape and
+// upcast the old one in `Event::from_bytes`. New event kinds only need a variant appended
+// at the end of `Event`.
+
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Shapes written by earlier versions, only ever decoded
+pub mod legacy {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct VoteAddedV1 {
+        pub participant_email: String, // Empty for participants who joined without one
+        pub date: String,              // ISO8601 YYYY-MM-DD
+        pub slot: String,              // e.g. "18:00"
+        pub vote: String,              // "yes", "no", "ifneedbe", or "cleared" once withdrawn
+    }
+
+    // V2 added when the vote was cast
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct VoteAddedV2 {
+        pub participant_email: String,
+        pub date: String,
+        pub slot: String,
+        pub vote: String,
+        pub recorded_at: i64, // Unix timestamp
+    }
+}
+
+// On-disk envelope: the variant is the schema version. Never reorder or remove variants.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+enum StoredEvent {
+    V1(legacy::VoteAddedV1),
+    V2(legacy::VoteAddedV2),
+    V3(Event),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vote {
+    Yes,
+    No,
+    IfNeedBe,
+    Cleared, // The participant withdrew their vote on the slot
+}
+
+impl Vote {
+    /// Vote for an availability status ("available", "busy", "tentative")
+    pub fn from_status(status: &str) -> Option<Self> {
+        match status {
+            "available" => Some(Vote::Yes),
+            "busy" => Some(Vote::No),
+            "tentative" => Some(Vote::IfNeedBe),
+            _ => None,
+        }
+    }
+
+    /// Availability status the vote stands for ("cleared" once withdrawn)
+    pub fn status(self) -> &'static str {
+        match self {
+            Vote::Yes => "available",
+            Vote::No => "busy",
+            Vote::IfNeedBe => "tentative",
+            Vote::Cleared => "cleared",
+        }
+    }
+
+    fn from_legacy(vote: &str) -> Result<Self> {
+        match vote {
+            "yes" => Ok(Vote::Yes),
+            "no" => Ok(Vote::No),
+            "ifneedbe" => Ok(Vote::IfNeedBe),
+            "cleared" => Ok(Vote::Cleared),
+            other => Err(anyhow!("Unknown vote in stored event: {}", other)),
+        }
+    }
+}
 
-// Concrete Event Definitions (V1)
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct VoteAddedV1 {
-    pub participant_email: String,
+/// A participant's vote on one slot changed. Lives in the participant's stream.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct VoteCast {
+    pub poll_id: String,
+    pub participant_id: String,
     pub date: String, // ISO8601 YYYY-MM-DD
     pub slot: String, // e.g. "18:00"
-    pub vote: String, // "yes", "no", "ifneedbe"
+    pub vote: Vote,
+    pub recorded_at: Option<i64>, // Unix timestamp; unknown for votes stored as V1
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PollCreated {
+    pub poll_id: String,
+    pub title: String,
+    pub dates: Vec<String>,
+    pub organizer_id: Option<String>, // None for anonymous organizers
+    pub recorded_at: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PollEdited {
+    pub poll_id: String,
+    pub title: String,
+    pub dates: Vec<String>,
+    pub recorded_at: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PollFinalized {
+    pub poll_id: String,
+    pub start: i64, // Unix timestamps of the session
+    pub end: i64,
+    pub recorded_at: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ParticipantJoined {
+    pub poll_id: String,
+    pub participant_id: String,
+    pub name: String,
+    pub recorded_at: i64,
+}
+
+/// Voting stopped, by the organizer or because the deadline passed
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PollClosed {
+    pub poll_id: String,
+    pub recorded_at: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PollReopened {
+    pub poll_id: String,
+    pub closes_at: Option<i64>, // New voting deadline, if any
+    pub recorded_at: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PollCancelled {
+    pub poll_id: String,
+    pub reason: String,
+    pub recorded_at: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PollUnfinalized {
+    pub poll_id: String,
+    pub recorded_at: i64,
+}
+
+/// A finalized session opened for voting again on new dates
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PollRescheduled {
+    pub poll_id: String,
+    pub dates: Vec<String>,
+    pub closes_at: Option<i64>,
+    pub recorded_at: i64,
 }
 
-// The Versioned Enum
-#[derive(Serialize, Deserialize, Debug, Clone)]
+// Current shape of every event, whatever version it was stored as
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum Event {
-    V1(VoteAddedV1),
-    // Future: V2(VoteAddedV2)
+    VoteCast(VoteCast),
+    PollCreated(PollCreated),
+    PollEdited(PollEdited),
+    PollFinalized(PollFinalized),
+    ParticipantJoined(ParticipantJoined),
+    PollClosed(PollClosed),
+    PollReopened(PollReopened),
+    PollCancelled(PollCancelled),
+    PollUnfinalized(PollUnfinalized),
+    PollRescheduled(PollRescheduled),
+}
+
+impl Event {
+    /// Encodes the event at the current schema version
+    pub fn to_bytes(&self) -> Result<Vec<u8>, bincode::Error> {
+        bincode::serialize(&StoredEvent::V3(self.clone()))
+    }
+
+    /// Decodes an event read from `stream_id`, upcasting older versions. Vote events
+    /// stored before V3 did not name their poll and participant: they come from the stream.
+    pub fn from_bytes(stream_id: &str, bytes: &[u8]) -> Result<Self> {
+        let vote_cast = |date, slot, vote: &str, recorded_at| -> Result<Self> {
+            let (poll_id, participant_id) = parse_participant_stream(stream_id)
+                .ok_or_else(|| anyhow!("Vote event outside a participant stream: {}", stream_id))?;
+            Ok(Event::VoteCast(VoteCast {
+                poll_id: poll_id.to_string(),
+                participant_id: participant_id.to_string(),
+                date,
+                slot,
+                vote: Vote::from_legacy(vote)?,
+                recorded_at,
+            }))
+        };
+
+        match bincode::deserialize(bytes)? {
+            StoredEvent::V1(e) => vote_cast(e.date, e.slot, &e.vote, None),
+            StoredEvent::V2(e) => vote_cast(e.date, e.slot, &e.vote, Some(e.recorded_at)),
+            StoredEvent::V3(event) => Ok(event),
+        }
+    }
+
+    /// When the event happened; `None` for votes stored before times were kept
+    pub fn recorded_at(&self) -> Option<i64> {
+        match self {
+            Event::VoteCast(e) => e.recorded_at,
+            Event::PollCreated(e) => Some(e.recorded_at),
+            Event::PollEdited(e) => Some(e.recorded_at),
+            Event::PollFinalized(e) => Some(e.recorded_at),
+            Event::ParticipantJoined(e) => Some(e.recorded_at),
+            Event::PollClosed(e) => Some(e.recorded_at),
+            Event::PollReopened(e) => Some(e.recorded_at),
+            Event::PollCancelled(e) => Some(e.recorded_at),
+            Event::PollUnfinalized(e) => Some(e.recorded_at),
+            Event::PollRescheduled(e) => Some(e.recorded_at),
+        }
+    }
+}
+
+/// Stream holding the vote history of one participant of a poll
+pub fn participant_stream(poll_id: &str, participant_id: &str) -> String {
+    format!("poll-{}/participant-{}", poll_id, participant_id)
+}
+
+/// Stream holding the life of a poll itself (created, edited, status changes). Not a
+/// prefix of the participant streams, which the store's range reads rely on.
+pub fn poll_stream(poll_id: &str) -> String {
+    format!("poll-{}/lifecycle", poll_id)
+}
+
+/// (poll id, participant id) of a `participant_stream`
+fn parse_participant_stream(stream_id: &str) -> Option<(&str, &str)> {
+    let (poll, participant) = stream_id.split_once('/')?;
+    Some((
+        poll.strip_prefix("poll-")?,
+        participant.strip_prefix("participant-")?,
+    ))
+}
+
+/// One event per slot whose vote differs between `before` and `after`, both given as
+/// (date, slot, status) of the participant's votes. Slots missing from `after` are
+/// recorded as cleared.
+pub fn vote_changes(
+    poll_id: &str,
+    participant_id: &str,
+    before: &[(String, String, String)],
+    after: &[(String, String, String)],
+    recorded_at: i64,
+) -> Result<Vec<Event>, String> {
+    let previous: HashMap<(&str, &str), &str> = before
+        .iter()
+        .map(|(date, slot, status)| ((date.as_str(), slot.as_str()), status.as_str()))
+        .collect();
+    let event = |date: &str, slot: &str, vote: Vote| {
+        Event::VoteCast(VoteCast {
+            poll_id: poll_id.to_string(),
+            participant_id: participant_id.to_string(),
+            date: date.to_string(),
+            slot: slot.to_string(),
+            vote,
+            recorded_at: Some(recorded_at),
+        })
+    };
+
+    let mut events = Vec::new();
+    let mut seen = HashSet::new();
+    // The last vote sent for a slot wins
+    for (date, slot, status) in after.iter().rev() {
+        if !seen.insert((date.as_str(), slot.as_str())) {
+            continue;
+        }
+        if previous.get(&(date.as_str(), slot.as_str())) != Some(&status.as_str()) {
+            let vote = Vote::from_status(status)
+                .ok_or_else(|| format!("Invalid availability status: {}", status))?;
+            events.push(event(date, slot, vote));
+        }
+    }
+    events.reverse();
+
+    for (date, slot, _) in before {
+        if seen.insert((date.as_str(), slot.as_str())) {
+            events.push(event(date, slot, Vote::Cleared));
+        }
+    }
+    Ok(events)
+}
+
+/// Replays a participant's vote history up to `as_of` (inclusive) into their
+/// (date, slot, status) votes at that moment. Undated votes predate every dated one and
+/// are always applied.
+pub fn votes_as_of(history: &[Event], as_of: i64) -> Vec<(String, String, String)> {
+    let mut votes = BTreeMap::new();
+    for event in history {
+        let Event::VoteCast(vote) = event else {
+            continue;
+        };
+        if vote.recorded_at.is_some_and(|t| t > as_of) {
+            continue;
+        }
+        let key = (vote.date.clone(), vote.slot.clone());
+        match vote.vote {
+            Vote::Cleared => votes.remove(&key),
+            other => votes.insert(key, other.status().to_string()),
+        };
+    }
+    votes
+        .into_iter()
+        .map(|((date, slot), status)| (date, slot, status))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STREAM: &str = "poll-p1/participant-u1";
+
+    fn vote(date: &str, slot: &str, status: &str) -> (String, String, String) {
+        (date.to_string(), slot.to_string(), status.to_string())
+    }
+
+    fn changes(
+        before: &[(String, String, String)],
+        after: &[(String, String, String)],
+        recorded_at: i64,
+    ) -> Vec<Event> {
+        vote_changes("p1", "u1", before, after, recorded_at).unwrap()
+    }
+
+    #[test]
+    fn test_vote_changes() {
+        let before = vec![
+            vote("2030-01-10", "20:00", "available"),
+            vote("2030-01-10", "21:00", "busy"),
+            vote("2030-01-11", "20:00", "tentative"),
+        ];
+        let after = vec![
+            vote("2030-01-10", "20:00", "available"),
+            vote("2030-01-10", "21:00", "tentative"),
+            vote("2030-01-12", "20:00", "busy"),
+        ];
+
+        let votes: Vec<(String, String, String)> = changes(&before, &after, 100)
+            .into_iter()
+            .map(|event| match event {
+                Event::VoteCast(e) => {
+                    assert_eq!(e.recorded_at, Some(100));
+                    vote(&e.date, &e.slot, e.vote.status())
+                }
+                other => panic!("Unexpected event {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            votes,
+            vec![
+                vote("2030-01-10", "21:00", "tentative"),
+                vote("2030-01-12", "20:00", "busy"),
+                vote("2030-01-11", "20:00", "cleared"),
+            ]
+        );
+
+        // Sending the same votes again changes nothing
+        assert!(changes(&after, &after, 200).is_empty());
+        assert!(vote_changes("p1", "u1", &[], &[vote("2030-01-10", "20:00", "maybe")], 1).is_err());
+    }
+
+    #[test]
+    fn test_votes_as_of() {
+        let friday = vote("2030-01-11", "20:00", "available");
+        let undated = legacy::VoteAddedV1 {
+            participant_email: String::new(),
+            date: "2030-01-10".to_string(),
+            slot: "20:00".to_string(),
+            vote: "no".to_string(),
+        };
+        let mut history = vec![Event::from_bytes(
+            STREAM,
+            &bincode::serialize(&StoredEvent::V1(undated)).unwrap(),
+        )
+        .unwrap()];
+        history.extend(changes(&[], std::slice::from_ref(&friday), 100));
+        history.extend(changes(&[friday], &[], 200));
+
+        assert_eq!(
+            votes_as_of(&history, 50),
+            vec![vote("2030-01-10", "20:00", "busy")]
+        );
+        assert_eq!(
+            votes_as_of(&history, 100),
+            vec![
+                vote("2030-01-10", "20:00", "busy"),
+                vote("2030-01-11", "20:00", "available"),
+            ]
+        );
+        // Cleared at 200: the Friday vote is gone, the undated one stays
+        assert_eq!(
+            votes_as_of(&history, 200),
+            vec![vote("2030-01-10", "20:00", "busy")]
+        );
+    }
+
+    #[test]
+    fn test_event_roundtrip() {
+        let event = Event::ParticipantJoined(ParticipantJoined {
+            poll_id: "p1".to_string(),
+            participant_id: "u1".to_string(),
+            name: "Alice".to_string(),
+            recorded_at: 100,
+        });
+        let bytes = event.to_bytes().unwrap();
+        assert_eq!(Event::from_bytes(STREAM, &bytes).unwrap(), event);
+    }
+
+    #[test]
+    fn test_upcasts_older_versions() {
+        let expected = |recorded_at| {
+            Event::VoteCast(VoteCast {
+                poll_id: "p1".to_string(),
+                participant_id: "u1".to_string(),
+                date: "2030-01-10".to_string(),
+                slot: "20:00".to_string(),
+                vote: Vote::IfNeedBe,
+                recorded_at,
+            })
+        };
+
+        // Bytes exactly as the V1 and V2 code wrote them
+        let v1 = bincode::serialize(&StoredEvent::V1(legacy::VoteAddedV1 {
+            participant_email: "p@test.com".to_string(),
+            date: "2030-01-10".to_string(),
+            slot: "20:00".to_string(),
+            vote: "ifneedbe".to_string(),
+        }))
+        .unwrap();
+        assert_eq!(Event::from_bytes(STREAM, &v1).unwrap(), expected(None));
+
+        let v2 = bincode::serialize(&StoredEvent::V2(legacy::VoteAddedV2 {
+            participant_email: String::new(),
+            date: "2030-01-10".to_string(),
+            slot: "20:00".to_string(),
+            vote: "ifneedbe".to_string(),
+            recorded_at: 100,
+        }))
+        .unwrap();
+        assert_eq!(Event::from_bytes(STREAM, &v2).unwrap(), expected(Some(100)));
+
+        // A vote needs its participant stream to be upcast
+        assert!(Event::from_bytes("poll-p1/lifecycle", &v2).is_err());
+    }
 }
@@ -0,0 +1,317 @@
+use crate::api::handlers::general::record_event;
+use crate::core::events::{self, Event, PollClosed, PollFinalized};
+use crate::core::models::{Poll, QuorumRule};
+use crate::core::scheduling::session_time::{self, SessionTime};
+use crate::core::scheduling::{quorum, recommendations, timezone};
+use crate::core::store::RedbEventStore;
+use crate::db::DbPool;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use tokio::time;
+
+const AUTO_FINALIZE_INTERVAL: u64 = 5 * 60; // 5 minutes
+
+pub async fn run_cron(pool: DbPool, event_store: Arc<RedbEventStore>) {
+    let mut interval = time::interval(time::Duration::from_secs(AUTO_FINALIZE_INTERVAL));
+
+    loop {
+        interval.tick().await;
+
+        match close_expired_polls(&pool, &event_store, Utc::now().timestamp()).await {
+            Ok(count) => {
+                if count > 0 {
+                    tracing::info!("Closed {} polls past their voting deadline", count);
+                }
+            }
+            Err(e) => tracing::error!("Closing expired polls failed: {}", e),
+        }
+
+        match finalize_ready_polls(&pool, &event_store, Utc::now().timestamp()).await {
+            Ok(count) => {
+                if count > 0 {
+                    tracing::info!("Auto-finalized {} polls", count);
+                }
+            }
+            Err(e) => tracing::error!("Auto-finalize job failed: {}", e),
+        }
+    }
+}
+
+/// Marks active polls whose `closes_at` has passed as closed.
+async fn close_expired_polls(
+    pool: &DbPool,
+    event_store: &RedbEventStore,
+    now: i64,
+) -> Result<u64, sqlx::Error> {
+    let closed: Vec<String> = sqlx::query_scalar(
+        "UPDATE polls SET status = 'closed' WHERE status = 'active' AND closes_at IS NOT NULL AND closes_at <= ? RETURNING id",
+    )
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+
+    for poll_id in &closed {
+        record_event(
+            event_store,
+            &events::poll_stream(poll_id),
+            Event::PollClosed(PollClosed {
+                poll_id: poll_id.clone(),
+                recorded_at: now,
+            }),
+        )
+        .await;
+    }
+    Ok(closed.len() as u64)
+}
+
+/// Finalizes every open or closed poll whose quorum rule is satisfied at `now`.
+async fn finalize_ready_polls(
+    pool: &DbPool,
+    event_store: &RedbEventStore,
+    now: i64,
+) -> Result<u64, sqlx::Error> {
+    let polls: Vec<Poll> = sqlx::query_as(
+        "SELECT * FROM polls WHERE status IN ('active', 'closed') AND (quorum_min_available IS NOT NULL OR quorum_deadline IS NOT NULL)",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut finalized = 0;
+    for poll in polls {
+        let rule = QuorumRule {
+            min_available: poll.quorum_min_available,
+            deadline: poll.quorum_deadline,
+        };
+
+        let ranking = match recommendations::rank_poll(pool, &poll).await {
+            Ok(ranking) => ranking,
+            Err(e) => {
+                tracing::warn!("Skipping auto-finalize of poll {}: {}", poll.id, e);
+                continue;
+            }
+        };
+
+        let Some((slot, reason)) = quorum::evaluate(
+            &rule,
+            &ranking.ranked,
+            &ranking.required_ids,
+            &ranking.availability,
+            now,
+        ) else {
+            continue;
+        };
+
+        // Same "date_time" key the frontend sends to finalize_poll
+        let finalized_time = format!("{}_{}", slot.date, slot.time_slot);
+        let poll_tz = timezone::parse_timezone(&poll.timezone).unwrap_or(chrono_tz::Tz::UTC);
+        let session = SessionTime::from_slot(
+            &slot.date,
+            &slot.time_slot,
+            poll_tz,
+            Duration::minutes(
+                poll.session_minutes
+                    .unwrap_or(session_time::DEFAULT_SESSION_MINUTES),
+            ),
+        );
+
+        // Guard on status so a concurrent manual finalization wins
+        let result = sqlx::query(
+            "UPDATE polls SET status = 'finalized', finalized_at = ?, finalized_time = ?, finalized_start = ?, finalized_end = ? WHERE id = ? AND status IN ('active', 'closed')",
+        )
+        .bind(now)
+        .bind(&finalized_time)
+        .bind(session.map(|s| s.start.timestamp()))
+        .bind(session.map(|s| s.end.timestamp()))
+        .bind(&poll.id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            continue;
+        }
+        // A slot with no instant in the poll's zone (e.g. a DST gap) has no session to record
+        if let Some(session) = session {
+            record_event(
+                event_store,
+                &events::poll_stream(&poll.id),
+                Event::PollFinalized(PollFinalized {
+                    poll_id: poll.id.clone(),
+                    start: session.start.timestamp(),
+                    end: session.end.timestamp(),
+                    recorded_at: now,
+                }),
+            )
+            .await;
+        }
+
+        tracing::info!(
+            "Poll {} auto-finalized on {} ({})",
+            poll.id,
+            finalized_time,
+            reason.as_str()
+        );
+
+        crate::activity_handlers::log_activity(
+            pool,
+            "poll_finalized",
+            "system".to_string(),
+            "Sistema".to_string(),
+            Some(poll.id.clone()),
+            Some(poll.title.clone()),
+        )
+        .await
+        .unwrap_or_else(|e| tracing::error!("Activity log error: {}", e));
+
+        finalized += 1;
+    }
+
+    Ok(finalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> DbPool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to connect to memory db");
+
+        sqlx::query("CREATE TABLE polls (id TEXT PRIMARY KEY, title TEXT NOT NULL, description TEXT NOT NULL, location TEXT NOT NULL, created_at INTEGER NOT NULL, dates TEXT NOT NULL, time_range TEXT NOT NULL, status TEXT NOT NULL DEFAULT 'active', finalized_at INTEGER, finalized_time TEXT, finalized_start INTEGER, finalized_end INTEGER, notes TEXT, organizer_id TEXT, tentative_weight REAL NOT NULL DEFAULT 0.5, tie_breaker TEXT NOT NULL DEFAULT 'earliest', quorum_min_available INTEGER, quorum_deadline INTEGER, timezone TEXT NOT NULL DEFAULT 'UTC', campaign_id TEXT, closes_at INTEGER, cancel_reason TEXT, session_minutes INTEGER)")
+            .execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE participants (id TEXT PRIMARY KEY, poll_id TEXT NOT NULL, name TEXT NOT NULL, email TEXT, access_token TEXT, user_id TEXT, attendance TEXT NOT NULL DEFAULT 'optional')")
+            .execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE availability (id INTEGER PRIMARY KEY AUTOINCREMENT, poll_id TEXT NOT NULL, participant_id TEXT NOT NULL, date TEXT NOT NULL, time_slot TEXT NOT NULL, status TEXT NOT NULL)")
+            .execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE activities (id TEXT PRIMARY KEY, activity_type TEXT NOT NULL, user_id TEXT NOT NULL, user_name TEXT NOT NULL, poll_id TEXT, poll_name TEXT, message TEXT NOT NULL, timestamp INTEGER NOT NULL)")
+            .execute(&pool).await.unwrap();
+        pool
+    }
+
+    async fn insert_poll(
+        pool: &DbPool,
+        id: &str,
+        min_available: Option<i64>,
+        deadline: Option<i64>,
+    ) {
+        sqlx::query("INSERT INTO polls (id, title, description, location, created_at, dates, time_range, quorum_min_available, quorum_deadline) VALUES (?, 'Poll', 'Desc', 'Loc', 0, ?, ?, ?, ?)")
+            .bind(id)
+            .bind(r#"["2030-01-01"]"#)
+            .bind(r#"["18:00", "20:00"]"#)
+            .bind(min_available)
+            .bind(deadline)
+            .execute(pool).await.unwrap();
+
+        for (participant, slot) in [("a", "20:00"), ("b", "20:00"), ("c", "18:00")] {
+            let participant_id = format!("{}-{}", id, participant);
+            sqlx::query("INSERT INTO participants (id, poll_id, name) VALUES (?, ?, ?)")
+                .bind(&participant_id)
+                .bind(id)
+                .bind(participant)
+                .execute(pool)
+                .await
+                .unwrap();
+            sqlx::query("INSERT INTO availability (poll_id, participant_id, date, time_slot, status) VALUES (?, ?, '2030-01-01', ?, 'available')")
+                .bind(id)
+                .bind(&participant_id)
+                .bind(slot)
+                .execute(pool).await.unwrap();
+        }
+    }
+
+    async fn poll_state(pool: &DbPool, id: &str) -> (String, Option<String>) {
+        sqlx::query_as("SELECT status, finalized_time FROM polls WHERE id = ?")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_auto_finalize_on_quorum_and_deadline() {
+        let pool = setup_test_db().await;
+        let store = RedbEventStore::in_memory().unwrap();
+
+        insert_poll(&pool, "quorum", Some(2), None).await;
+        insert_poll(&pool, "waiting", Some(3), Some(1_000)).await;
+        insert_poll(&pool, "expired", None, Some(100)).await;
+
+        let count = finalize_ready_polls(&pool, &store, 500).await.unwrap();
+        assert_eq!(count, 2);
+
+        assert_eq!(
+            poll_state(&pool, "quorum").await,
+            (
+                "finalized".to_string(),
+                Some("2030-01-01_20:00".to_string())
+            )
+        );
+        assert_eq!(
+            poll_state(&pool, "expired").await,
+            (
+                "finalized".to_string(),
+                Some("2030-01-01_20:00".to_string())
+            )
+        );
+        assert_eq!(poll_state(&pool, "waiting").await.0, "active");
+
+        // Once the deadline passes the remaining poll is closed too
+        assert_eq!(finalize_ready_polls(&pool, &store, 1_000).await.unwrap(), 1);
+        assert_eq!(poll_state(&pool, "waiting").await.0, "finalized");
+
+        let logged: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM activities")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(logged, 3);
+
+        let finalized = store
+            .read_stream(&events::poll_stream("quorum"))
+            .await
+            .unwrap();
+        assert_eq!(finalized.len(), 1);
+        assert!(matches!(
+            Event::from_bytes(&events::poll_stream("quorum"), &finalized[0]).unwrap(),
+            Event::PollFinalized(PollFinalized {
+                recorded_at: 500,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_close_expired_polls() {
+        let pool = setup_test_db().await;
+        let store = RedbEventStore::in_memory().unwrap();
+
+        insert_poll(&pool, "expired", None, None).await;
+        insert_poll(&pool, "open", None, None).await;
+        insert_poll(&pool, "quorum", Some(2), None).await;
+        sqlx::query("UPDATE polls SET closes_at = 100 WHERE id IN ('expired', 'quorum')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(close_expired_polls(&pool, &store, 100).await.unwrap(), 2);
+        assert_eq!(poll_state(&pool, "expired").await.0, "closed");
+        assert_eq!(poll_state(&pool, "open").await.0, "active");
+        let closed = store
+            .read_stream(&events::poll_stream("expired"))
+            .await
+            .unwrap();
+        assert!(matches!(
+            Event::from_bytes(&events::poll_stream("expired"), &closed[0]).unwrap(),
+            Event::PollClosed(PollClosed {
+                recorded_at: 100,
+                ..
+            })
+        ));
+
+        // Closed polls can still be finalized by their quorum rule
+        assert_eq!(finalize_ready_polls(&pool, &store, 100).await.unwrap(), 1);
+        assert_eq!(poll_state(&pool, "quorum").await.0, "finalized");
+    }
+}
@@ -1 +1,3 @@
+pub mod auto_finalize;
 pub mod cleanup;
+pub mod recurring_sessions;
@@ -0,0 +1,421 @@
+use crate::api::handlers::general::record_event;
+use crate::core::events::{self, Event, PollCreated, PollFinalized};
+use crate::core::models::{Campaign, SeriesException, SessionSeries};
+use crate::core::scheduling::recurrence::{self, Occurrence, MODE_SESSION};
+use crate::core::scheduling::session_time::SessionTime;
+use crate::core::scheduling::time_preferences::TimePreferences;
+use crate::core::scheduling::timezone;
+use crate::core::store::RedbEventStore;
+use crate::db::DbPool;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::time;
+use uuid::Uuid;
+
+const GENERATION_INTERVAL: u64 = 60 * 60; // 1 hour
+
+pub async fn run_cron(pool: DbPool, event_store: Arc<RedbEventStore>) {
+    let mut interval = time::interval(time::Duration::from_secs(GENERATION_INTERVAL));
+
+    loop {
+        interval.tick().await;
+
+        match generate_due_sessions(&pool, &event_store, Utc::now()).await {
+            Ok(count) => {
+                if count > 0 {
+                    tracing::info!("Generated {} recurring sessions", count);
+                }
+            }
+            Err(e) => tracing::error!("Recurring sessions job failed: {}", e),
+        }
+    }
+}
+
+/// Generates the upcoming occurrences of every active series of an active campaign.
+async fn generate_due_sessions(
+    pool: &DbPool,
+    event_store: &RedbEventStore,
+    now: DateTime<Utc>,
+) -> Result<u64, sqlx::Error> {
+    let series: Vec<SessionSeries> = sqlx::query_as(
+        "SELECT s.* FROM session_series s JOIN campaigns c ON c.id = s.campaign_id WHERE s.status = 'active' AND c.status = 'active'",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut generated = 0;
+    for series in series {
+        generated += generate_for_series(pool, event_store, &series, now).await?;
+    }
+    Ok(generated)
+}
+
+/// Creates a poll (or a tentative finalized session) for each occurrence of the series
+/// falling within its lead time that hasn't been generated yet.
+pub async fn generate_for_series(
+    pool: &DbPool,
+    event_store: &RedbEventStore,
+    series: &SessionSeries,
+    now: DateTime<Utc>,
+) -> Result<u64, sqlx::Error> {
+    let tz = timezone::parse_timezone(&series.timezone).unwrap_or(chrono_tz::Tz::UTC);
+    let parsed = recurrence::parse_date(&series.first_date).and_then(|first| {
+        let start = recurrence::parse_time(&series.start_time)?;
+        let end = recurrence::parse_time(&series.end_time)?;
+        Ok((first, start, recurrence::session_duration(start, end)?))
+    });
+    let (first_date, start_time, duration) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            tracing::warn!("Skipping session series {}: {}", series.id, e);
+            return Ok(0);
+        }
+    };
+
+    let Some(campaign): Option<Campaign> = sqlx::query_as("SELECT * FROM campaigns WHERE id = ?")
+        .bind(&series.campaign_id)
+        .fetch_optional(pool)
+        .await?
+    else {
+        return Ok(0);
+    };
+
+    let exceptions: HashMap<String, SeriesException> =
+        sqlx::query_as::<_, SeriesException>("SELECT * FROM series_exceptions WHERE series_id = ?")
+            .bind(&series.id)
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|e| (e.occurrence_date.clone(), e))
+            .collect();
+
+    let done: HashSet<String> =
+        sqlx::query_scalar("SELECT occurrence_date FROM series_occurrences WHERE series_id = ?")
+            .bind(&series.id)
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .collect();
+
+    let members: Vec<String> = sqlx::query_scalar(
+        "SELECT email FROM campaign_members WHERE campaign_id = ? ORDER BY added_at, email",
+    )
+    .bind(&campaign.id)
+    .fetch_all(pool)
+    .await?;
+
+    let today = now.with_timezone(&tz).date_naive();
+    let horizon = today + Duration::weeks(series.lead_weeks);
+
+    let mut generated = 0;
+    for date in recurrence::occurrences_between(first_date, series.interval_weeks, today, horizon) {
+        let key = date.format("%Y-%m-%d").to_string();
+        if done.contains(&key) {
+            continue;
+        }
+        let Some(occurrence) =
+            recurrence::resolve(date, start_time, duration, exceptions.get(&key))
+        else {
+            continue;
+        };
+        // Moved into the past: nothing left to schedule
+        if occurrence.date < today {
+            continue;
+        }
+
+        if create_occurrence_poll(
+            pool,
+            event_store,
+            series,
+            &campaign,
+            &members,
+            &occurrence,
+            now,
+        )
+        .await?
+        {
+            generated += 1;
+        }
+    }
+    Ok(generated)
+}
+
+/// Inserts the poll of one occurrence. Returns false if it was generated concurrently.
+async fn create_occurrence_poll(
+    pool: &DbPool,
+    event_store: &RedbEventStore,
+    series: &SessionSeries,
+    campaign: &Campaign,
+    members: &[String],
+    occurrence: &Occurrence,
+    now: DateTime<Utc>,
+) -> Result<bool, sqlx::Error> {
+    let poll_id = Uuid::new_v4().to_string();
+    let date = occurrence.date.format("%Y-%m-%d").to_string();
+    let slots = recurrence::session_slots(occurrence.start_time, occurrence.duration);
+    let start_slot = occurrence.start_time.format("%H:%M").to_string();
+
+    let title = format!("{} - {}", campaign.name, occurrence.date.format("%d/%m/%Y"));
+    let description = if campaign.description.trim().is_empty() {
+        format!("Sessione ricorrente di {}", campaign.name)
+    } else {
+        campaign.description.clone()
+    };
+
+    // "session" series skip the vote: the occurrence is scheduled on its start slot
+    let (status, finalized_at, finalized_time, session) = if series.mode == MODE_SESSION {
+        let tz = timezone::parse_timezone(&series.timezone).unwrap_or(chrono_tz::Tz::UTC);
+        (
+            "finalized",
+            Some(now.timestamp()),
+            Some(format!("{}_{}", date, start_slot)),
+            SessionTime::from_slot(&date, &start_slot, tz, occurrence.duration),
+        )
+    } else {
+        ("active", None, None, None)
+    };
+
+    let mut tx = pool.begin().await?;
+
+    let claimed = sqlx::query(
+        "INSERT OR IGNORE INTO series_occurrences (series_id, occurrence_date, poll_id, created_at) VALUES (?, ?, ?, ?)",
+    )
+    .bind(&series.id)
+    .bind(occurrence.occurrence_date.format("%Y-%m-%d").to_string())
+    .bind(&poll_id)
+    .bind(now.timestamp())
+    .execute(&mut *tx)
+    .await?;
+    if claimed.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    sqlx::query(
+        "INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status, finalized_at, finalized_time, finalized_start, finalized_end, admin_token, organizer_id, timezone, campaign_id, session_minutes) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&poll_id)
+    .bind(&title)
+    .bind(&description)
+    .bind(&campaign.default_location)
+    .bind(now.timestamp())
+    .bind(serde_json::json!([date]).to_string())
+    .bind(TimePreferences::Daily(slots).to_storage())
+    .bind(status)
+    .bind(finalized_at)
+    .bind(&finalized_time)
+    .bind(session.map(|s| s.start.timestamp()))
+    .bind(session.map(|s| s.end.timestamp()))
+    .bind(Uuid::new_v4().to_string())
+    .bind(&campaign.dm_id)
+    .bind(&series.timezone)
+    .bind(&campaign.id)
+    .bind(occurrence.duration.num_minutes())
+    .execute(&mut *tx)
+    .await?;
+
+    for email in members {
+        let name = email.split('@').next().unwrap_or("Player").to_string();
+        sqlx::query(
+            "INSERT INTO participants (id, poll_id, name, email, access_token) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&poll_id)
+        .bind(&name)
+        .bind(email)
+        .bind(Uuid::new_v4().to_string())
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    let stream_id = events::poll_stream(&poll_id);
+    record_event(
+        event_store,
+        &stream_id,
+        Event::PollCreated(PollCreated {
+            poll_id: poll_id.clone(),
+            title: title.clone(),
+            dates: vec![date],
+            organizer_id: Some(campaign.dm_id.clone()),
+            recorded_at: now.timestamp(),
+        }),
+    )
+    .await;
+    if let Some(session) = session {
+        record_event(
+            event_store,
+            &stream_id,
+            Event::PollFinalized(PollFinalized {
+                poll_id: poll_id.clone(),
+                start: session.start.timestamp(),
+                end: session.end.timestamp(),
+                recorded_at: now.timestamp(),
+            }),
+        )
+        .await;
+    }
+
+    tracing::info!(
+        "Series {} generated poll {} for {}",
+        series.id,
+        poll_id,
+        occurrence.occurrence_date
+    );
+
+    crate::activity_handlers::log_activity(
+        pool,
+        "poll_created",
+        "system".to_string(),
+        "Sistema".to_string(),
+        Some(poll_id),
+        Some(title),
+    )
+    .await
+    .unwrap_or_else(|e| tracing::error!("Activity log error: {}", e));
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> DbPool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to connect to memory db");
+
+        sqlx::query("CREATE TABLE polls (id TEXT PRIMARY KEY, title TEXT NOT NULL, description TEXT NOT NULL, location TEXT NOT NULL, created_at INTEGER NOT NULL, dates TEXT NOT NULL, time_range TEXT NOT NULL, status TEXT NOT NULL DEFAULT 'active', finalized_at INTEGER, finalized_time TEXT, finalized_start INTEGER, finalized_end INTEGER, notes TEXT, admin_token TEXT, organizer_id TEXT, tentative_weight REAL NOT NULL DEFAULT 0.5, tie_breaker TEXT NOT NULL DEFAULT 'earliest', quorum_min_available INTEGER, quorum_deadline INTEGER, timezone TEXT NOT NULL DEFAULT 'UTC', campaign_id TEXT, closes_at INTEGER, cancel_reason TEXT, session_minutes INTEGER)")
+            .execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE participants (id TEXT PRIMARY KEY, poll_id TEXT NOT NULL, name TEXT NOT NULL, email TEXT, access_token TEXT, user_id TEXT, attendance TEXT NOT NULL DEFAULT 'optional')")
+            .execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE activities (id TEXT PRIMARY KEY, activity_type TEXT NOT NULL, user_id TEXT NOT NULL, user_name TEXT NOT NULL, poll_id TEXT, poll_name TEXT, message TEXT NOT NULL, timestamp INTEGER NOT NULL)")
+            .execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE campaigns (id TEXT PRIMARY KEY, name TEXT NOT NULL, description TEXT NOT NULL DEFAULT '', dm_id TEXT NOT NULL, default_location TEXT NOT NULL DEFAULT '', default_time_slots TEXT NOT NULL DEFAULT '[]', status TEXT NOT NULL DEFAULT 'active', created_at INTEGER NOT NULL)")
+            .execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE campaign_members (campaign_id TEXT NOT NULL, email TEXT NOT NULL, user_id TEXT, added_at INTEGER NOT NULL, PRIMARY KEY (campaign_id, email))")
+            .execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE session_series (id TEXT PRIMARY KEY, campaign_id TEXT NOT NULL, first_date TEXT NOT NULL, interval_weeks INTEGER NOT NULL DEFAULT 1, start_time TEXT NOT NULL, end_time TEXT NOT NULL, timezone TEXT NOT NULL DEFAULT 'UTC', mode TEXT NOT NULL DEFAULT 'poll', lead_weeks INTEGER NOT NULL DEFAULT 2, status TEXT NOT NULL DEFAULT 'active', created_at INTEGER NOT NULL)")
+            .execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE series_exceptions (series_id TEXT NOT NULL, occurrence_date TEXT NOT NULL, action TEXT NOT NULL, moved_date TEXT, moved_start_time TEXT, created_at INTEGER NOT NULL, PRIMARY KEY (series_id, occurrence_date))")
+            .execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE series_occurrences (series_id TEXT NOT NULL, occurrence_date TEXT NOT NULL, poll_id TEXT, created_at INTEGER NOT NULL, PRIMARY KEY (series_id, occurrence_date))")
+            .execute(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO campaigns (id, name, dm_id, default_location, created_at) VALUES ('c1', 'Strahd', 'dm', 'Discord', 0)")
+            .execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO campaign_members (campaign_id, email, added_at) VALUES ('c1', 'a@test.com', 0), ('c1', 'b@test.com', 0)")
+            .execute(&pool).await.unwrap();
+        pool
+    }
+
+    async fn insert_series(pool: &DbPool, id: &str, mode: &str) {
+        // Every other Friday starting 2030-01-04, four weeks ahead
+        sqlx::query("INSERT INTO session_series (id, campaign_id, first_date, interval_weeks, start_time, end_time, timezone, mode, lead_weeks, created_at) VALUES (?, 'c1', '2030-01-04', 2, '20:00', '23:00', 'Europe/Rome', ?, 4, 0)")
+            .bind(id)
+            .bind(mode)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    async fn generated_polls(
+        pool: &DbPool,
+        series_id: &str,
+    ) -> Vec<(String, String, String, Option<String>)> {
+        sqlx::query_as("SELECT p.dates, p.time_range, p.status, p.finalized_time FROM series_occurrences o JOIN polls p ON p.id = o.poll_id WHERE o.series_id = ? ORDER BY o.occurrence_date")
+            .bind(series_id)
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_generates_upcoming_occurrences_once() {
+        let pool = setup_test_db().await;
+        let store = RedbEventStore::in_memory().unwrap();
+        insert_series(&pool, "s1", "poll").await;
+
+        // Skip 2030-01-18, move 2030-02-01 to Saturday 18:00
+        sqlx::query("INSERT INTO series_exceptions (series_id, occurrence_date, action, created_at) VALUES ('s1', '2030-01-18', 'skip', 0)")
+            .execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO series_exceptions (series_id, occurrence_date, action, moved_date, moved_start_time, created_at) VALUES ('s1', '2030-02-01', 'move', '2030-02-02', '18:00', 0)")
+            .execute(&pool).await.unwrap();
+
+        let now = Utc.with_ymd_and_hms(2030, 1, 10, 12, 0, 0).unwrap();
+        assert_eq!(generate_due_sessions(&pool, &store, now).await.unwrap(), 1);
+
+        let polls = generated_polls(&pool, "s1").await;
+        assert_eq!(polls.len(), 1);
+        assert_eq!(polls[0].0, r#"["2030-02-02"]"#);
+        assert_eq!(polls[0].1, r#"["18:00","19:00","20:00"]"#);
+        assert_eq!(polls[0].2, "active");
+
+        let invited: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM participants")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(invited, 2);
+
+        // Running again doesn't duplicate anything
+        assert_eq!(generate_due_sessions(&pool, &store, now).await.unwrap(), 0);
+
+        // Two weeks later the next occurrence enters the window
+        let later = now + Duration::weeks(2);
+        assert_eq!(
+            generate_due_sessions(&pool, &store, later).await.unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_mode_creates_tentative_sessions() {
+        let pool = setup_test_db().await;
+        let store = RedbEventStore::in_memory().unwrap();
+        insert_series(&pool, "s2", "session").await;
+
+        let now = Utc.with_ymd_and_hms(2030, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(generate_due_sessions(&pool, &store, now).await.unwrap(), 2);
+
+        let polls = generated_polls(&pool, "s2").await;
+        assert_eq!(polls[0].2, "finalized");
+        assert_eq!(polls[0].3.as_deref(), Some("2030-01-04_20:00"));
+
+        // Each poll is recorded as created, then finalized
+        let poll_id: String = sqlx::query_scalar(
+            "SELECT poll_id FROM series_occurrences WHERE series_id = 's2' ORDER BY occurrence_date LIMIT 1",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let stream_id = events::poll_stream(&poll_id);
+        let recorded: Vec<Event> = store
+            .read_stream(&stream_id)
+            .await
+            .unwrap()
+            .iter()
+            .map(|bytes| Event::from_bytes(&stream_id, bytes).unwrap())
+            .collect();
+        assert!(matches!(
+            recorded.as_slice(),
+            [Event::PollCreated(_), Event::PollFinalized(_)]
+        ));
+
+        // Paused series are left alone
+        sqlx::query("UPDATE session_series SET status = 'paused'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let later = now + Duration::weeks(2);
+        assert_eq!(
+            generate_due_sessions(&pool, &store, later).await.unwrap(),
+            0
+        );
+    }
+}
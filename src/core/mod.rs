@@ -1,5 +1,6 @@
 pub mod events;
 pub mod jobs;
 pub mod models;
+pub mod scheduling;
 pub mod services;
 pub mod store;
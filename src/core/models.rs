@@ -1,5 +1,8 @@
+use crate::core::scheduling::lifecycle::PollStatus;
+use crate::core::scheduling::time_preferences::TimePreferences;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::collections::HashMap;
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
 pub struct Poll {
@@ -7,14 +10,27 @@ pub struct Poll {
     pub title: String,
     pub description: String,
     pub location: String,
-    pub created_at: i64,    // Unix timestamp
-    pub dates: String,      // JSON string of dates
-    pub time_range: String, // JSON string or simple string
-    pub status: String,     // "active", "finalized", "cancelled"
+    pub created_at: i64, // Unix timestamp
+    pub dates: String,   // JSON string of dates
+    #[sqlx(try_from = "String")]
+    pub time_range: TimePreferences, // Stored as JSON in the `time_range` column
+    #[sqlx(try_from = "String")]
+    pub status: PollStatus, // See `scheduling::lifecycle` for the allowed transitions
     pub finalized_at: Option<i64>,
-    pub finalized_time: Option<String>,
+    pub finalized_time: Option<String>, // Start slot key "date_HH:MM" in the poll's zone
+    pub finalized_start: Option<i64>,   // Unix timestamp the finalized session starts at
+    pub finalized_end: Option<i64>,     // Unix timestamp the finalized session ends at
     pub notes: Option<String>,
     pub organizer_id: Option<String>,
+    pub tentative_weight: f64, // Weight of a "tentative" vote when ranking slots
+    pub tie_breaker: String,   // "earliest", "latest", "most_available", "fewest_busy"
+    pub quorum_min_available: Option<i64>, // Auto-finalize once this many players are available
+    pub quorum_deadline: Option<i64>, // Unix timestamp: auto-finalize on the best slot
+    pub timezone: String,      // IANA zone the dates and slots are expressed in
+    pub campaign_id: Option<String>,
+    pub closes_at: Option<i64>, // Unix timestamp after which joins and votes are rejected
+    pub cancel_reason: Option<String>,
+    pub session_minutes: Option<i64>, // Desired session length, for block detection
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
@@ -24,6 +40,7 @@ pub struct Participant {
     pub name: String,
     pub email: Option<String>,
     pub access_token: Option<String>,
+    pub attendance: String, // "required" or "optional" (the organizer is always required)
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
@@ -37,7 +54,7 @@ pub struct Availability {
 }
 
 // Request structs
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Default)]
 pub struct CreatePollRequest {
     pub title: String,
     pub description: String,
@@ -48,12 +65,75 @@ pub struct CreatePollRequest {
     #[serde(rename = "timePreferences")]
     pub time_preferences: Option<serde_json::Value>, // New: per-day time preferences as JSON object
     pub participants: Vec<String>, // List of emails
+    #[serde(rename = "requiredParticipants")]
+    pub required_participants: Option<Vec<String>>, // Subset of `participants` that must attend
+    #[serde(rename = "tentativeWeight")]
+    pub tentative_weight: Option<f64>, // Slot ranking: weight of a tentative vote (0.0 - 1.0)
+    #[serde(rename = "tieBreaker")]
+    pub tie_breaker: Option<String>, // Slot ranking: rule for equal scores
+    pub quorum: Option<QuorumRule>,
+    pub timezone: Option<String>, // IANA zone, e.g. "Europe/Rome" (default: UTC)
+    #[serde(rename = "campaignId")]
+    pub campaign_id: Option<String>, // Fills in location, slots and participants when omitted
+    #[serde(rename = "closesAt")]
+    pub closes_at: Option<i64>, // Voting deadline (Unix timestamp)
+    #[serde(rename = "sessionMinutes")]
+    pub session_minutes: Option<i64>, // Desired session length, e.g. 240 for four hours
+    #[serde(rename = "dateMoves")]
+    pub date_moves: Option<HashMap<String, String>>, // Update only: old date -> new date, votes follow
+    #[serde(rename = "slotMoves")]
+    pub slot_moves: Option<HashMap<String, String>>, // Update only: old "HH:MM" -> new, votes follow
+    #[serde(rename = "notifyAffected")]
+    pub notify_affected: Option<bool>, // Update only: email players whose votes moved or were dropped
+}
+
+/// Automatic finalization rule attached to a poll
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct QuorumRule {
+    #[serde(rename = "minAvailable")]
+    pub min_available: Option<i64>, // Players that must be available on the same slot
+    pub deadline: Option<i64>, // Unix timestamp after which voting ends
+}
+
+/// Start of the session (an offered slot) plus either its end or its duration.
+/// Times without an offset are read in `timezone`, defaulting to the poll's zone.
+#[derive(Debug, Deserialize, Default)]
+pub struct FinalizePollRequest {
+    pub start: Option<String>,
+    pub end: Option<String>,
+    #[serde(rename = "durationMinutes")]
+    pub duration_minutes: Option<i64>,
+    pub timezone: Option<String>,
+    pub finalized_time: Option<String>, // Deprecated: slot key "date_HH:MM", same as `start`
+    pub notes: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
-pub struct FinalizePollRequest {
-    pub finalized_time: String,
-    pub notes: Option<String>,
+pub struct CancelPollRequest {
+    pub reason: String,
+}
+
+/// Moves a finalized session back to voting; votes on slots still offered are kept
+#[derive(Debug, Deserialize, Default)]
+pub struct ReschedulePollRequest {
+    pub dates: Option<Vec<String>>, // Omitted: keep the current dates
+    #[serde(rename = "timeRange")]
+    pub time_range: Option<String>,
+    #[serde(rename = "timePreferences")]
+    pub time_preferences: Option<serde_json::Value>,
+    #[serde(rename = "closesAt")]
+    pub closes_at: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct ReopenPollRequest {
+    #[serde(rename = "closesAt")]
+    pub closes_at: Option<i64>, // New voting deadline, if any
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateAttendanceRequest {
+    pub attendance: String, // "required" or "optional"
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -62,6 +142,22 @@ pub struct JoinPollRequest {
     pub email: Option<String>,
 }
 
+/// Asks for a fresh personal link to be emailed to an invitee
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecoverLinkRequest {
+    pub email: String,
+}
+
+/// Participations to link to the signed-in account. With neither field set, every
+/// participation carrying the account's email is claimed.
+#[derive(Debug, Default, Deserialize)]
+pub struct ClaimParticipationsRequest {
+    #[serde(rename = "participantIds")]
+    pub participant_ids: Option<Vec<String>>,
+    #[serde(rename = "accessTokens", default)]
+    pub access_tokens: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Admin {
     pub id: String,
@@ -179,6 +275,7 @@ pub struct User {
     pub created_at: i64,
     pub last_login: Option<i64>,
     pub phone: Option<String>,
+    pub timezone: Option<String>, // IANA zone used to present poll times
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
@@ -217,6 +314,7 @@ pub struct UserPublic {
     pub name: String,
     pub role: String,
     pub phone: Option<String>,
+    pub timezone: Option<String>,
     pub created_at: i64,
 }
 
@@ -228,6 +326,7 @@ impl From<User> for UserPublic {
             name: user.name,
             role: user.role,
             phone: user.phone,
+            timezone: user.timezone,
             created_at: user.created_at,
         }
     }
@@ -313,6 +412,168 @@ impl Activity {
     }
 }
 
+// ============================================================================
+// CAMPAIGN MODELS
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct Campaign {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub dm_id: String, // users.id of the Dungeon Master
+    pub default_location: String,
+    #[sqlx(json)]
+    pub default_time_slots: Vec<String>, // "HH:MM" slots offered by new polls
+    pub status: String, // "active", "archived"
+    pub created_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct CampaignMember {
+    pub campaign_id: String,
+    pub email: String,
+    pub user_id: Option<String>, // Set when the email belongs to a registered user
+    pub added_at: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CampaignRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(rename = "defaultLocation", default)]
+    pub default_location: String,
+    #[serde(rename = "defaultTimeSlots", default)]
+    pub default_time_slots: Vec<String>,
+    #[serde(default)]
+    pub members: Vec<String>, // Member emails
+    pub status: Option<String>,
+}
+
+/// A recurring slot of a campaign, e.g. every other Friday 20:00-23:00
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct SessionSeries {
+    pub id: String,
+    pub campaign_id: String,
+    pub first_date: String, // YYYY-MM-DD of the first occurrence; fixes the weekday
+    pub interval_weeks: i64, // 1 = weekly, 2 = every other week, ...
+    pub start_time: String, // "HH:MM"
+    pub end_time: String,   // "HH:MM"
+    pub timezone: String,   // IANA zone of first_date/start_time
+    pub mode: String,       // "poll" (availability poll) or "session" (tentative session)
+    pub lead_weeks: i64,    // How far ahead occurrences are generated
+    pub status: String,     // "active", "paused"
+    pub created_at: i64,
+}
+
+/// Per-occurrence change to a series: skip it or move it to another date/time
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct SeriesException {
+    pub series_id: String,
+    pub occurrence_date: String, // Date the occurrence would normally fall on
+    pub action: String,          // "skip", "move"
+    pub moved_date: Option<String>,
+    pub moved_start_time: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SessionSeriesRequest {
+    #[serde(rename = "firstDate")]
+    pub first_date: String,
+    #[serde(rename = "intervalWeeks")]
+    pub interval_weeks: Option<i64>,
+    #[serde(rename = "startTime")]
+    pub start_time: String,
+    #[serde(rename = "endTime")]
+    pub end_time: String,
+    pub timezone: Option<String>,
+    pub mode: Option<String>,
+    #[serde(rename = "leadWeeks")]
+    pub lead_weeks: Option<i64>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SeriesExceptionRequest {
+    pub action: String, // "skip" or "move"
+    #[serde(rename = "movedDate")]
+    pub moved_date: Option<String>,
+    #[serde(rename = "movedStartTime")]
+    pub moved_start_time: Option<String>,
+}
+
+// ============================================================================
+// POLL TEMPLATE MODELS
+// ============================================================================
+
+/// Reusable poll setup owned by one organizer. `dates` are the dates it was saved with;
+/// new polls shift them by an offset.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct PollTemplate {
+    pub id: String,
+    #[serde(rename = "ownerId")]
+    pub owner_id: String,
+    pub name: String,
+    pub title: String,
+    pub description: String,
+    pub location: String,
+    #[sqlx(json)]
+    pub dates: Vec<String>,
+    #[serde(rename = "timePreferences")]
+    #[sqlx(try_from = "String")]
+    pub time_preferences: TimePreferences,
+    #[sqlx(json)]
+    pub participants: Vec<String>, // Invitee emails
+    #[serde(rename = "requiredParticipants")]
+    #[sqlx(json)]
+    pub required_participants: Vec<String>,
+    pub timezone: String,
+    #[serde(rename = "sessionMinutes")]
+    pub session_minutes: Option<i64>,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+}
+
+/// Saves a template either from one of the user's polls (`pollId`) or from the fields
+#[derive(Debug, Deserialize, Default)]
+pub struct PollTemplateRequest {
+    pub name: String,
+    #[serde(rename = "pollId")]
+    pub poll_id: Option<String>,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub location: String,
+    #[serde(default)]
+    pub dates: Vec<String>,
+    #[serde(rename = "timePreferences")]
+    pub time_preferences: Option<serde_json::Value>,
+    #[serde(default)]
+    pub participants: Vec<String>,
+    #[serde(rename = "requiredParticipants", default)]
+    pub required_participants: Vec<String>,
+    pub timezone: Option<String>,
+    #[serde(rename = "sessionMinutes")]
+    pub session_minutes: Option<i64>,
+}
+
+/// New poll from a template or an existing poll: dates move by `offsetDays`, or so that
+/// the first one falls on `startDate`
+#[derive(Debug, Deserialize, Default)]
+pub struct CopyPollRequest {
+    #[serde(rename = "offsetDays")]
+    pub offset_days: Option<i64>,
+    #[serde(rename = "startDate")]
+    pub start_date: Option<String>,
+    pub title: Option<String>, // Defaults to the original title
+    #[serde(rename = "closesAt")]
+    pub closes_at: Option<i64>,
+}
+
 // ============================================================================
 // REMINDER MODELS
 // ============================================================================
@@ -321,7 +582,6 @@ impl Activity {
 pub struct WhatsAppReminderRequest {
     pub phone: String,
     pub message: String,
-    #[allow(dead_code)]
     pub session_id: String,
 }
 
@@ -329,7 +589,6 @@ pub struct WhatsAppReminderRequest {
 pub struct TelegramReminderRequest {
     pub chat_id: String,
     pub message: String,
-    #[allow(dead_code)]
     pub session_id: String,
 }
 
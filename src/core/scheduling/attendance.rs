@@ -0,0 +1,104 @@
+// Participant Attendance Requirements
+// Required participants (and the poll organizer, implicitly) veto any slot they are busy in.
+
+use crate::core::models::Availability;
+use crate::db::DbPool;
+
+pub const ATTENDANCE_REQUIRED: &str = "required";
+pub const ATTENDANCE_OPTIONAL: &str = "optional";
+
+pub fn validate_attendance(attendance: &str) -> Result<(), String> {
+    match attendance {
+        ATTENDANCE_REQUIRED | ATTENDANCE_OPTIONAL => Ok(()),
+        _ => Err("Attendance must be 'required' or 'optional'".to_string()),
+    }
+}
+
+/// Participants whose presence is mandatory for the poll: those explicitly marked
+/// as required plus the organizer's own participation (the DM).
+pub async fn required_participant_ids(
+    pool: &DbPool,
+    poll_id: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT p.id FROM participants p JOIN polls ON polls.id = p.poll_id
+         WHERE p.poll_id = ? AND (p.attendance = 'required' OR (p.user_id IS NOT NULL AND p.user_id = polls.organizer_id))",
+    )
+    .bind(poll_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Required participants that voted "busy" for the given slot
+pub fn busy_required_participants<'a>(
+    required_ids: &'a [String],
+    availability: &[Availability],
+    date: &str,
+    time_slot: &str,
+) -> Vec<&'a String> {
+    required_ids
+        .iter()
+        .filter(|id| {
+            availability.iter().any(|a| {
+                &a.participant_id == *id
+                    && a.date == date
+                    && a.time_slot == time_slot
+                    && a.status == "busy"
+            })
+        })
+        .collect()
+}
+
+/// Splits a finalized time such as "2026-01-01_20:00" (frontend) or
+/// "2026-01-01 20:00" into its date and time slot parts.
+pub fn parse_slot_key(value: &str) -> Option<(String, String)> {
+    let value = value.trim();
+    let date = value.get(..10)?;
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+
+    let rest = value.get(10..)?;
+    let slot = rest.strip_prefix(['_', ' ', 'T'])?.trim();
+    if slot.is_empty() {
+        return None;
+    }
+
+    Some((date.to_string(), slot.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_slot_key() {
+        assert_eq!(
+            parse_slot_key("2026-01-01_20:00"),
+            Some(("2026-01-01".to_string(), "20:00".to_string()))
+        );
+        assert_eq!(
+            parse_slot_key("2026-01-01 20:00"),
+            Some(("2026-01-01".to_string(), "20:00".to_string()))
+        );
+        assert_eq!(parse_slot_key("Friday evening"), None);
+        assert_eq!(parse_slot_key("2026-01-01"), None);
+    }
+
+    #[test]
+    fn test_busy_required_participants() {
+        let required = vec!["dm".to_string()];
+        let votes = vec![Availability {
+            id: None,
+            poll_id: "poll".to_string(),
+            participant_id: "dm".to_string(),
+            date: "2026-01-01".to_string(),
+            time_slot: "20:00".to_string(),
+            status: "busy".to_string(),
+        }];
+
+        assert_eq!(
+            busy_required_participants(&required, &votes, "2026-01-01", "20:00").len(),
+            1
+        );
+        assert!(busy_required_participants(&required, &votes, "2026-01-01", "21:00").is_empty());
+    }
+}
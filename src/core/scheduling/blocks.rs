@@ -0,0 +1,233 @@
+// Contiguous Time Blocks
+// Sessions run for hours, so a poll can ask for a minimum length. A block is a run of
+// back-to-back offered slots on one date that is long enough, scored by how many
+// players can make every slot of it.
+
+use crate::core::models::Availability;
+use crate::core::scheduling::session_time::MAX_SESSION_MINUTES;
+use chrono::NaiveTime;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Slot length assumed when a date offers a single slot
+pub const DEFAULT_SLOT_MINUTES: i64 = 60;
+pub const MIN_SESSION_MINUTES: i64 = 30;
+
+pub fn validate_session_minutes(minutes: Option<i64>) -> Result<(), String> {
+    match minutes {
+        Some(minutes) if !(MIN_SESSION_MINUTES..=MAX_SESSION_MINUTES).contains(&minutes) => {
+            Err(format!(
+                "Session length must be between {} minutes and {} hours",
+                MIN_SESSION_MINUTES,
+                MAX_SESSION_MINUTES / 60
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TimeBlock {
+    pub date: String,
+    pub start: String, // "HH:MM" of the first slot
+    pub end: String,   // "HH:MM" the last slot ends at
+    pub slots: Vec<String>,
+    pub available: usize, // Players available on every slot
+    pub tentative: usize, // Players at least tentative on every slot, with some tentative votes
+    #[serde(rename = "everyoneAvailable")]
+    pub everyone_available: bool,
+}
+
+fn parse_slot(slot: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(slot, "%H:%M").ok()
+}
+
+/// Smallest gap between consecutive slots of a day, i.e. how long one slot lasts
+pub fn slot_minutes(slots: &[NaiveTime]) -> i64 {
+    slots
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).num_minutes())
+        .filter(|gap| *gap > 0)
+        .min()
+        .unwrap_or(DEFAULT_SLOT_MINUTES)
+}
+
+/// Every window of back-to-back slots covering `session_minutes`, best first.
+///
+/// `offered` are the poll's (date, slot) pairs as returned by `offered_slots`. Only
+/// votes of `participant_ids` count; a missing vote means the player can't make it.
+pub fn contiguous_blocks(
+    offered: &[(String, String)],
+    participant_ids: &[String],
+    availability: &[Availability],
+    session_minutes: i64,
+) -> Vec<TimeBlock> {
+    let mut days: HashMap<&str, Vec<NaiveTime>> = HashMap::new();
+    for (date, slot) in offered {
+        if let Some(time) = parse_slot(slot) {
+            days.entry(date.as_str()).or_default().push(time);
+        }
+    }
+
+    let mut votes: HashMap<(&str, &str, &str), &str> = HashMap::new();
+    for entry in availability {
+        votes.insert(
+            (
+                entry.date.as_str(),
+                entry.time_slot.as_str(),
+                entry.participant_id.as_str(),
+            ),
+            entry.status.as_str(),
+        );
+    }
+
+    let mut blocks = Vec::new();
+    for (date, mut times) in days {
+        times.sort();
+        times.dedup();
+        let step = slot_minutes(&times);
+        let needed = ((session_minutes + step - 1) / step).max(1) as usize;
+
+        // Split the day into runs of back-to-back slots
+        let mut runs: Vec<Vec<NaiveTime>> = Vec::new();
+        for time in times {
+            match runs.last_mut() {
+                Some(run) if (time - *run.last().unwrap()).num_minutes() == step => run.push(time),
+                _ => runs.push(vec![time]),
+            }
+        }
+
+        for run in runs.iter().filter(|run| run.len() >= needed) {
+            for window in run.windows(needed) {
+                let slots: Vec<String> = window
+                    .iter()
+                    .map(|t| t.format("%H:%M").to_string())
+                    .collect();
+
+                let mut available = 0;
+                let mut tentative = 0;
+                for participant in participant_ids {
+                    let statuses: Vec<&str> = slots
+                        .iter()
+                        .map(|slot| {
+                            votes
+                                .get(&(date, slot.as_str(), participant.as_str()))
+                                .copied()
+                                .unwrap_or("")
+                        })
+                        .collect();
+                    if statuses.iter().all(|s| *s == "available") {
+                        available += 1;
+                    } else if statuses
+                        .iter()
+                        .all(|s| *s == "available" || *s == "tentative")
+                    {
+                        tentative += 1;
+                    }
+                }
+
+                let last = *window.last().unwrap();
+                let (end, _) = last.overflowing_add_signed(chrono::Duration::minutes(step));
+                blocks.push(TimeBlock {
+                    date: date.to_string(),
+                    start: slots[0].clone(),
+                    end: end.format("%H:%M").to_string(),
+                    slots,
+                    available,
+                    tentative,
+                    everyone_available: !participant_ids.is_empty()
+                        && available == participant_ids.len(),
+                });
+            }
+        }
+    }
+
+    blocks.sort_by(|a, b| {
+        (
+            Reverse(a.available),
+            Reverse(a.tentative),
+            &a.date,
+            &a.start,
+        )
+            .cmp(&(
+                Reverse(b.available),
+                Reverse(b.tentative),
+                &b.date,
+                &b.start,
+            ))
+    });
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(participant: &str, slot: &str, status: &str) -> Availability {
+        Availability {
+            id: None,
+            poll_id: "poll".to_string(),
+            participant_id: participant.to_string(),
+            date: "2030-01-10".to_string(),
+            time_slot: slot.to_string(),
+            status: status.to_string(),
+        }
+    }
+
+    fn offered(slots: &[&str]) -> Vec<(String, String)> {
+        slots
+            .iter()
+            .map(|s| ("2030-01-10".to_string(), s.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_four_hour_blocks() {
+        let slots = offered(&["18:00", "19:00", "20:00", "21:00", "22:00", "23:00"]);
+        let players = vec!["a".to_string(), "b".to_string()];
+        let mut votes = Vec::new();
+        for slot in ["19:00", "20:00", "21:00", "22:00"] {
+            votes.push(vote("a", slot, "available"));
+            votes.push(vote("b", slot, "available"));
+        }
+        votes.push(vote("a", "18:00", "available"));
+        votes.push(vote("b", "18:00", "tentative"));
+        votes.push(vote("a", "23:00", "busy"));
+
+        let blocks = contiguous_blocks(&slots, &players, &votes, 240);
+        assert_eq!(blocks.len(), 3);
+
+        assert_eq!(blocks[0].start, "19:00");
+        assert_eq!(blocks[0].end, "23:00");
+        assert!(blocks[0].everyone_available);
+
+        assert_eq!(
+            (blocks[1].start.as_str(), blocks[1].available),
+            ("18:00", 1)
+        );
+        assert_eq!(blocks[1].tentative, 1);
+        assert_eq!(
+            (blocks[2].start.as_str(), blocks[2].available),
+            ("20:00", 0)
+        );
+        assert!(!blocks[2].everyone_available);
+    }
+
+    #[test]
+    fn test_gaps_break_blocks() {
+        // 21:00 isn't offered, so no three-hour run exists
+        let slots = offered(&["19:00", "20:00", "22:00", "23:00"]);
+        assert!(contiguous_blocks(&slots, &[], &[], 180).is_empty());
+
+        // Half-hour slots: two hours need four of them
+        let half_hours = offered(&["20:00", "20:30", "21:00", "21:30"]);
+        let blocks = contiguous_blocks(&half_hours, &[], &[], 120);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].end, "22:00");
+
+        assert!(validate_session_minutes(Some(240)).is_ok());
+        assert!(validate_session_minutes(Some(10)).is_err());
+        assert!(validate_session_minutes(None).is_ok());
+    }
+}
@@ -0,0 +1,125 @@
+// Participation Claims
+// Players often vote through their emailed link before they have an account. Once they
+// sign in, those participations are linked to the account (participants.user_id), so the
+// session alone lets them vote and their polls show up on the dashboard. Password accounts
+// never prove they own their email, so rows matching it are only offered: linking needs
+// the row's access token, which the confirmation email sends to that address. Identity
+// providers that verified the address link the matching rows right away.
+
+use crate::core::models::User;
+use crate::db::DbPool;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ClaimableParticipation {
+    #[serde(rename = "participantId")]
+    pub participant_id: String,
+    #[serde(rename = "pollId")]
+    pub poll_id: String,
+    #[serde(rename = "pollTitle")]
+    pub poll_title: String,
+    pub name: String,
+}
+
+/// Participations not linked to any account yet that carry the user's email, skipping
+/// polls the user already takes part in with their account
+pub async fn claimable_by_email(
+    pool: &DbPool,
+    user: &User,
+) -> Result<Vec<ClaimableParticipation>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT p.id AS participant_id, p.poll_id, polls.title AS poll_title, p.name
+         FROM participants p JOIN polls ON polls.id = p.poll_id
+         WHERE p.user_id IS NULL AND LOWER(p.email) = LOWER(?)
+           AND NOT EXISTS (SELECT 1 FROM participants mine WHERE mine.poll_id = p.poll_id AND mine.user_id = ?)
+         ORDER BY polls.created_at DESC, p.id",
+    )
+    .bind(&user.email)
+    .bind(&user.id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Access tokens of the user's claimable participations (all of them, or those in
+/// `only`), for the confirmation email. Rows invited before tokens existed get one.
+pub async fn confirmation_tokens(
+    pool: &DbPool,
+    user: &User,
+    only: Option<&[String]>,
+) -> Result<Vec<(ClaimableParticipation, String)>, sqlx::Error> {
+    let mut tokens = Vec::new();
+    for participation in claimable_by_email(pool, user).await? {
+        if only.is_some_and(|ids| !ids.contains(&participation.participant_id)) {
+            continue;
+        }
+        let fresh = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            "UPDATE participants SET access_token = COALESCE(access_token, ?) WHERE id = ?",
+        )
+        .bind(&fresh)
+        .bind(&participation.participant_id)
+        .execute(pool)
+        .await?;
+        let token: String =
+            sqlx::query_scalar("SELECT access_token FROM participants WHERE id = ?")
+                .bind(&participation.participant_id)
+                .fetch_one(pool)
+                .await?;
+        tokens.push((participation, token));
+    }
+    Ok(tokens)
+}
+
+/// Links the user's claimable participations (all of them, or those in `only`) to the
+/// account. Only for addresses an identity provider verified. Returns the ids that were
+/// linked.
+pub async fn claim_by_email(
+    pool: &DbPool,
+    user: &User,
+    only: Option<&[String]>,
+) -> Result<Vec<String>, sqlx::Error> {
+    let mut claimed = Vec::new();
+    for participation in claimable_by_email(pool, user).await? {
+        if only.is_some_and(|ids| !ids.contains(&participation.participant_id)) {
+            continue;
+        }
+        if link(pool, user, &participation.participant_id).await? {
+            claimed.push(participation.participant_id);
+        }
+    }
+    Ok(claimed)
+}
+
+/// Links the participation `access_token` belongs to, whatever its email. Returns its id,
+/// or `None` if the token is unknown, already linked, or the user already takes part in
+/// that poll.
+pub async fn claim_by_token(
+    pool: &DbPool,
+    user: &User,
+    access_token: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    let participant_id: Option<String> = sqlx::query_scalar(
+        "SELECT p.id FROM participants p
+         WHERE p.access_token = ? AND p.user_id IS NULL
+           AND NOT EXISTS (SELECT 1 FROM participants mine WHERE mine.poll_id = p.poll_id AND mine.user_id = ?)",
+    )
+    .bind(access_token)
+    .bind(&user.id)
+    .fetch_optional(pool)
+    .await?;
+
+    match participant_id {
+        Some(id) if link(pool, user, &id).await? => Ok(Some(id)),
+        _ => Ok(None),
+    }
+}
+
+async fn link(pool: &DbPool, user: &User, participant_id: &str) -> Result<bool, sqlx::Error> {
+    let result =
+        sqlx::query("UPDATE participants SET user_id = ? WHERE id = ? AND user_id IS NULL")
+            .bind(&user.id)
+            .bind(participant_id)
+            .execute(pool)
+            .await?;
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,190 @@
+// Poll Lifecycle
+// The states a poll moves through and which moves are allowed:
+//
+//   active -> closed -> finalized -> archived
+//
+// plus cancelling (from any open state) and reopening back to `active` (from closed,
+// finalized or cancelled polls). Archiving is housekeeping and allowed from anywhere.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PollStatus {
+    Active,
+    Closed,
+    Finalized,
+    Archived,
+    Cancelled,
+}
+
+impl PollStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PollStatus::Active => "active",
+            PollStatus::Closed => "closed",
+            PollStatus::Finalized => "finalized",
+            PollStatus::Archived => "archived",
+            PollStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "active" => Ok(PollStatus::Active),
+            "closed" => Ok(PollStatus::Closed),
+            "finalized" => Ok(PollStatus::Finalized),
+            "archived" => Ok(PollStatus::Archived),
+            "cancelled" => Ok(PollStatus::Cancelled),
+            other => Err(format!("Unknown poll status: {}", other)),
+        }
+    }
+
+    pub fn can_transition_to(&self, to: PollStatus) -> bool {
+        use PollStatus::*;
+        matches!(
+            (self, to),
+            (Active, Closed)
+                | (Active, Finalized)
+                | (Active, Cancelled)
+                | (Closed, Active)
+                | (Closed, Finalized)
+                | (Closed, Cancelled)
+                | (Finalized, Active)
+                | (Finalized, Cancelled)
+                | (Cancelled, Active)
+        ) || (to == Archived && *self != Archived)
+    }
+
+    /// Checks a transition, explaining why it isn't allowed
+    pub fn transition(&self, to: PollStatus) -> Result<PollStatus, String> {
+        if self.can_transition_to(to) {
+            return Ok(to);
+        }
+        Err(match (self, to) {
+            (PollStatus::Finalized, PollStatus::Finalized) => {
+                "Poll is already finalized; un-finalize or reschedule it first".to_string()
+            }
+            (from, to) if *from == to => format!("Poll is already {}", to),
+            (from, to) => format!("Cannot move a poll from {} to {}", from, to),
+        })
+    }
+}
+
+impl fmt::Display for PollStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<String> for PollStatus {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        PollStatus::parse(&value)
+    }
+}
+
+/// Voting is open while the poll is active and its `closes_at` (if any) hasn't passed.
+pub fn voting_open(status: PollStatus, closes_at: Option<i64>, now: i64) -> bool {
+    status == PollStatus::Active && closes_at.is_none_or(|closes_at| now < closes_at)
+}
+
+/// Explains why a poll no longer accepts joins or votes
+pub fn ensure_voting_open(
+    status: PollStatus,
+    closes_at: Option<i64>,
+    now: i64,
+) -> Result<(), String> {
+    if voting_open(status, closes_at, now) {
+        return Ok(());
+    }
+    Err(match status {
+        PollStatus::Finalized => "Poll is already finalized".to_string(),
+        PollStatus::Cancelled => "Poll was cancelled".to_string(),
+        PollStatus::Active | PollStatus::Closed => match closes_at {
+            Some(closes_at) if closes_at <= now => {
+                let closed = chrono::DateTime::from_timestamp(closes_at, 0)
+                    .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+                    .unwrap_or_default();
+                format!("Voting closed on {}", closed)
+            }
+            _ => "Voting is closed for this poll".to_string(),
+        },
+        PollStatus::Archived => "Poll is no longer active".to_string(),
+    })
+}
+
+pub fn validate_closes_at(closes_at: Option<i64>, now: i64) -> Result<(), String> {
+    match closes_at {
+        Some(closes_at) if closes_at <= now => {
+            Err("Voting deadline must be in the future".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_voting_open() {
+        use PollStatus::*;
+        assert!(voting_open(Active, None, 100));
+        assert!(voting_open(Active, Some(101), 100));
+        assert!(!voting_open(Active, Some(100), 100));
+        assert!(!voting_open(Closed, None, 100));
+        assert!(!voting_open(Finalized, Some(200), 100));
+
+        assert_eq!(
+            ensure_voting_open(Active, Some(0), 100).unwrap_err(),
+            "Voting closed on 1970-01-01 00:00 UTC"
+        );
+        assert_eq!(
+            ensure_voting_open(Closed, None, 100).unwrap_err(),
+            "Voting is closed for this poll"
+        );
+        assert_eq!(
+            ensure_voting_open(Archived, None, 100).unwrap_err(),
+            "Poll is no longer active"
+        );
+    }
+
+    #[test]
+    fn test_transitions() {
+        use PollStatus::*;
+        assert!(Active.can_transition_to(Closed));
+        assert!(Closed.can_transition_to(Finalized));
+        assert!(Finalized.can_transition_to(Archived));
+        assert!(Finalized.can_transition_to(Active));
+        assert!(Cancelled.can_transition_to(Active));
+        assert!(Active.can_transition_to(Archived));
+
+        assert!(!Archived.can_transition_to(Active));
+        assert!(!Cancelled.can_transition_to(Finalized));
+        assert!(!Active.can_transition_to(Active));
+
+        assert_eq!(
+            Finalized.transition(Finalized).unwrap_err(),
+            "Poll is already finalized; un-finalize or reschedule it first"
+        );
+        assert_eq!(
+            Archived.transition(Cancelled).unwrap_err(),
+            "Cannot move a poll from archived to cancelled"
+        );
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in ["active", "closed", "finalized", "archived", "cancelled"] {
+            assert_eq!(PollStatus::parse(status).unwrap().as_str(), status);
+        }
+        assert!(PollStatus::parse("reopened").is_err());
+        assert_eq!(
+            serde_json::to_value(PollStatus::Cancelled).unwrap(),
+            "cancelled"
+        );
+    }
+}
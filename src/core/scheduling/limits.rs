@@ -0,0 +1,198 @@
+// Poll Limits
+// How big a poll may get is a deployment choice: the defaults suit a weekly game, while a
+// group scheduling a multi-week convention needs more dates and a longer range. Every
+// limit is read from the environment and can be raised (or lowered) for one role only by
+// appending the role to the variable name, e.g. POLL_MAX_RANGE_DAYS_DM=60.
+
+use chrono::NaiveDate;
+
+pub const DEFAULT_MAX_DATES: usize = 365;
+pub const DEFAULT_MAX_PARTICIPANTS: usize = 100;
+pub const DEFAULT_MAX_AVAILABILITY_ENTRIES: usize = 1000;
+pub const DEFAULT_MAX_RANGE_DAYS: i64 = 14;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PollLimits {
+    pub max_dates: usize,
+    pub max_participants: usize,
+    pub max_availability_entries: usize,
+    /// Days between the first and last candidate date; `None` means no cap
+    pub max_range_days: Option<i64>,
+    pub allow_past_dates: bool,
+}
+
+impl Default for PollLimits {
+    fn default() -> Self {
+        Self {
+            max_dates: DEFAULT_MAX_DATES,
+            max_participants: DEFAULT_MAX_PARTICIPANTS,
+            max_availability_entries: DEFAULT_MAX_AVAILABILITY_ENTRIES,
+            max_range_days: Some(DEFAULT_MAX_RANGE_DAYS),
+            allow_past_dates: false,
+        }
+    }
+}
+
+impl PollLimits {
+    /// Limits for a user with `role` (`None` for anonymous organizers)
+    pub fn for_role(role: Option<&str>) -> Self {
+        Self::from_lookup(role, |name| std::env::var(name).ok())
+    }
+
+    /// Builds the limits from `lookup` (variable name -> value). A role-specific
+    /// variable wins over the deployment-wide one; unparsable values are ignored.
+    pub fn from_lookup(role: Option<&str>, lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let suffix = role
+            .map(|r| r.trim().to_uppercase())
+            .filter(|r| !r.is_empty());
+        let setting = |name: &str| -> Option<String> {
+            let role_value = suffix
+                .as_ref()
+                .and_then(|suffix| lookup(&format!("{}_{}", name, suffix)));
+            role_value
+                .or_else(|| lookup(name))
+                .map(|v| v.trim().to_string())
+        };
+        let number = |name: &str, default: usize| -> usize {
+            match setting(name).map(|v| v.parse::<usize>()) {
+                Some(Ok(value)) if value > 0 => value,
+                Some(_) => {
+                    tracing::warn!("Ignoring invalid {}, using {}", name, default);
+                    default
+                }
+                None => default,
+            }
+        };
+
+        let defaults = Self::default();
+        let max_range_days = match setting("POLL_MAX_RANGE_DAYS").map(|v| v.parse::<i64>()) {
+            // 0 lifts the cap
+            Some(Ok(0)) => None,
+            Some(Ok(days)) if days > 0 => Some(days),
+            Some(_) => {
+                tracing::warn!("Ignoring invalid POLL_MAX_RANGE_DAYS");
+                defaults.max_range_days
+            }
+            None => defaults.max_range_days,
+        };
+        let allow_past_dates = setting("POLL_ALLOW_PAST_DATES")
+            .map(|v| v.to_lowercase() == "true" || v == "1")
+            .unwrap_or(defaults.allow_past_dates);
+
+        Self {
+            max_dates: number("POLL_MAX_DATES", defaults.max_dates),
+            max_participants: number("POLL_MAX_PARTICIPANTS", defaults.max_participants),
+            max_availability_entries: number(
+                "POLL_MAX_AVAILABILITY_ENTRIES",
+                defaults.max_availability_entries,
+            ),
+            max_range_days,
+            allow_past_dates,
+        }
+    }
+
+    /// Story 1.3: Date Logic. Candidate dates must be well formed and, unless the
+    /// deployment allows otherwise, not in the past and within the range cap.
+    pub fn validate_dates(&self, dates: &[String], today: NaiveDate) -> Result<(), String> {
+        if dates.is_empty() {
+            return Err("At least one date is required".to_string());
+        }
+        if dates.len() > self.max_dates {
+            return Err(format!("Too many dates (max: {})", self.max_dates));
+        }
+
+        let mut min_date: Option<NaiveDate> = None;
+        let mut max_date: Option<NaiveDate> = None;
+
+        for date_str in dates {
+            let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+                .map_err(|_| format!("Invalid date format: {}", date_str))?;
+
+            if !self.allow_past_dates && date < today {
+                return Err(format!("Date cannot be in the past: {}", date_str));
+            }
+
+            min_date = Some(min_date.map_or(date, |min| min.min(date)));
+            max_date = Some(max_date.map_or(date, |max| max.max(date)));
+        }
+
+        if let (Some(max_days), Some(min), Some(max)) = (self.max_range_days, min_date, max_date) {
+            if max.signed_duration_since(min).num_days() > max_days {
+                return Err(format!("Date range cannot exceed {} days", max_days));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn limits(role: Option<&str>, vars: &[(&str, &str)]) -> PollLimits {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        PollLimits::from_lookup(role, |name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_limits_from_environment() {
+        assert_eq!(limits(None, &[]), PollLimits::default());
+
+        let vars = [
+            ("POLL_MAX_DATES", "60"),
+            ("POLL_MAX_RANGE_DAYS", "30"),
+            ("POLL_MAX_RANGE_DAYS_DM", "0"),
+            ("POLL_MAX_PARTICIPANTS_DM", "250"),
+            ("POLL_MAX_AVAILABILITY_ENTRIES", "lots"),
+            ("POLL_ALLOW_PAST_DATES_ADMIN", "true"),
+        ];
+        let player = limits(Some("player"), &vars);
+        assert_eq!(player.max_dates, 60);
+        assert_eq!(player.max_range_days, Some(30));
+        assert_eq!(player.max_participants, DEFAULT_MAX_PARTICIPANTS);
+        assert_eq!(
+            player.max_availability_entries,
+            DEFAULT_MAX_AVAILABILITY_ENTRIES
+        );
+        assert!(!player.allow_past_dates);
+
+        // Role overrides win and unset ones fall back to the deployment-wide value
+        let dm = limits(Some("dm"), &vars);
+        assert_eq!(dm.max_range_days, None);
+        assert_eq!(dm.max_participants, 250);
+        assert_eq!(dm.max_dates, 60);
+        assert!(limits(Some("admin"), &vars).allow_past_dates);
+    }
+
+    #[test]
+    fn test_validate_dates() {
+        let today = NaiveDate::from_ymd_opt(2030, 1, 10).unwrap();
+        let dates = |list: &[&str]| list.iter().map(|d| d.to_string()).collect::<Vec<_>>();
+        let defaults = PollLimits::default();
+
+        assert!(defaults
+            .validate_dates(&dates(&["2030-01-10", "2030-01-24"]), today)
+            .is_ok());
+        assert_eq!(
+            defaults.validate_dates(&dates(&["2030-01-10", "2030-01-25"]), today),
+            Err("Date range cannot exceed 14 days".to_string())
+        );
+        assert!(defaults
+            .validate_dates(&dates(&["2030-01-09"]), today)
+            .is_err());
+
+        let convention = PollLimits {
+            max_range_days: None,
+            allow_past_dates: true,
+            ..PollLimits::default()
+        };
+        assert!(convention
+            .validate_dates(&dates(&["2030-01-09", "2030-03-01"]), today)
+            .is_ok());
+        assert!(convention.validate_dates(&[], today).is_err());
+    }
+}
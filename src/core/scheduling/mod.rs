@@ -0,0 +1,12 @@
+pub mod attendance;
+pub mod blocks;
+pub mod claims;
+pub mod lifecycle;
+pub mod limits;
+pub mod quorum;
+pub mod recommendations;
+pub mod reconcile;
+pub mod recurrence;
+pub mod session_time;
+pub mod time_preferences;
+pub mod timezone;
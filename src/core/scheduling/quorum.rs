@@ -0,0 +1,181 @@
+// Quorum Rules
+// Decide when a poll can be finalized without the organizer: either enough players
+// (including every required participant) agree on a slot, or the voting deadline passed.
+
+use crate::core::models::{Availability, QuorumRule};
+use crate::core::scheduling::recommendations::SlotRecommendation;
+
+/// Why a poll is being finalized automatically
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuorumReason {
+    QuorumReached,
+    DeadlinePassed,
+}
+
+impl QuorumReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuorumReason::QuorumReached => "quorum_reached",
+            QuorumReason::DeadlinePassed => "deadline_passed",
+        }
+    }
+}
+
+pub fn validate_quorum_rule(
+    rule: &QuorumRule,
+    max_participants: usize,
+    now: i64,
+) -> Result<(), String> {
+    if let Some(min) = rule.min_available {
+        if min < 1 || min as usize > max_participants {
+            return Err(format!(
+                "Quorum minimum must be between 1 and {}",
+                max_participants
+            ));
+        }
+    }
+    if let Some(deadline) = rule.deadline {
+        if deadline <= now {
+            return Err("Quorum deadline must be in the future".to_string());
+        }
+    }
+    Ok(())
+}
+
+pub fn is_empty(rule: &QuorumRule) -> bool {
+    rule.min_available.is_none() && rule.deadline.is_none()
+}
+
+/// Every required participant voted "available" for the slot
+fn required_available(
+    required_ids: &[String],
+    availability: &[Availability],
+    slot: &SlotRecommendation,
+) -> bool {
+    required_ids.iter().all(|id| {
+        availability.iter().any(|a| {
+            &a.participant_id == id
+                && a.date == slot.date
+                && a.time_slot == slot.time_slot
+                && a.status == "available"
+        })
+    })
+}
+
+/// Picks the slot to finalize on, if the rule allows finalizing now.
+/// `ranked` must already be ordered best first (see `recommendations::rank_slots`).
+pub fn evaluate<'a>(
+    rule: &QuorumRule,
+    ranked: &'a [SlotRecommendation],
+    required_ids: &[String],
+    availability: &[Availability],
+    now: i64,
+) -> Option<(&'a SlotRecommendation, QuorumReason)> {
+    if let Some(min) = rule.min_available {
+        let reached = ranked.iter().find(|slot| {
+            slot.available as i64 >= min && required_available(required_ids, availability, slot)
+        });
+        if let Some(slot) = reached {
+            return Some((slot, QuorumReason::QuorumReached));
+        }
+    }
+
+    match rule.deadline {
+        // Nobody voted for anything: leave the poll to the organizer
+        Some(deadline) if now >= deadline => ranked
+            .iter()
+            .find(|slot| slot.score > 0.0)
+            .map(|slot| (slot, QuorumReason::DeadlinePassed)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot(date: &str, time_slot: &str, available: usize) -> SlotRecommendation {
+        SlotRecommendation {
+            date: date.to_string(),
+            time_slot: time_slot.to_string(),
+            available,
+            tentative: 0,
+            busy: 0,
+            no_response: 0,
+            score: available as f64,
+        }
+    }
+
+    fn vote(participant: &str, date: &str, time_slot: &str) -> Availability {
+        Availability {
+            id: None,
+            poll_id: "poll".to_string(),
+            participant_id: participant.to_string(),
+            date: date.to_string(),
+            time_slot: time_slot.to_string(),
+            status: "available".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_quorum_requires_required_participants() {
+        let ranked = vec![
+            slot("2030-01-01", "20:00", 3),
+            slot("2030-01-02", "20:00", 2),
+        ];
+        let votes = vec![vote("dm", "2030-01-02", "20:00")];
+        let rule = QuorumRule {
+            min_available: Some(2),
+            deadline: None,
+        };
+
+        // The best slot lacks the DM, so the second one reaches quorum
+        let (picked, reason) = evaluate(&rule, &ranked, &["dm".to_string()], &votes, 0).unwrap();
+        assert_eq!(picked.date, "2030-01-02");
+        assert_eq!(reason, QuorumReason::QuorumReached);
+
+        let strict = QuorumRule {
+            min_available: Some(3),
+            deadline: None,
+        };
+        assert!(evaluate(&strict, &ranked, &["dm".to_string()], &votes, 0).is_none());
+    }
+
+    #[test]
+    fn test_deadline_picks_best_slot() {
+        let ranked = vec![
+            slot("2030-01-01", "20:00", 1),
+            slot("2030-01-02", "20:00", 0),
+        ];
+        let rule = QuorumRule {
+            min_available: Some(4),
+            deadline: Some(100),
+        };
+
+        assert!(evaluate(&rule, &ranked, &[], &[], 99).is_none());
+
+        let (picked, reason) = evaluate(&rule, &ranked, &[], &[], 100).unwrap();
+        assert_eq!(picked.date, "2030-01-01");
+        assert_eq!(reason, QuorumReason::DeadlinePassed);
+
+        // No votes at all: nothing to pick
+        let empty = vec![slot("2030-01-01", "20:00", 0)];
+        assert!(evaluate(&rule, &empty, &[], &[], 100).is_none());
+    }
+
+    #[test]
+    fn test_validate_quorum_rule() {
+        let rule = QuorumRule {
+            min_available: Some(0),
+            deadline: None,
+        };
+        assert!(validate_quorum_rule(&rule, 50, 0).is_err());
+
+        let rule = QuorumRule {
+            min_available: Some(3),
+            deadline: Some(10),
+        };
+        assert!(validate_quorum_rule(&rule, 50, 20).is_err());
+        assert!(validate_quorum_rule(&rule, 50, 5).is_ok());
+    }
+}
@@ -0,0 +1,373 @@
+// Slot Recommendation Engine
+// Scores every offered (date, time_slot) of a poll against the availability rows.
+
+use crate::core::models::{Availability, Poll};
+use crate::core::scheduling::attendance;
+use crate::core::scheduling::time_preferences::TimePreferences;
+use crate::db::DbPool;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+
+pub const DEFAULT_TENTATIVE_WEIGHT: f64 = 0.5;
+
+/// Rule used to order slots that end up with the same score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreaker {
+    /// Earlier date/time wins (default, matches the frontend ordering)
+    #[default]
+    Earliest,
+    /// Later date/time wins
+    Latest,
+    /// More firm "available" votes win over tentative ones
+    MostAvailable,
+    /// Fewer "busy" votes win
+    FewestBusy,
+}
+
+impl TieBreaker {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "earliest" => Some(TieBreaker::Earliest),
+            "latest" => Some(TieBreaker::Latest),
+            "most_available" => Some(TieBreaker::MostAvailable),
+            "fewest_busy" => Some(TieBreaker::FewestBusy),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TieBreaker::Earliest => "earliest",
+            TieBreaker::Latest => "latest",
+            TieBreaker::MostAvailable => "most_available",
+            TieBreaker::FewestBusy => "fewest_busy",
+        }
+    }
+}
+
+/// Per-poll scoring configuration
+#[derive(Debug, Clone, Copy)]
+pub struct RecommendationSettings {
+    pub tentative_weight: f64,
+    pub tie_breaker: TieBreaker,
+}
+
+impl Default for RecommendationSettings {
+    fn default() -> Self {
+        Self {
+            tentative_weight: DEFAULT_TENTATIVE_WEIGHT,
+            tie_breaker: TieBreaker::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SlotRecommendation {
+    pub date: String,
+    #[serde(rename = "timeSlot")]
+    pub time_slot: String,
+    pub available: usize,
+    pub tentative: usize,
+    pub busy: usize,
+    #[serde(rename = "noResponse")]
+    pub no_response: usize,
+    pub score: f64,
+}
+
+/// Everything needed to reason about a poll's best slots
+#[derive(Debug, Clone)]
+pub struct PollRanking {
+    pub settings: RecommendationSettings,
+    pub participant_ids: Vec<String>,
+    pub required_ids: Vec<String>,
+    pub availability: Vec<Availability>,
+    pub ranked: Vec<SlotRecommendation>,
+}
+
+/// Loads participants and votes of `poll` and ranks its offered slots.
+pub async fn rank_poll(pool: &DbPool, poll: &Poll) -> Result<PollRanking, String> {
+    let participant_ids: Vec<String> =
+        sqlx::query_scalar("SELECT id FROM participants WHERE poll_id = ?")
+            .bind(&poll.id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    let availability: Vec<Availability> =
+        sqlx::query_as("SELECT * FROM availability WHERE poll_id = ?")
+            .bind(&poll.id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    let required_ids = attendance::required_participant_ids(pool, &poll.id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let dates: Vec<String> = serde_json::from_str(&poll.dates)
+        .map_err(|e| format!("Failed to parse poll dates: {}", e))?;
+
+    let settings = RecommendationSettings {
+        tentative_weight: poll.tentative_weight,
+        tie_breaker: TieBreaker::parse(&poll.tie_breaker).unwrap_or_default(),
+    };
+
+    let slots = offered_slots(&dates, &poll.time_range, &availability);
+    let ranked = rank_slots(
+        &slots,
+        &participant_ids,
+        &required_ids,
+        &availability,
+        &settings,
+    );
+
+    Ok(PollRanking {
+        settings,
+        participant_ids,
+        required_ids,
+        availability,
+        ranked,
+    })
+}
+
+/// Expands the poll's `dates` and time preferences into the list of offered slots.
+///
+/// When a date has no declared slots (no preferences, or a legacy free-text range)
+/// we fall back to the slots participants actually voted on.
+pub fn offered_slots(
+    dates: &[String],
+    time_preferences: &TimePreferences,
+    availability: &[Availability],
+) -> Vec<(String, String)> {
+    let mut sorted_dates: Vec<&String> = dates.iter().collect();
+    sorted_dates.sort();
+    sorted_dates.dedup();
+
+    let mut slots = Vec::new();
+    for date in sorted_dates {
+        let mut day_slots: BTreeSet<String> = time_preferences
+            .slots_for(date)
+            .map(|list| list.iter().cloned().collect())
+            .unwrap_or_default();
+
+        if day_slots.is_empty() {
+            day_slots = availability
+                .iter()
+                .filter(|a| &a.date == date)
+                .map(|a| a.time_slot.clone())
+                .collect();
+        }
+
+        for slot in day_slots {
+            slots.push((date.clone(), slot));
+        }
+    }
+
+    slots
+}
+
+/// Ranks the given slots, best first.
+///
+/// Only votes from `participant_ids` are counted so stale rows of removed
+/// participants don't skew the result. Slots where any of `required_ids` is
+/// busy are left out entirely.
+pub fn rank_slots(
+    slots: &[(String, String)],
+    participant_ids: &[String],
+    required_ids: &[String],
+    availability: &[Availability],
+    settings: &RecommendationSettings,
+) -> Vec<SlotRecommendation> {
+    let mut votes: HashMap<(&str, &str), HashMap<&str, &str>> = HashMap::new();
+    for entry in availability {
+        if !participant_ids.contains(&entry.participant_id) {
+            continue;
+        }
+        votes
+            .entry((entry.date.as_str(), entry.time_slot.as_str()))
+            .or_default()
+            .insert(entry.participant_id.as_str(), entry.status.as_str());
+    }
+
+    let total = participant_ids.len();
+    let mut ranked: Vec<SlotRecommendation> = slots
+        .iter()
+        .filter(|(date, time_slot)| {
+            attendance::busy_required_participants(required_ids, availability, date, time_slot)
+                .is_empty()
+        })
+        .map(|(date, time_slot)| {
+            let mut available = 0;
+            let mut tentative = 0;
+            let mut busy = 0;
+            if let Some(slot_votes) = votes.get(&(date.as_str(), time_slot.as_str())) {
+                for status in slot_votes.values() {
+                    match *status {
+                        "available" => available += 1,
+                        "tentative" => tentative += 1,
+                        "busy" => busy += 1,
+                        _ => {}
+                    }
+                }
+            }
+
+            SlotRecommendation {
+                date: date.clone(),
+                time_slot: time_slot.clone(),
+                available,
+                tentative,
+                busy,
+                no_response: total.saturating_sub(available + tentative + busy),
+                score: available as f64 + settings.tentative_weight * tentative as f64,
+            }
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| compare(a, b, settings.tie_breaker));
+    ranked
+}
+
+fn compare(a: &SlotRecommendation, b: &SlotRecommendation, tie_breaker: TieBreaker) -> Ordering {
+    let chronological =
+        (a.date.as_str(), a.time_slot.as_str()).cmp(&(b.date.as_str(), b.time_slot.as_str()));
+
+    b.score
+        .partial_cmp(&a.score)
+        .unwrap_or(Ordering::Equal)
+        .then_with(|| match tie_breaker {
+            TieBreaker::Earliest => Ordering::Equal,
+            TieBreaker::Latest => chronological.reverse(),
+            TieBreaker::MostAvailable => b.available.cmp(&a.available),
+            TieBreaker::FewestBusy => a.busy.cmp(&b.busy),
+        })
+        // Always fall back to chronological order so results are deterministic
+        .then(chronological)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(participant: &str, date: &str, slot: &str, status: &str) -> Availability {
+        Availability {
+            id: None,
+            poll_id: "poll".to_string(),
+            participant_id: participant.to_string(),
+            date: date.to_string(),
+            time_slot: slot.to_string(),
+            status: status.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_offered_slots_per_day_and_legacy() {
+        let dates = vec!["2030-01-02".to_string(), "2030-01-01".to_string()];
+
+        let per_day = r#"{"2030-01-01": ["19:00", "18:00"], "2030-01-02": ["20:00"]}"#;
+        assert_eq!(
+            offered_slots(&dates, &TimePreferences::from_stored(per_day), &[]),
+            vec![
+                ("2030-01-01".to_string(), "18:00".to_string()),
+                ("2030-01-01".to_string(), "19:00".to_string()),
+                ("2030-01-02".to_string(), "20:00".to_string()),
+            ]
+        );
+
+        let global = r#"["18:00"]"#;
+        assert_eq!(
+            offered_slots(&dates, &TimePreferences::from_stored(global), &[]).len(),
+            2
+        );
+
+        // Legacy free-text falls back to the voted slots
+        let votes = vec![vote("p1", "2030-01-01", "21:00", "available")];
+        assert_eq!(
+            offered_slots(&dates, &TimePreferences::from_stored("evening"), &votes),
+            vec![("2030-01-01".to_string(), "21:00".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_rank_slots_counts_and_weights() {
+        let participants = vec!["p1".to_string(), "p2".to_string(), "p3".to_string()];
+        let slots = vec![
+            ("2030-01-01".to_string(), "18:00".to_string()),
+            ("2030-01-01".to_string(), "19:00".to_string()),
+        ];
+        let votes = vec![
+            vote("p1", "2030-01-01", "18:00", "available"),
+            vote("p2", "2030-01-01", "18:00", "busy"),
+            vote("p1", "2030-01-01", "19:00", "tentative"),
+            vote("p2", "2030-01-01", "19:00", "tentative"),
+            vote("p3", "2030-01-01", "19:00", "tentative"),
+            // Removed participant, must be ignored
+            vote("gone", "2030-01-01", "18:00", "available"),
+        ];
+
+        let ranked = rank_slots(
+            &slots,
+            &participants,
+            &[],
+            &votes,
+            &RecommendationSettings::default(),
+        );
+        assert_eq!(ranked[0].time_slot, "19:00");
+        assert_eq!(ranked[0].tentative, 3);
+        assert_eq!(ranked[0].score, 1.5);
+        assert_eq!(ranked[1].available, 1);
+        assert_eq!(ranked[1].busy, 1);
+        assert_eq!(ranked[1].no_response, 1);
+
+        let strict = RecommendationSettings {
+            tentative_weight: 0.0,
+            tie_breaker: TieBreaker::Earliest,
+        };
+        let ranked = rank_slots(&slots, &participants, &[], &votes, &strict);
+        assert_eq!(ranked[0].time_slot, "18:00");
+
+        // p2 is required and busy at 18:00, so only 19:00 remains
+        let required = vec!["p2".to_string()];
+        let ranked = rank_slots(&slots, &participants, &required, &votes, &strict);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].time_slot, "19:00");
+    }
+
+    #[test]
+    fn test_rank_slots_tie_breakers() {
+        let participants = vec!["p1".to_string(), "p2".to_string()];
+        let slots = vec![
+            ("2030-01-01".to_string(), "18:00".to_string()),
+            ("2030-01-02".to_string(), "18:00".to_string()),
+        ];
+        let votes = vec![
+            vote("p1", "2030-01-01", "18:00", "available"),
+            vote("p2", "2030-01-01", "18:00", "busy"),
+            vote("p1", "2030-01-02", "18:00", "available"),
+        ];
+
+        let earliest = RecommendationSettings::default();
+        assert_eq!(
+            rank_slots(&slots, &participants, &[], &votes, &earliest)[0].date,
+            "2030-01-01"
+        );
+
+        let fewest_busy = RecommendationSettings {
+            tie_breaker: TieBreaker::FewestBusy,
+            ..Default::default()
+        };
+        assert_eq!(
+            rank_slots(&slots, &participants, &[], &votes, &fewest_busy)[0].date,
+            "2030-01-02"
+        );
+
+        let latest = RecommendationSettings {
+            tie_breaker: TieBreaker::Latest,
+            ..Default::default()
+        };
+        assert_eq!(
+            rank_slots(&slots, &participants, &[], &votes, &latest)[0].date,
+            "2030-01-02"
+        );
+    }
+}
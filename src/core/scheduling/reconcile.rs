@@ -0,0 +1,241 @@
+// Vote Reconciliation
+// Editing a poll's dates or slots would otherwise leave votes on slots nobody can see
+// anymore. Such votes follow an explicit move (old date to new date, old slot to new
+// slot) when the target is offered, otherwise they are dropped; the players who lost or
+// moved votes are reported back so the organizer can warn them.
+
+use crate::core::models::Availability;
+use crate::core::scheduling::time_preferences::TimePreferences;
+use serde::Serialize;
+use sqlx::SqliteConnection;
+use std::collections::{HashMap, HashSet};
+
+/// Where votes on removed dates/slots should go
+#[derive(Debug, Default, Clone)]
+pub struct VoteMoves {
+    pub dates: HashMap<String, String>, // old date -> new date
+    pub slots: HashMap<String, String>, // old "HH:MM" -> new "HH:MM"
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VoteFate {
+    Kept,
+    Moved { date: String, time_slot: String },
+    Dropped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AffectedParticipant {
+    #[serde(rename = "participantId")]
+    pub participant_id: String,
+    pub name: String,
+    #[serde(skip)]
+    pub email: Option<String>,
+    pub moved: usize,
+    pub dropped: usize,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ReconcileReport {
+    pub kept: usize,
+    pub moved: usize,
+    pub dropped: usize,
+    #[serde(rename = "affectedParticipants")]
+    pub affected: Vec<AffectedParticipant>,
+    /// Every moved or dropped vote as it was before the edit, with where it went
+    #[serde(skip)]
+    pub changes: Vec<(Availability, VoteFate)>,
+}
+
+fn is_offered(dates: &[String], prefs: &TimePreferences, date: &str, slot: &str) -> bool {
+    dates.iter().any(|d| d == date) && prefs.validate_slot(date, slot).is_ok()
+}
+
+/// What happens to one vote under the new dates and slots
+pub fn reconcile_vote(
+    vote: &Availability,
+    dates: &[String],
+    prefs: &TimePreferences,
+    moves: &VoteMoves,
+) -> VoteFate {
+    if is_offered(dates, prefs, &vote.date, &vote.time_slot) {
+        return VoteFate::Kept;
+    }
+
+    let date = moves.dates.get(&vote.date).unwrap_or(&vote.date);
+    let time_slot = moves.slots.get(&vote.time_slot).unwrap_or(&vote.time_slot);
+    let relocated = *date != vote.date || *time_slot != vote.time_slot;
+    if relocated && is_offered(dates, prefs, date, time_slot) {
+        VoteFate::Moved {
+            date: date.clone(),
+            time_slot: time_slot.clone(),
+        }
+    } else {
+        VoteFate::Dropped
+    }
+}
+
+/// Applies `reconcile_vote` to every vote of the poll. Run it in the transaction that
+/// changes the poll's dates/slots. A move onto a slot the player already voted on keeps
+/// the existing vote and drops the moved one. The report lists what was moved or
+/// dropped, for the vote history.
+pub async fn reconcile_availability(
+    conn: &mut SqliteConnection,
+    poll_id: &str,
+    dates: &[String],
+    prefs: &TimePreferences,
+    moves: &VoteMoves,
+) -> Result<ReconcileReport, sqlx::Error> {
+    let votes: Vec<Availability> =
+        sqlx::query_as("SELECT * FROM availability WHERE poll_id = ? ORDER BY id")
+            .bind(poll_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+    let fates: Vec<VoteFate> = votes
+        .iter()
+        .map(|vote| reconcile_vote(vote, dates, prefs, moves))
+        .collect();
+
+    // Slots each player already holds a vote on after the edit
+    let mut taken: HashSet<(String, String, String)> = votes
+        .iter()
+        .zip(&fates)
+        .filter(|(_, fate)| **fate == VoteFate::Kept)
+        .map(|(vote, _)| {
+            (
+                vote.participant_id.clone(),
+                vote.date.clone(),
+                vote.time_slot.clone(),
+            )
+        })
+        .collect();
+
+    let mut report = ReconcileReport::default();
+    let mut per_participant: HashMap<String, (usize, usize)> = HashMap::new();
+    for (vote, fate) in votes.iter().zip(fates) {
+        let fate = match fate {
+            VoteFate::Moved { date, time_slot } => {
+                let key = (vote.participant_id.clone(), date, time_slot);
+                if taken.insert(key.clone()) {
+                    VoteFate::Moved {
+                        date: key.1,
+                        time_slot: key.2,
+                    }
+                } else {
+                    VoteFate::Dropped
+                }
+            }
+            fate => fate,
+        };
+
+        match &fate {
+            VoteFate::Kept => report.kept += 1,
+            VoteFate::Moved { date, time_slot } => {
+                sqlx::query("UPDATE availability SET date = ?, time_slot = ? WHERE id = ?")
+                    .bind(date)
+                    .bind(time_slot)
+                    .bind(vote.id)
+                    .execute(&mut *conn)
+                    .await?;
+                report.moved += 1;
+                per_participant
+                    .entry(vote.participant_id.clone())
+                    .or_default()
+                    .0 += 1;
+            }
+            VoteFate::Dropped => {
+                sqlx::query("DELETE FROM availability WHERE id = ?")
+                    .bind(vote.id)
+                    .execute(&mut *conn)
+                    .await?;
+                report.dropped += 1;
+                per_participant
+                    .entry(vote.participant_id.clone())
+                    .or_default()
+                    .1 += 1;
+            }
+        }
+        if fate != VoteFate::Kept {
+            report.changes.push((vote.clone(), fate));
+        }
+    }
+
+    if per_participant.is_empty() {
+        return Ok(report);
+    }
+
+    let participants: Vec<(String, String, Option<String>)> =
+        sqlx::query_as("SELECT id, name, email FROM participants WHERE poll_id = ? ORDER BY name")
+            .bind(poll_id)
+            .fetch_all(&mut *conn)
+            .await?;
+    for (participant_id, name, email) in participants {
+        if let Some((moved, dropped)) = per_participant.get(&participant_id) {
+            report.affected.push(AffectedParticipant {
+                participant_id,
+                name,
+                email,
+                moved: *moved,
+                dropped: *dropped,
+            });
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(date: &str, slot: &str) -> Availability {
+        Availability {
+            id: None,
+            poll_id: "poll".to_string(),
+            participant_id: "p1".to_string(),
+            date: date.to_string(),
+            time_slot: slot.to_string(),
+            status: "available".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_reconcile_vote() {
+        let dates = vec!["2030-01-11".to_string(), "2030-01-12".to_string()];
+        let prefs = TimePreferences::Daily(vec!["20:00".to_string(), "21:00".to_string()]);
+        let mut moves = VoteMoves::default();
+        moves
+            .dates
+            .insert("2030-01-10".to_string(), "2030-01-11".to_string());
+        moves.slots.insert("19:00".to_string(), "20:00".to_string());
+
+        assert_eq!(
+            reconcile_vote(&vote("2030-01-12", "21:00"), &dates, &prefs, &moves),
+            VoteFate::Kept
+        );
+        // Date and slot moves combine
+        assert_eq!(
+            reconcile_vote(&vote("2030-01-10", "19:00"), &dates, &prefs, &moves),
+            VoteFate::Moved {
+                date: "2030-01-11".to_string(),
+                time_slot: "20:00".to_string()
+            }
+        );
+        assert_eq!(
+            reconcile_vote(&vote("2030-01-12", "19:00"), &dates, &prefs, &moves),
+            VoteFate::Moved {
+                date: "2030-01-12".to_string(),
+                time_slot: "20:00".to_string()
+            }
+        );
+        // No move, or a move onto something not offered, drops the vote
+        assert_eq!(
+            reconcile_vote(&vote("2030-01-13", "20:00"), &dates, &prefs, &moves),
+            VoteFate::Dropped
+        );
+        assert_eq!(
+            reconcile_vote(&vote("2030-01-10", "22:00"), &dates, &prefs, &moves),
+            VoteFate::Dropped
+        );
+    }
+}
@@ -0,0 +1,214 @@
+// Session Series Recurrence
+// Expands a series ("every N weeks from the first date") into concrete occurrences
+// and applies the per-occurrence exceptions (skip, move).
+
+use crate::core::models::SeriesException;
+use crate::core::scheduling::time_preferences::normalize_slot;
+use chrono::{Duration, NaiveDate, NaiveTime};
+
+pub const MAX_INTERVAL_WEEKS: i64 = 8;
+pub const MAX_LEAD_WEEKS: i64 = 8;
+pub const DEFAULT_LEAD_WEEKS: i64 = 2;
+
+pub const MODE_POLL: &str = "poll";
+pub const MODE_SESSION: &str = "session";
+
+pub const EXCEPTION_SKIP: &str = "skip";
+pub const EXCEPTION_MOVE: &str = "move";
+
+/// An occurrence once exceptions are applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occurrence {
+    pub occurrence_date: NaiveDate, // The date the series would normally fall on
+    pub date: NaiveDate,
+    pub start_time: NaiveTime,
+    pub duration: Duration,
+}
+
+pub fn parse_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date format: {}", date))
+}
+
+pub fn parse_time(time: &str) -> Result<NaiveTime, String> {
+    let normalized = normalize_slot(time)?;
+    NaiveTime::parse_from_str(&normalized, "%H:%M").map_err(|e| e.to_string())
+}
+
+/// Length of a session running from `start` to `end` on the same day
+pub fn session_duration(start: NaiveTime, end: NaiveTime) -> Result<Duration, String> {
+    if end <= start {
+        return Err("End time must be after start time".to_string());
+    }
+    Ok(end - start)
+}
+
+fn step(interval_weeks: i64) -> i64 {
+    7 * interval_weeks.max(1)
+}
+
+/// Whether `date` is one of the series' regular occurrences
+pub fn is_occurrence(first_date: NaiveDate, interval_weeks: i64, date: NaiveDate) -> bool {
+    let days = (date - first_date).num_days();
+    days >= 0 && days % step(interval_weeks) == 0
+}
+
+/// Regular occurrence dates within `from..=to`
+pub fn occurrences_between(
+    first_date: NaiveDate,
+    interval_weeks: i64,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<NaiveDate> {
+    let step = step(interval_weeks);
+    let offset = (from - first_date).num_days().max(0);
+    // First occurrence on or after `from`
+    let mut date = first_date + Duration::days((offset + step - 1) / step * step);
+
+    let mut dates = Vec::new();
+    while date <= to {
+        dates.push(date);
+        date += Duration::days(step);
+    }
+    dates
+}
+
+/// Applies the exception recorded for an occurrence, if any. `None` means skipped.
+pub fn resolve(
+    occurrence_date: NaiveDate,
+    start_time: NaiveTime,
+    duration: Duration,
+    exception: Option<&SeriesException>,
+) -> Option<Occurrence> {
+    let mut occurrence = Occurrence {
+        occurrence_date,
+        date: occurrence_date,
+        start_time,
+        duration,
+    };
+
+    match exception {
+        Some(e) if e.action == EXCEPTION_SKIP => return None,
+        Some(e) if e.action == EXCEPTION_MOVE => {
+            if let Some(date) = e.moved_date.as_deref().and_then(|d| parse_date(d).ok()) {
+                occurrence.date = date;
+            }
+            if let Some(time) = e
+                .moved_start_time
+                .as_deref()
+                .and_then(|t| parse_time(t).ok())
+            {
+                occurrence.start_time = time;
+            }
+        }
+        _ => {}
+    }
+    Some(occurrence)
+}
+
+/// Hourly "HH:MM" slots covering the session, stopping at midnight
+pub fn session_slots(start_time: NaiveTime, duration: Duration) -> Vec<String> {
+    let mut slots = Vec::new();
+    let mut offset = Duration::zero();
+    while offset < duration {
+        let (slot, wrapped) = start_time.overflowing_add_signed(offset);
+        if wrapped != 0 {
+            break;
+        }
+        slots.push(slot.format("%H:%M").to_string());
+        offset += Duration::hours(1);
+    }
+    slots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        parse_date(s).unwrap()
+    }
+
+    fn exception(
+        action: &str,
+        moved_date: Option<&str>,
+        moved_time: Option<&str>,
+    ) -> SeriesException {
+        SeriesException {
+            series_id: "series".to_string(),
+            occurrence_date: "2030-01-18".to_string(),
+            action: action.to_string(),
+            moved_date: moved_date.map(|d| d.to_string()),
+            moved_start_time: moved_time.map(|t| t.to_string()),
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_every_other_friday() {
+        // 2030-01-04 is a Friday
+        let first = date("2030-01-04");
+        let dates = occurrences_between(first, 2, date("2030-01-10"), date("2030-02-15"));
+        assert_eq!(
+            dates,
+            vec![date("2030-01-18"), date("2030-02-01"), date("2030-02-15")]
+        );
+
+        // Nothing before the first occurrence
+        let early = occurrences_between(first, 2, date("2029-12-01"), date("2030-01-10"));
+        assert_eq!(early, vec![first]);
+
+        assert!(is_occurrence(first, 2, date("2030-02-01")));
+        assert!(!is_occurrence(first, 2, date("2030-01-11")));
+        assert!(!is_occurrence(first, 2, date("2029-12-21")));
+    }
+
+    #[test]
+    fn test_resolve_exceptions() {
+        let start = parse_time("20:00").unwrap();
+        let duration = Duration::hours(3);
+        let regular = date("2030-01-18");
+
+        assert!(resolve(
+            regular,
+            start,
+            duration,
+            Some(&exception("skip", None, None))
+        )
+        .is_none());
+
+        let moved = resolve(
+            regular,
+            start,
+            duration,
+            Some(&exception("move", Some("2030-01-19"), Some("18:30"))),
+        )
+        .unwrap();
+        assert_eq!(moved.occurrence_date, regular);
+        assert_eq!(moved.date, date("2030-01-19"));
+        assert_eq!(moved.start_time, parse_time("18:30").unwrap());
+        assert_eq!(moved.duration, duration);
+
+        assert_eq!(
+            resolve(regular, start, duration, None).unwrap().date,
+            regular
+        );
+    }
+
+    #[test]
+    fn test_session_slots() {
+        let start = parse_time("20:00").unwrap();
+        let end = parse_time("23:00").unwrap();
+        let duration = session_duration(start, end).unwrap();
+        assert_eq!(
+            session_slots(start, duration),
+            vec!["20:00", "21:00", "22:00"]
+        );
+
+        // A session moved late in the evening stops at midnight
+        let late = parse_time("22:30").unwrap();
+        assert_eq!(session_slots(late, duration), vec!["22:30", "23:30"]);
+
+        assert!(session_duration(end, start).is_err());
+    }
+}
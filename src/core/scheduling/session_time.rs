@@ -0,0 +1,207 @@
+// Finalized Session Time
+// A finalized session is a start and an end instant. Organizers pick the start among the
+// poll's offered slots (in the poll's zone or their own) and give either an end or a
+// duration; the slot key "date_HH:MM" is still kept in `finalized_time` for older readers.
+
+use crate::core::scheduling::time_preferences::TimePreferences;
+use crate::core::scheduling::timezone;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use serde_json::{json, Value};
+
+/// One hourly slot, used when neither an end nor a duration is given
+pub const DEFAULT_SESSION_MINUTES: i64 = 60;
+pub const MAX_SESSION_MINUTES: i64 = 24 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionTime {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// Parses a datetime either with an explicit offset ("2030-01-10T20:00:00+01:00") or as
+/// wall-clock time in `tz` ("2030-01-10T20:00", "2030-01-10 20:00", "2030-01-10_20:00").
+pub fn parse_datetime(value: &str, tz: Tz) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(instant) = DateTime::parse_from_rfc3339(value) {
+        return Ok(instant.with_timezone(&Utc));
+    }
+
+    let normalized = value.replacen(['_', ' '], "T", 1);
+    let naive = NaiveDateTime::parse_from_str(&normalized, "%Y-%m-%dT%H:%M")
+        .or_else(|_| NaiveDateTime::parse_from_str(&normalized, "%Y-%m-%dT%H:%M:%S"))
+        .map_err(|_| format!("Invalid date and time: {}", value))?;
+    Ok(timezone::resolve_local(tz, naive).with_timezone(&Utc))
+}
+
+impl SessionTime {
+    /// Builds the session from a start plus either an end or a duration in minutes
+    pub fn new(
+        start: &str,
+        end: Option<&str>,
+        duration_minutes: Option<i64>,
+        tz: Tz,
+    ) -> Result<Self, String> {
+        let start = parse_datetime(start, tz)?;
+        let end = match (end, duration_minutes) {
+            (Some(end), _) => parse_datetime(end, tz)?,
+            (None, Some(minutes)) => start + Duration::minutes(minutes),
+            (None, None) => start + Duration::minutes(DEFAULT_SESSION_MINUTES),
+        };
+
+        if end <= start {
+            return Err("Session end must be after its start".to_string());
+        }
+        if end - start > Duration::minutes(MAX_SESSION_MINUTES) {
+            return Err(format!(
+                "Session cannot last more than {} hours",
+                MAX_SESSION_MINUTES / 60
+            ));
+        }
+        Ok(SessionTime { start, end })
+    }
+
+    /// A session starting on a poll slot, e.g. one picked by a background job
+    pub fn from_slot(date: &str, time_slot: &str, tz: Tz, duration: Duration) -> Option<Self> {
+        let start = timezone::slot_instant(date, time_slot, tz)?;
+        Some(SessionTime {
+            start,
+            end: start + duration,
+        })
+    }
+
+    pub fn duration_minutes(&self) -> i64 {
+        (self.end - self.start).num_minutes()
+    }
+
+    /// The start as a poll slot: (date, "HH:MM") in the poll's zone
+    pub fn start_slot(&self, poll_tz: Tz) -> (String, String) {
+        let local = self.start.with_timezone(&poll_tz);
+        (
+            local.format("%Y-%m-%d").to_string(),
+            local.format("%H:%M").to_string(),
+        )
+    }
+
+    /// Key stored in `polls.finalized_time`, like the frontend cells ("date_HH:MM")
+    pub fn slot_key(&self, poll_tz: Tz) -> String {
+        let (date, time_slot) = self.start_slot(poll_tz);
+        format!("{}_{}", date, time_slot)
+    }
+
+    /// Checks that the session starts on one of the poll's dates and offered slots
+    pub fn validate_offered(
+        &self,
+        dates: &[String],
+        prefs: &TimePreferences,
+        poll_tz: Tz,
+    ) -> Result<(), String> {
+        let (date, time_slot) = self.start_slot(poll_tz);
+        if !dates.contains(&date) {
+            return Err(format!("Date {} is not offered by this poll", date));
+        }
+        prefs.validate_slot(&date, &time_slot)
+    }
+
+    /// Offered slots on the start date that fall within the session
+    pub fn covered_slots(&self, prefs: &TimePreferences, poll_tz: Tz) -> Vec<(String, String)> {
+        let (date, start_slot) = self.start_slot(poll_tz);
+        let Some(offered) = prefs.slots_for(&date) else {
+            return vec![(date, start_slot)];
+        };
+
+        let mut slots: Vec<(String, String)> = offered
+            .iter()
+            .filter(|slot| {
+                timezone::slot_instant(&date, slot, poll_tz)
+                    .is_some_and(|at| self.start <= at && at < self.end)
+            })
+            .map(|slot| (date.clone(), slot.clone()))
+            .collect();
+        if !slots.iter().any(|(_, slot)| *slot == start_slot) {
+            slots.insert(0, (date, start_slot));
+        }
+        slots
+    }
+
+    /// Start and end as seen from `tz`
+    pub fn to_value(&self, tz: Tz) -> Value {
+        json!({
+            "start": self.start.with_timezone(&tz).to_rfc3339(),
+            "end": self.end.with_timezone(&tz).to_rfc3339(),
+            "durationMinutes": self.duration_minutes(),
+            "timezone": tz.name()
+        })
+    }
+}
+
+/// Reads the `finalized_start`/`finalized_end` columns back
+pub fn from_columns(start: Option<i64>, end: Option<i64>) -> Option<SessionTime> {
+    Some(SessionTime {
+        start: DateTime::from_timestamp(start?, 0)?,
+        end: DateTime::from_timestamp(end?, 0)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rome() -> Tz {
+        timezone::parse_timezone("Europe/Rome").unwrap()
+    }
+
+    #[test]
+    fn test_session_from_duration_and_end() {
+        let session = SessionTime::new("2030-01-10_20:00", None, Some(240), rome()).unwrap();
+        assert_eq!(session.start.to_rfc3339(), "2030-01-10T19:00:00+00:00");
+        assert_eq!(session.duration_minutes(), 240);
+        assert_eq!(session.slot_key(rome()), "2030-01-10_20:00");
+
+        // Explicit offsets win over the zone; the end defaults to one slot
+        let session = SessionTime::new("2030-01-10T19:00:00Z", None, None, rome()).unwrap();
+        assert_eq!(session.slot_key(rome()), "2030-01-10_20:00");
+        assert_eq!(session.duration_minutes(), DEFAULT_SESSION_MINUTES);
+
+        let session =
+            SessionTime::new("2030-01-10 20:00", Some("2030-01-11 01:30"), None, rome()).unwrap();
+        assert_eq!(session.duration_minutes(), 330);
+
+        assert!(
+            SessionTime::new("2030-01-10 20:00", Some("2030-01-10 19:00"), None, rome()).is_err()
+        );
+        assert!(SessionTime::new("2030-01-10 20:00", None, Some(25 * 60), rome()).is_err());
+        assert!(SessionTime::new("tomorrow evening", None, None, rome()).is_err());
+    }
+
+    #[test]
+    fn test_validate_offered_and_covered_slots() {
+        let dates = vec!["2030-01-10".to_string()];
+        let prefs = TimePreferences::Daily(vec![
+            "19:00".to_string(),
+            "20:00".to_string(),
+            "21:00".to_string(),
+            "22:00".to_string(),
+        ]);
+
+        let session = SessionTime::new("2030-01-10 20:00", None, Some(120), rome()).unwrap();
+        assert!(session.validate_offered(&dates, &prefs, rome()).is_ok());
+        assert_eq!(
+            session.covered_slots(&prefs, rome()),
+            vec![
+                ("2030-01-10".to_string(), "20:00".to_string()),
+                ("2030-01-10".to_string(), "21:00".to_string())
+            ]
+        );
+
+        let off_slot = SessionTime::new("2030-01-10 20:30", None, None, rome()).unwrap();
+        assert!(off_slot.validate_offered(&dates, &prefs, rome()).is_err());
+        let off_date = SessionTime::new("2030-01-11 20:00", None, None, rome()).unwrap();
+        assert_eq!(
+            off_date
+                .validate_offered(&dates, &prefs, rome())
+                .unwrap_err(),
+            "Date 2030-01-11 is not offered by this poll"
+        );
+    }
+}
@@ -0,0 +1,313 @@
+// Poll Time Preferences
+// Typed view of `polls.time_range`: which time slots a poll offers on each of its dates.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub const MAX_SLOTS_PER_DAY: usize = 48;
+
+/// The time slots offered by a poll.
+///
+/// Stored in `polls.time_range` as JSON and serialized the same way in API responses:
+/// `{}` (no slots declared), `["18:00", "19:00"]` (same slots on every date) or
+/// `{"2025-12-10": ["18:00"]}` (per-day slots). Free text saved by old clients is kept
+/// as `Legacy` so existing polls still load, but new free text is rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TimePreferences {
+    /// No slots declared: any well-formed slot may be voted on
+    #[default]
+    Any,
+    /// The same slots on every poll date
+    Daily(Vec<String>),
+    /// Slots per date; dates missing from the map offer nothing
+    PerDay(BTreeMap<String, Vec<String>>),
+    /// Free-text time range from before slots were validated
+    Legacy(String),
+}
+
+/// Normalizes a slot to "HH:MM" (accepts e.g. "9:00" or "09:00:00").
+pub fn normalize_slot(slot: &str) -> Result<String, String> {
+    let slot = slot.trim();
+    chrono::NaiveTime::parse_from_str(slot, "%H:%M")
+        .or_else(|_| chrono::NaiveTime::parse_from_str(slot, "%H:%M:%S"))
+        .map(|t| t.format("%H:%M").to_string())
+        .map_err(|_| format!("Invalid time slot: {}", slot))
+}
+
+fn normalize_slots(values: &[Value]) -> Result<Vec<String>, String> {
+    let mut slots = BTreeSet::new();
+    for value in values {
+        let slot = value
+            .as_str()
+            .ok_or_else(|| "Time slots must be strings like \"18:00\"".to_string())?;
+        slots.insert(normalize_slot(slot)?);
+    }
+    if slots.len() > MAX_SLOTS_PER_DAY {
+        return Err(format!(
+            "Too many time slots per day (max: {})",
+            MAX_SLOTS_PER_DAY
+        ));
+    }
+    Ok(slots.into_iter().collect())
+}
+
+impl TimePreferences {
+    /// Parses and validates the time preferences of a create/update request.
+    ///
+    /// `time_preferences` (per-day object, or a list applied to every date) wins over
+    /// the legacy `timeRange` string, which must hold a JSON list or object.
+    pub fn from_request(
+        time_preferences: Option<&Value>,
+        time_range: Option<&str>,
+        dates: &[String],
+    ) -> Result<Self, String> {
+        let value = match (time_preferences, time_range) {
+            (Some(value), _) => value.clone(),
+            (None, Some(raw)) if !raw.trim().is_empty() => serde_json::from_str(raw)
+                .map_err(|_| "Time range must be a JSON list of time slots".to_string())?,
+            _ => return Ok(TimePreferences::Any),
+        };
+
+        let prefs = match value {
+            Value::Null => TimePreferences::Any,
+            Value::Array(list) => TimePreferences::Daily(normalize_slots(&list)?),
+            Value::Object(per_day) => {
+                let mut map = BTreeMap::new();
+                for (date, slots) in per_day {
+                    if !dates.contains(&date) {
+                        return Err(format!(
+                            "Time preferences reference a date that is not in the poll: {}",
+                            date
+                        ));
+                    }
+                    let list = slots
+                        .as_array()
+                        .ok_or_else(|| format!("Time slots for {} must be a list", date))?;
+                    let slots = normalize_slots(list)?;
+                    if !slots.is_empty() {
+                        map.insert(date, slots);
+                    }
+                }
+                TimePreferences::PerDay(map)
+            }
+            _ => return Err("Time preferences must be a list or a per-day object".to_string()),
+        };
+
+        Ok(prefs.normalized())
+    }
+
+    /// Reads a stored `time_range` column. Never fails: unknown content becomes `Legacy`.
+    pub fn from_stored(raw: &str) -> Self {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return TimePreferences::Any;
+        }
+
+        let prefs = match serde_json::from_str::<Value>(trimmed) {
+            Ok(Value::Array(list)) => TimePreferences::Daily(
+                list.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect(),
+            ),
+            Ok(Value::Object(per_day)) => TimePreferences::PerDay(
+                per_day
+                    .into_iter()
+                    .map(|(date, slots)| {
+                        let slots = slots
+                            .as_array()
+                            .map(|list| {
+                                list.iter()
+                                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                        (date, slots)
+                    })
+                    .filter(|(_, slots): &(String, Vec<String>)| !slots.is_empty())
+                    .collect(),
+            ),
+            Ok(Value::String(text)) => TimePreferences::Legacy(text),
+            Ok(Value::Null) => TimePreferences::Any,
+            _ => TimePreferences::Legacy(raw.to_string()),
+        };
+
+        prefs.normalized()
+    }
+
+    /// Collapses empty declarations to `Any`
+    fn normalized(self) -> Self {
+        match self {
+            TimePreferences::Daily(slots) if slots.is_empty() => TimePreferences::Any,
+            TimePreferences::PerDay(map) if map.is_empty() => TimePreferences::Any,
+            TimePreferences::Legacy(text) if text.trim().is_empty() => TimePreferences::Any,
+            other => other,
+        }
+    }
+
+    /// Slots offered on `date`, or `None` when the poll doesn't restrict slots.
+    pub fn slots_for(&self, date: &str) -> Option<&[String]> {
+        match self {
+            TimePreferences::Any | TimePreferences::Legacy(_) => None,
+            TimePreferences::Daily(slots) => Some(slots),
+            TimePreferences::PerDay(map) => {
+                Some(map.get(date).map(|s| s.as_slice()).unwrap_or(&[]))
+            }
+        }
+    }
+
+    /// Checks a vote's slot against the preferences for `date`.
+    pub fn validate_slot(&self, date: &str, slot: &str) -> Result<(), String> {
+        if normalize_slot(slot)? != slot {
+            return Err(format!("Invalid time slot: {}", slot));
+        }
+        match self.slots_for(date) {
+            Some(offered) if !offered.iter().any(|s| s == slot) => {
+                Err(format!("Time slot {} is not offered on {}", slot, date))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        match self {
+            TimePreferences::Any => Value::Object(Default::default()),
+            TimePreferences::Daily(slots) => serde_json::json!(slots),
+            TimePreferences::PerDay(map) => serde_json::json!(map),
+            TimePreferences::Legacy(text) => Value::String(text.clone()),
+        }
+    }
+
+    /// Canonical JSON written to `polls.time_range`
+    pub fn to_storage(&self) -> String {
+        self.to_value().to_string()
+    }
+
+    /// The same preferences with per-day keys moved `days` later, for copied polls
+    pub fn shifted(&self, days: i64) -> Result<Self, String> {
+        match self {
+            TimePreferences::PerDay(map) => map
+                .iter()
+                .map(|(date, slots)| Ok((shift_date(date, days)?, slots.clone())))
+                .collect::<Result<_, String>>()
+                .map(TimePreferences::PerDay),
+            other => Ok(other.clone()),
+        }
+    }
+}
+
+/// Moves a "YYYY-MM-DD" date by `days`
+pub fn shift_date(date: &str, days: i64) -> Result<String, String> {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.checked_add_signed(chrono::Duration::days(days)))
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .ok_or_else(|| format!("Invalid date: {}", date))
+}
+
+impl From<String> for TimePreferences {
+    fn from(raw: String) -> Self {
+        TimePreferences::from_stored(&raw)
+    }
+}
+
+impl Serialize for TimePreferences {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_value().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TimePreferences {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        Ok(match value {
+            Value::String(raw) => TimePreferences::from_stored(&raw),
+            other => TimePreferences::from_stored(&other.to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn dates() -> Vec<String> {
+        vec!["2030-01-01".to_string(), "2030-01-02".to_string()]
+    }
+
+    #[test]
+    fn test_from_request_normalizes() {
+        let prefs = TimePreferences::from_request(
+            Some(&json!({"2030-01-01": ["19:00", "9:00", "19:00"], "2030-01-02": []})),
+            None,
+            &dates(),
+        )
+        .unwrap();
+        assert_eq!(prefs.to_storage(), r#"{"2030-01-01":["09:00","19:00"]}"#);
+
+        let legacy = TimePreferences::from_request(None, Some(r#"["20:00"]"#), &dates()).unwrap();
+        assert_eq!(legacy, TimePreferences::Daily(vec!["20:00".to_string()]));
+
+        assert_eq!(
+            TimePreferences::from_request(None, Some("[]"), &dates()).unwrap(),
+            TimePreferences::Any
+        );
+    }
+
+    #[test]
+    fn test_from_request_rejects_junk() {
+        assert!(TimePreferences::from_request(None, Some("evening"), &dates()).is_err());
+        assert!(TimePreferences::from_request(
+            Some(&json!({"2030-01-01": ["25:00"]})),
+            None,
+            &dates()
+        )
+        .is_err());
+        assert!(TimePreferences::from_request(
+            Some(&json!({"2031-01-01": ["18:00"]})),
+            None,
+            &dates()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_validate_slot() {
+        let prefs = TimePreferences::from_stored(r#"{"2030-01-01": ["18:00"]}"#);
+        assert!(prefs.validate_slot("2030-01-01", "18:00").is_ok());
+        assert!(prefs.validate_slot("2030-01-01", "19:00").is_err());
+        assert!(prefs.validate_slot("2030-01-02", "18:00").is_err());
+
+        let any = TimePreferences::from_stored("{}");
+        assert!(any.validate_slot("2030-01-02", "21:00").is_ok());
+        assert!(any.validate_slot("2030-01-02", "<script>").is_err());
+
+        // Old free-text polls keep loading and don't restrict slots
+        let legacy = TimePreferences::from_stored("Friday evening");
+        assert_eq!(
+            legacy,
+            TimePreferences::Legacy("Friday evening".to_string())
+        );
+        assert!(legacy.validate_slot("2030-01-01", "20:00").is_ok());
+    }
+
+    #[test]
+    fn test_shifted() {
+        let prefs = TimePreferences::from_request(
+            Some(&json!({"2030-01-01": ["19:00"], "2030-01-02": ["20:00"]})),
+            None,
+            &dates(),
+        )
+        .unwrap();
+        assert_eq!(
+            prefs.shifted(31).unwrap().to_storage(),
+            r#"{"2030-02-01":["19:00"],"2030-02-02":["20:00"]}"#
+        );
+
+        let daily = TimePreferences::Daily(vec!["20:00".to_string()]);
+        assert_eq!(daily.shifted(7).unwrap(), daily);
+        assert_eq!(shift_date("2030-12-30", 7).unwrap(), "2031-01-06");
+        assert!(shift_date("next week", 7).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+// Poll Timezones
+// Slots are stored as a bare date + "HH:MM" in the poll's IANA zone. These helpers pin
+// them to an instant and re-express them in the zone of whoever is looking.
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::Serialize;
+
+pub const DEFAULT_TIMEZONE: &str = "UTC";
+
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    name.trim()
+        .parse::<Tz>()
+        .map_err(|_| format!("Unknown timezone: {}", name))
+}
+
+/// Picks the zone to present times in: an explicit request wins, then the viewer's
+/// profile, then the poll's own zone. Only an invalid explicit request is an error.
+pub fn viewer_timezone(
+    requested: Option<&str>,
+    user_timezone: Option<&str>,
+    poll_timezone: &str,
+) -> Result<Tz, String> {
+    if let Some(requested) = requested.filter(|tz| !tz.trim().is_empty()) {
+        return parse_timezone(requested);
+    }
+    Ok(user_timezone
+        .and_then(|tz| parse_timezone(tz).ok())
+        .or_else(|| parse_timezone(poll_timezone).ok())
+        .unwrap_or(Tz::UTC))
+}
+
+/// Resolves a wall-clock time in `tz`. When clocks go back the earlier instant wins;
+/// a time skipped when clocks go forward is moved past the gap.
+pub fn resolve_local(tz: Tz, naive: NaiveDateTime) -> DateTime<Tz> {
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(naive + Duration::hours(1)))
+                .earliest()
+        })
+        .unwrap_or_else(|| tz.from_utc_datetime(&naive))
+}
+
+/// The instant a poll slot starts at
+pub fn slot_instant(date: &str, time_slot: &str, tz: Tz) -> Option<DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    let time = NaiveTime::parse_from_str(time_slot, "%H:%M").ok()?;
+    Some(resolve_local(tz, date.and_time(time)).with_timezone(&Utc))
+}
+
+/// A poll slot as seen from another zone
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LocalizedSlot {
+    pub utc: String,
+    pub date: String,
+    #[serde(rename = "timeSlot")]
+    pub time_slot: String,
+    #[serde(rename = "utcOffset")]
+    pub utc_offset: String,
+    pub timezone: String,
+}
+
+pub fn localize_slot(
+    date: &str,
+    time_slot: &str,
+    poll_tz: Tz,
+    viewer_tz: Tz,
+) -> Option<LocalizedSlot> {
+    let instant = slot_instant(date, time_slot, poll_tz)?;
+    let local = instant.with_timezone(&viewer_tz);
+    let offset = local.offset().fix().local_minus_utc();
+
+    Some(LocalizedSlot {
+        utc: instant.to_rfc3339(),
+        date: local.format("%Y-%m-%d").to_string(),
+        time_slot: local.format("%H:%M").to_string(),
+        utc_offset: format!(
+            "{}{:02}:{:02}",
+            if offset < 0 { '-' } else { '+' },
+            offset.abs() / 3600,
+            (offset.abs() % 3600) / 60
+        ),
+        timezone: viewer_tz.name().to_string(),
+    })
+}
+
+/// Human readable slot for notifications, e.g. "02/01/2030 19:00 (Europe/London)"
+pub fn format_slot(date: &str, time_slot: &str, poll_tz: Tz, viewer_tz: Tz) -> Option<String> {
+    let local = slot_instant(date, time_slot, poll_tz)?.with_timezone(&viewer_tz);
+    Some(format!(
+        "{} ({})",
+        local.format("%d/%m/%Y %H:%M"),
+        viewer_tz.name()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_localize_rome_to_london() {
+        let rome = parse_timezone("Europe/Rome").unwrap();
+        let london = parse_timezone("Europe/London").unwrap();
+
+        // Winter: Rome is UTC+1, London UTC+0
+        let winter = localize_slot("2030-01-10", "20:00", rome, london).unwrap();
+        assert_eq!(winter.time_slot, "19:00");
+        assert_eq!(winter.utc, "2030-01-10T19:00:00+00:00");
+        assert_eq!(winter.utc_offset, "+00:00");
+
+        // Summer: both shift by an hour
+        let summer = localize_slot("2030-07-10", "20:00", rome, london).unwrap();
+        assert_eq!(summer.time_slot, "19:00");
+        assert_eq!(summer.utc, "2030-07-10T18:00:00+00:00");
+        assert_eq!(summer.utc_offset, "+01:00");
+
+        // Just past midnight in Rome is still the previous day in London
+        let late = localize_slot("2030-01-10", "00:30", rome, london).unwrap();
+        assert_eq!(late.date, "2030-01-09");
+    }
+
+    #[test]
+    fn test_dst_transitions() {
+        let rome = parse_timezone("Europe/Rome").unwrap();
+
+        // 2030-03-31 02:30 doesn't exist in Rome: moved past the gap
+        let gap = slot_instant("2030-03-31", "02:30", rome).unwrap();
+        assert_eq!(gap.to_rfc3339(), "2030-03-31T01:30:00+00:00");
+
+        // 2030-10-27 02:30 happens twice: the first (summer time) one wins
+        let overlap = slot_instant("2030-10-27", "02:30", rome).unwrap();
+        assert_eq!(overlap.to_rfc3339(), "2030-10-27T00:30:00+00:00");
+    }
+
+    #[test]
+    fn test_viewer_timezone_precedence() {
+        assert_eq!(
+            viewer_timezone(Some("Europe/London"), Some("Europe/Rome"), "UTC").unwrap(),
+            Tz::Europe__London
+        );
+        assert_eq!(
+            viewer_timezone(None, Some("Europe/Rome"), "UTC").unwrap(),
+            Tz::Europe__Rome
+        );
+        assert_eq!(
+            viewer_timezone(None, Some("Not/AZone"), "Europe/London").unwrap(),
+            Tz::Europe__London
+        );
+        assert!(viewer_timezone(Some("Mars/Olympus"), None, "UTC").is_err());
+    }
+}
@@ -98,19 +98,158 @@ pub async fn send_reminder_email(
     email: &str,
     session_name: &str,
     message: &str,
+    session_time: Option<&str>,
 ) -> Result<(), String> {
     let subject = format!("Promemoria Sessione: {}", session_name);
+    let when = session_time
+        .map(|t| format!("<p><strong>Quando:</strong> {}</p>", t))
+        .unwrap_or_default();
     let body = format!(
         r#"
         <h2>Promemoria Sessione: {}</h2>
         <p>Ciao,</p>
         <p>Questo è un promemoria per la tua prossima sessione.</p>
+        {}
         <p><strong>Messaggio:</strong> {}</p>
         <br>
         <p>A presto!</p>
         "#,
-        session_name, message
+        session_name, when, message
     );
 
     send_email(email, &subject, &body).await
 }
+
+/// Avvisa un giocatore che le date della sessione sono cambiate e i suoi voti sono stati
+/// spostati o rimossi
+pub async fn send_poll_changed_email(
+    email: &str,
+    session_name: &str,
+    moved: usize,
+    dropped: usize,
+) -> Result<(), String> {
+    let subject = format!("Sessione modificata: {}", session_name);
+    let mut changes = String::new();
+    if moved > 0 {
+        changes.push_str(&format!(
+            "<li>{} disponibilità spostate sui nuovi orari</li>",
+            moved
+        ));
+    }
+    if dropped > 0 {
+        changes.push_str(&format!(
+            "<li>{} disponibilità rimosse perché data o orario non sono più proposti</li>",
+            dropped
+        ));
+    }
+    let body = format!(
+        r#"
+        <h2>Sessione modificata: {}</h2>
+        <p>Ciao,</p>
+        <p>L'organizzatore ha cambiato le date o gli orari proposti.</p>
+        <ul>{}</ul>
+        <p>Controlla le tue disponibilità e aggiornale se necessario.</p>
+        <br>
+        <p>A presto!</p>
+        "#,
+        session_name, changes
+    );
+
+    send_email(email, &subject, &body).await
+}
+
+/// Indirizzo pubblico dell'app, usato per i link nelle email (APP_BASE_URL)
+pub fn app_base_url() -> String {
+    match env::var("APP_BASE_URL") {
+        Ok(v) => v.trim_end_matches('/').to_string(),
+        Err(_) => "http://localhost:3000".to_string(),
+    }
+}
+
+/// Link personale con cui un invitato vota senza account
+pub fn invite_link(poll_id: &str, access_token: &str) -> String {
+    format!(
+        "{}/participate.html?id={}&invite={}",
+        app_base_url(),
+        poll_id,
+        access_token
+    )
+}
+
+/// Link personale che, aperto da un utente connesso, collega la partecipazione al suo account
+pub fn claim_link(poll_id: &str, access_token: &str) -> String {
+    format!("{}&claim=1", invite_link(poll_id, access_token))
+}
+
+/// Invita un giocatore a indicare le sue disponibilità per una sessione
+pub async fn send_invitation_email(
+    email: &str,
+    session_name: &str,
+    link: &str,
+) -> Result<(), String> {
+    let subject = format!("Invito alla sessione: {}", session_name);
+    let body = format!(
+        r#"
+        <h2>Sei invitato: {}</h2>
+        <p>Ciao,</p>
+        <p>Il tuo DM sta organizzando una nuova sessione e vuole sapere quando sei disponibile.</p>
+        <p><a href="{}">Indica le tue disponibilità</a></p>
+        <p>Il link è personale: non condividerlo con altri giocatori.</p>
+        <br>
+        <p>A presto!</p>
+        "#,
+        session_name, link
+    );
+
+    send_email(email, &subject, &body).await
+}
+
+/// Nuovo link personale richiesto da un invitato che ha perso il precedente
+pub async fn send_recovery_email(
+    email: &str,
+    session_name: &str,
+    link: &str,
+) -> Result<(), String> {
+    let subject = format!("Il tuo link per la sessione: {}", session_name);
+    let body = format!(
+        r#"
+        <h2>Il tuo nuovo link: {}</h2>
+        <p>Ciao,</p>
+        <p>Hai chiesto un nuovo link personale per indicare le tue disponibilità.</p>
+        <p><a href="{}">Apri la sessione</a></p>
+        <p>Il link precedente non è più valido. Se non sei stato tu a chiederlo, puoi ignorare questa email.</p>
+        <br>
+        <p>A presto!</p>
+        "#,
+        session_name, link
+    );
+
+    send_email(email, &subject, &body).await
+}
+
+/// Conferma dell'indirizzo prima di collegare le partecipazioni fatte senza account
+pub async fn send_claim_confirmation_email(
+    email: &str,
+    sessions: &[(String, String)],
+) -> Result<(), String> {
+    let subject = "Collega le tue sessioni al tuo account";
+    let items: String = sessions
+        .iter()
+        .map(|(title, link)| format!(r#"<li><a href="{}">{}</a></li>"#, link, title))
+        .collect();
+    let body = format!(
+        r#"
+        <h2>Collega le tue sessioni</h2>
+        <p>Ciao,</p>
+        <p>Hai chiesto di collegare al tuo account le sessioni a cui hai partecipato con questo indirizzo.</p>
+        <p>Apri ciascun link dopo aver effettuato l'accesso per confermare:</p>
+        <ul>{}</ul>
+        <p>Se non sei stato tu a chiederlo, puoi ignorare questa email.</p>
+        <br>
+        <p>A presto!</p>
+        "#,
+        items
+    );
+
+    send_email(email, subject, &body).await
+}
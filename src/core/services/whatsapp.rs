@@ -50,7 +50,7 @@ pub fn validate_phone_number(phone: &str) -> bool {
 
     // E.164 requires 7-15 digits (including country code)
     let digit_count = digits.len();
-    digit_count >= 7 && digit_count <= 15
+    (7..=15).contains(&digit_count)
 }
 
 /// Formats a phone number for WhatsApp (adds whatsapp: prefix)
@@ -1,26 +1,121 @@
 use anyhow::{anyhow, Result};
-use redb::{Database, ReadableTable, TableDefinition};
+use redb::{Database, ReadableTable, ReadableTableMetadata, Table, TableDefinition};
 use std::sync::Arc;
+use tokio::sync::{mpsc, watch};
 
 // Table: Key = "stream_id/version_formatted", Value = Event Payload (Bincode/Bytes)
 // We use a helper function to generate keys.
 const EVENTS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("events");
+// Table: Key = global position (1, 2, ...) in commit order, Value = key in EVENTS_TABLE
+const GLOBAL_TABLE: TableDefinition<u64, &str> = TableDefinition::new("global");
+
+// Events a subscription reads per round trip to the database, and holds for a slow
+// subscriber before waiting on it
+const SUBSCRIPTION_PAGE: usize = 256;
+
+/// An append found the stream at another version than the writer expected: someone
+/// else wrote to it in between. Callers can `downcast_ref` it out of the error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConcurrencyError {
+    pub stream_id: String,
+    pub expected: u64,
+    pub found: u64,
+}
+
+impl std::fmt::Display for ConcurrencyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Concurrency Error on {}: Expected version {}, found {}",
+            self.stream_id, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ConcurrencyError {}
+
+/// Events to append to one stream, if it is still at `expected_version`
+#[derive(Debug, Clone)]
+pub struct StreamAppend {
+    pub stream_id: String,
+    pub events: Vec<Vec<u8>>,
+    pub expected_version: u64,
+}
+
+/// An event read back with its position in the stream (starting at 1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamEvent {
+    pub version: u64,
+    pub data: Vec<u8>,
+}
+
+/// An event of any stream, with its position in the global log (starting at 1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedEvent {
+    pub position: u64,
+    pub stream_id: String,
+    pub version: u64,
+    pub data: Vec<u8>,
+}
 
 pub struct RedbEventStore {
     db: Arc<Database>,
+    // Position of the last committed event, watched by subscriptions
+    head: watch::Sender<u64>,
+}
+
+/// Runs `work` on the blocking thread pool: redb transactions do disk IO and wait for
+/// each other's locks, which must not stall the async runtime.
+async fn run_blocking<T: Send + 'static>(
+    db: Arc<Database>,
+    work: impl FnOnce(&Database) -> Result<T> + Send + 'static,
+) -> Result<T> {
+    tokio::task::spawn_blocking(move || work(&db)).await?
 }
 
 impl RedbEventStore {
     pub fn new(path: &str) -> Result<Self> {
-        let db = Database::builder().create(path)?;
+        Self::init(Database::builder().create(path)?)
+    }
+
+    /// A store that lives only as long as the process (tests)
+    pub fn in_memory() -> Result<Self> {
+        Self::init(Database::builder().create_with_backend(redb::backends::InMemoryBackend::new())?)
+    }
+
+    fn init(db: Database) -> Result<Self> {
         let write_txn = db.begin_write()?;
+        let head;
         {
-            // Ensure table exists
-            write_txn.open_table(EVENTS_TABLE)?;
+            // Ensure tables exist
+            let events = write_txn.open_table(EVENTS_TABLE)?;
+            let mut global = write_txn.open_table(GLOBAL_TABLE)?;
+
+            // Events stored before the global log existed get positions in key order
+            if global.is_empty()? && !events.is_empty()? {
+                let keys = events
+                    .iter()?
+                    .map(|entry| entry.map(|(k, _)| k.value().to_string()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                for (position, key) in (1..).zip(keys) {
+                    global.insert(position, key.as_str())?;
+                }
+            }
+            head = Self::last_position(&global)?;
         }
         write_txn.commit()?;
 
-        Ok(Self { db: Arc::new(db) })
+        Ok(Self {
+            db: Arc::new(db),
+            head: watch::Sender::new(head),
+        })
+    }
+
+    async fn blocking<T: Send + 'static>(
+        &self,
+        work: impl FnOnce(&Database) -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        run_blocking(self.db.clone(), work).await
     }
 
     fn make_key(stream_id: &str, version: u64) -> String {
@@ -28,79 +123,510 @@ impl RedbEventStore {
         format!("{}/{:020}", stream_id, version)
     }
 
+    /// Keys bounding every event of the stream: "stream_id/" to "stream_id0" covers all
+    /// "stream_id/..." keys, '/' being 47 and '0' 48 in ASCII
+    fn stream_bounds(stream_id: &str) -> (String, String) {
+        (format!("{}/", stream_id), format!("{}0", stream_id))
+    }
+
+    fn version_of(key: &str) -> u64 {
+        // Parse version from key "stream_id/000...123"
+        key.rsplit('/')
+            .next()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Version of the last event in the stream (0 for an empty stream)
+    fn last_version(
+        table: &impl ReadableTable<&'static str, &'static [u8]>,
+        stream_id: &str,
+    ) -> Result<u64> {
+        let (start_key, end_key) = Self::stream_bounds(stream_id);
+        let mut range = table.range(start_key.as_str()..end_key.as_str())?;
+        match range.next_back() {
+            Some(entry) => Ok(Self::version_of(entry?.0.value())),
+            None => Ok(0),
+        }
+    }
+
+    /// Position of the last event in the global log (0 when empty)
+    fn last_position(global: &impl ReadableTable<u64, &'static str>) -> Result<u64> {
+        Ok(global.last()?.map(|(k, _)| k.value()).unwrap_or(0))
+    }
+
+    /// Checks the stream's version and appends `events` after it, inside the caller's
+    /// write transaction, giving them the global positions after `position`. Returns the
+    /// new version.
+    fn append_in(
+        table: &mut Table<&'static str, &'static [u8]>,
+        global: &mut Table<u64, &'static str>,
+        position: &mut u64,
+        stream_id: &str,
+        events: &[Vec<u8>],
+        expected_version: u64,
+    ) -> Result<u64> {
+        // 1. Concurrency Check: Find current version
+        let last_version = Self::last_version(table, stream_id)?;
+        if last_version != expected_version {
+            return Err(ConcurrencyError {
+                stream_id: stream_id.to_string(),
+                expected: expected_version,
+                found: last_version,
+            }
+            .into());
+        }
+
+        // 2. Append
+        let mut version = expected_version;
+        for event_data in events {
+            version += 1;
+            let key = Self::make_key(stream_id, version);
+            table.insert(key.as_str(), event_data.as_slice())?;
+            *position += 1;
+            global.insert(*position, key.as_str())?;
+        }
+        Ok(version)
+    }
+
+    /// Current version of a stream, to pass back as `expected_version`
+    pub async fn current_version(&self, stream_id: &str) -> Result<u64> {
+        let stream_id = stream_id.to_string();
+        self.blocking(move |db| {
+            let read_txn = db.begin_read()?;
+            let table = read_txn.open_table(EVENTS_TABLE)?;
+            Self::last_version(&table, &stream_id)
+        })
+        .await
+    }
+
     pub async fn append(
         &self,
         stream_id: &str,
         event_data: &[u8],
         expected_version: u64,
     ) -> Result<u64> {
-        // Redb is synchronous/blocking, so strict async usage might need `spawn_blocking`
-        // if high throughput, but for now direct call in async function is acceptable
-        // if latency is low. For "100% Reliability", correctness is key.
-        // We clone Arc DB to move into blocking task if needed, but redb handles are cheap.
+        self.append_all(stream_id, &[event_data], expected_version)
+            .await
+    }
 
-        // Note: For true async-friendliness we should wrap this in spawn_blocking,
-        // but let's keep it simple first.
+    /// Appends `events` in order, all or none, if the stream is still at
+    /// `expected_version`. Returns the new version.
+    pub async fn append_all(
+        &self,
+        stream_id: &str,
+        events: &[&[u8]],
+        expected_version: u64,
+    ) -> Result<u64> {
+        let versions = self
+            .append_batch(vec![StreamAppend {
+                stream_id: stream_id.to_string(),
+                events: events.iter().map(|e| e.to_vec()).collect(),
+                expected_version,
+            }])
+            .await?;
+        Ok(versions[0])
+    }
 
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(EVENTS_TABLE)?;
-
-            // 1. Concurrency Check: Find current version
-            // We scan the stream to find "last" key.
-            // Range scan: "stream_id/" to "stream_id0" (next char)
-            let start_key = format!("{}/", stream_id);
-            let end_key = format!("{}0", stream_id); // '0' is after '/' in ASCII? No.
-                                                     // '/' is 47. '0' is 48. So "stream_id/" to "stream_id0" covers all "stream_id/..."
-
-            let mut last_version = 0;
-
-            // This is efficient?
-            // A better way is to iterate in reverse from the end of the range,
-            // but redb range iterators are double ended?
-            let range = table.range(start_key.as_str()..end_key.as_str())?;
-            if let Some(Ok((k, _))) = range.last() {
-                let value = k.value();
-                // Parse version from key "stream_id/000...123"
-                let parts: Vec<&str> = value.split('/').collect();
-                if let Some(ver_str) = parts.last() {
-                    last_version = ver_str.parse::<u64>().unwrap_or(0);
+    /// Appends to several streams in one transaction: either every stream gets its
+    /// events or, if any of them moved past its expected version, none does. Returns the
+    /// new version of each stream, in order. A stream listed twice is expected at the
+    /// version its earlier append left it at.
+    pub async fn append_batch(&self, appends: Vec<StreamAppend>) -> Result<Vec<u64>> {
+        let (versions, position) = self
+            .blocking(move |db| {
+                let write_txn = db.begin_write()?;
+                let mut versions = Vec::with_capacity(appends.len());
+                let position;
+                {
+                    let mut table = write_txn.open_table(EVENTS_TABLE)?;
+                    let mut global = write_txn.open_table(GLOBAL_TABLE)?;
+                    let mut next_position = Self::last_position(&global)?;
+                    for append in &appends {
+                        versions.push(Self::append_in(
+                            &mut table,
+                            &mut global,
+                            &mut next_position,
+                            &append.stream_id,
+                            &append.events,
+                            append.expected_version,
+                        )?);
+                    }
+                    position = next_position;
                 }
+                write_txn.commit()?;
+                Ok((versions, position))
+            })
+            .await?;
+
+        // Wake the subscriptions up; commits finishing out of order never move it back
+        self.head.send_if_modified(|head| {
+            let moved = position > *head;
+            *head = (*head).max(position);
+            moved
+        });
+        Ok(versions)
+    }
+
+    pub async fn read_stream(&self, stream_id: &str) -> Result<Vec<Vec<u8>>> {
+        Ok(self
+            .read_stream_from(stream_id, 1)
+            .await?
+            .into_iter()
+            .map(|event| event.data)
+            .collect())
+    }
+
+    /// Events from `from_version` (inclusive) to the end of the stream, oldest first
+    pub async fn read_stream_from(
+        &self,
+        stream_id: &str,
+        from_version: u64,
+    ) -> Result<Vec<StreamEvent>> {
+        let stream_id = stream_id.to_string();
+        self.blocking(move |db| {
+            let read_txn = db.begin_read()?;
+            let table = read_txn.open_table(EVENTS_TABLE)?;
+
+            let start_key = Self::make_key(&stream_id, from_version);
+            let (_, end_key) = Self::stream_bounds(&stream_id);
+
+            let mut events = Vec::new();
+            for result in table.range(start_key.as_str()..end_key.as_str())? {
+                let (k, v) = result?;
+                events.push(StreamEvent {
+                    version: Self::version_of(k.value()),
+                    data: v.value().to_vec(),
+                });
             }
+            Ok(events)
+        })
+        .await
+    }
+
+    /// Up to `max_count` events, newest first, starting at `from_version` (inclusive) or
+    /// at the end of the stream when `None`
+    pub async fn read_stream_backward(
+        &self,
+        stream_id: &str,
+        from_version: Option<u64>,
+        max_count: usize,
+    ) -> Result<Vec<StreamEvent>> {
+        let stream_id = stream_id.to_string();
+        self.blocking(move |db| {
+            let read_txn = db.begin_read()?;
+            let table = read_txn.open_table(EVENTS_TABLE)?;
 
-            if last_version != expected_version {
-                return Err(anyhow!(
-                    "Concurrency Error: Expected version {}, found {}",
-                    expected_version,
-                    last_version
-                ));
+            let (start_key, end_key) = Self::stream_bounds(&stream_id);
+            let end_key = match from_version {
+                // The key right after `from_version`'s
+                Some(version) => Self::make_key(&stream_id, version.saturating_add(1)),
+                None => end_key,
+            };
+
+            let mut events = Vec::new();
+            for result in table
+                .range(start_key.as_str()..end_key.as_str())?
+                .rev()
+                .take(max_count)
+            {
+                let (k, v) = result?;
+                events.push(StreamEvent {
+                    version: Self::version_of(k.value()),
+                    data: v.value().to_vec(),
+                });
             }
+            Ok(events)
+        })
+        .await
+    }
 
-            // 2. Append
-            let new_version = last_version + 1;
-            let key = Self::make_key(stream_id, new_version);
-            table.insert(key.as_str(), event_data)?;
+    /// Position of the last event committed to any stream (0 for an empty store)
+    pub fn head_position(&self) -> u64 {
+        *self.head.borrow()
+    }
+
+    fn read_global(
+        db: &Database,
+        from_position: u64,
+        max_count: usize,
+    ) -> Result<Vec<RecordedEvent>> {
+        let read_txn = db.begin_read()?;
+        let global = read_txn.open_table(GLOBAL_TABLE)?;
+        let table = read_txn.open_table(EVENTS_TABLE)?;
+
+        let mut events = Vec::new();
+        for result in global.range(from_position..)?.take(max_count) {
+            let (position, key) = result?;
+            let key = key.value();
+            let data = table
+                .get(key)?
+                .ok_or_else(|| anyhow!("Global log points to a missing event: {}", key))?;
+            let (stream_id, _) = key.rsplit_once('/').unwrap_or((key, ""));
+            events.push(RecordedEvent {
+                position: position.value(),
+                stream_id: stream_id.to_string(),
+                version: Self::version_of(key),
+                data: data.value().to_vec(),
+            });
         }
-        write_txn.commit()?;
+        Ok(events)
+    }
 
-        Ok(expected_version + 1)
+    /// Up to `max_count` events of every stream from `from_position` (inclusive), in the
+    /// order they were committed
+    pub async fn read_all_from(
+        &self,
+        from_position: u64,
+        max_count: usize,
+    ) -> Result<Vec<RecordedEvent>> {
+        self.blocking(move |db| Self::read_global(db, from_position, max_count))
+            .await
     }
 
-    pub async fn read_stream(&self, stream_id: &str) -> Result<Vec<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(EVENTS_TABLE)?;
+    /// Every event from `from_position` (inclusive) on, then each new one as it is
+    /// committed, for projections to fold into their read models. A projection stores
+    /// the last position it handled and resumes from the next one. The subscription ends
+    /// when the receiver is dropped or reading fails.
+    pub fn subscribe(&self, from_position: u64) -> mpsc::Receiver<RecordedEvent> {
+        let (sender, receiver) = mpsc::channel(SUBSCRIPTION_PAGE);
+        let db = self.db.clone();
+        // Watching before the first read: a commit landing during it wakes us up again
+        let mut head = self.head.subscribe();
 
-        let start_key = format!("{}/", stream_id);
-        let end_key = format!("{}0", stream_id); // Assumes stream_id doesn't contain older chars than '/'
+        tokio::spawn(async move {
+            let mut next = from_position.max(1);
+            loop {
+                // Catch up on everything committed so far, a page at a time
+                let from = next;
+                let page = match run_blocking(db.clone(), move |db| {
+                    Self::read_global(db, from, SUBSCRIPTION_PAGE)
+                })
+                .await
+                {
+                    Ok(page) => page,
+                    Err(e) => {
+                        tracing::error!("Event subscription stopped: {}", e);
+                        return;
+                    }
+                };
+                let caught_up = page.len() < SUBSCRIPTION_PAGE;
+                for event in page {
+                    next = event.position + 1;
+                    if sender.send(event).await.is_err() {
+                        return; // Subscriber gone
+                    }
+                }
 
-        let mut events = Vec::new();
-        let range = table.range(start_key.as_str()..end_key.as_str())?;
+                // Live tail: wait for the next commit
+                if caught_up {
+                    tokio::select! {
+                        changed = head.changed() => {
+                            if changed.is_err() {
+                                return; // Store dropped
+                            }
+                        }
+                        _ = sender.closed() => return,
+                    }
+                }
+            }
+        });
+        receiver
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn next(subscription: &mut mpsc::Receiver<RecordedEvent>) -> RecordedEvent {
+        tokio::time::timeout(std::time::Duration::from_secs(5), subscription.recv())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    fn versions(events: &[StreamEvent]) -> Vec<u64> {
+        events.iter().map(|e| e.version).collect()
+    }
+
+    #[tokio::test]
+    async fn test_append_checks_expected_version() {
+        let store = RedbEventStore::in_memory().unwrap();
+        assert_eq!(store.current_version("poll-1/p1").await.unwrap(), 0);
+
+        assert_eq!(
+            store
+                .append_all("poll-1/p1", &[b"a", b"b"], 0)
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(store.append("poll-1/p2", b"x", 0).await.unwrap(), 1);
+
+        let err = store.append("poll-1/p1", b"c", 1).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ConcurrencyError>(),
+            Some(&ConcurrencyError {
+                stream_id: "poll-1/p1".to_string(),
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(
+            store.read_stream("poll-1/p1").await.unwrap(),
+            vec![b"a".to_vec(), b"b".to_vec()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_append_batch_is_all_or_nothing() {
+        let store = RedbEventStore::in_memory().unwrap();
+        let append = |stream_id: &str, data: &[u8], expected_version| StreamAppend {
+            stream_id: stream_id.to_string(),
+            events: vec![data.to_vec()],
+            expected_version,
+        };
+
+        let new_versions = store
+            .append_batch(vec![
+                append("poll-1/a", b"1", 0),
+                append("poll-1/b", b"2", 0),
+                append("poll-1/a", b"3", 1),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(new_versions, vec![1, 1, 2]);
+
+        // The stale second append sinks the first one too
+        let err = store
+            .append_batch(vec![
+                append("poll-1/c", b"4", 0),
+                append("poll-1/b", b"5", 0),
+            ])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ConcurrencyError>()
+                .map(|e| e.stream_id.as_str()),
+            Some("poll-1/b")
+        );
+        assert_eq!(store.current_version("poll-1/c").await.unwrap(), 0);
+        assert_eq!(
+            store.read_stream("poll-1/a").await.unwrap(),
+            vec![b"1".to_vec(), b"3".to_vec()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_global_log_and_subscriptions() {
+        let store = RedbEventStore::in_memory().unwrap();
+        store.append("poll-1/a", b"1", 0).await.unwrap();
+        store.append("poll-1/b", b"2", 0).await.unwrap();
+        store.append("poll-1/a", b"3", 1).await.unwrap();
+        assert_eq!(store.head_position(), 3);
+
+        let all = store.read_all_from(2, 10).await.unwrap();
+        assert_eq!(
+            all,
+            vec![
+                RecordedEvent {
+                    position: 2,
+                    stream_id: "poll-1/b".to_string(),
+                    version: 1,
+                    data: b"2".to_vec()
+                },
+                RecordedEvent {
+                    position: 3,
+                    stream_id: "poll-1/a".to_string(),
+                    version: 2,
+                    data: b"3".to_vec()
+                },
+            ]
+        );
+
+        // Catch up from position 2, then follow new commits
+        let mut subscription = store.subscribe(2);
+        assert_eq!(next(&mut subscription).await.data, b"2".to_vec());
+        assert_eq!(next(&mut subscription).await.data, b"3".to_vec());
+        store.append("poll-1/c", b"4", 0).await.unwrap();
+        let live = next(&mut subscription).await;
+        assert_eq!((live.position, live.stream_id.as_str()), (4, "poll-1/c"));
 
-        for result in range {
-            let (_, v) = result?;
-            events.push(v.value().to_vec());
+        // A failed append takes no position
+        assert!(store.append("poll-1/c", b"x", 0).await.is_err());
+        store.append("poll-1/c", b"5", 1).await.unwrap();
+        assert_eq!(next(&mut subscription).await.position, 5);
+    }
+
+    #[tokio::test]
+    async fn test_positions_backfilled_for_older_stores() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.redb");
+        {
+            // A store written before the global log existed
+            let db = Database::create(&path).unwrap();
+            let write_txn = db.begin_write().unwrap();
+            {
+                let mut table = write_txn.open_table(EVENTS_TABLE).unwrap();
+                for key in [
+                    "poll-1/a/00000000000000000001",
+                    "poll-1/b/00000000000000000001",
+                ] {
+                    table.insert(key, b"old".as_slice()).unwrap();
+                }
+            }
+            write_txn.commit().unwrap();
         }
 
-        Ok(events)
+        let store = RedbEventStore::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(store.head_position(), 2);
+        store.append("poll-1/a", b"new", 1).await.unwrap();
+        let streams: Vec<(u64, String)> = store
+            .read_all_from(1, 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.position, e.stream_id))
+            .collect();
+        assert_eq!(
+            streams,
+            vec![
+                (1, "poll-1/a".to_string()),
+                (2, "poll-1/b".to_string()),
+                (3, "poll-1/a".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_stream_from_and_backward() {
+        let store = RedbEventStore::in_memory().unwrap();
+        store
+            .append_all("poll-1/a", &[b"1", b"2", b"3", b"4"], 0)
+            .await
+            .unwrap();
+        // A stream whose name extends this one's is not read along with it
+        store.append("poll-1/ab", b"x", 0).await.unwrap();
+
+        let from_3 = store.read_stream_from("poll-1/a", 3).await.unwrap();
+        assert_eq!(versions(&from_3), vec![3, 4]);
+        assert_eq!(from_3[0].data, b"3".to_vec());
+        assert!(store
+            .read_stream_from("poll-1/a", 5)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let latest = store
+            .read_stream_backward("poll-1/a", None, 3)
+            .await
+            .unwrap();
+        assert_eq!(versions(&latest), vec![4, 3, 2]);
+        let older = store
+            .read_stream_backward("poll-1/a", Some(2), 10)
+            .await
+            .unwrap();
+        assert_eq!(versions(&older), vec![2, 1]);
     }
 }
@@ -250,6 +250,20 @@ pub async fn init_db() -> Result<DbPool, Box<dyn Error>> {
         }
     }
 
+    // Migration: Add attendance requirement to participants table
+    if let Err(e) = sqlx::query(
+        r#"
+        ALTER TABLE participants ADD COLUMN attendance TEXT NOT NULL DEFAULT 'optional';
+        "#,
+    )
+    .execute(&pool)
+    .await
+    {
+        if !e.to_string().contains("duplicate column") {
+            tracing::warn!("Migration failed (add attendance): {}", e);
+        }
+    }
+
     // Create activities table for activity feed
     sqlx::query(
         r#"
@@ -465,5 +479,251 @@ pub async fn init_db() -> Result<DbPool, Box<dyn Error>> {
         }
     }
 
+    // Migration: Per-poll slot recommendation settings
+    if let Err(e) =
+        sqlx::query("ALTER TABLE polls ADD COLUMN tentative_weight REAL NOT NULL DEFAULT 0.5")
+            .execute(&pool)
+            .await
+    {
+        if !e.to_string().contains("duplicate column") {
+            tracing::warn!("Migration failed (add tentative_weight): {}", e);
+        }
+    }
+
+    if let Err(e) =
+        sqlx::query("ALTER TABLE polls ADD COLUMN tie_breaker TEXT NOT NULL DEFAULT 'earliest'")
+            .execute(&pool)
+            .await
+    {
+        if !e.to_string().contains("duplicate column") {
+            tracing::warn!("Migration failed (add tie_breaker): {}", e);
+        }
+    }
+
+    // Migration: Quorum rule for automatic finalization
+    if let Err(e) = sqlx::query("ALTER TABLE polls ADD COLUMN quorum_min_available INTEGER")
+        .execute(&pool)
+        .await
+    {
+        if !e.to_string().contains("duplicate column") {
+            tracing::warn!("Migration failed (add quorum_min_available): {}", e);
+        }
+    }
+
+    if let Err(e) = sqlx::query("ALTER TABLE polls ADD COLUMN quorum_deadline INTEGER")
+        .execute(&pool)
+        .await
+    {
+        if !e.to_string().contains("duplicate column") {
+            tracing::warn!("Migration failed (add quorum_deadline): {}", e);
+        }
+    }
+
+    // Migration: Timezones (polls default to UTC, users inherit the poll's zone)
+    if let Err(e) = sqlx::query("ALTER TABLE polls ADD COLUMN timezone TEXT NOT NULL DEFAULT 'UTC'")
+        .execute(&pool)
+        .await
+    {
+        if !e.to_string().contains("duplicate column") {
+            tracing::warn!("Migration failed (add polls.timezone): {}", e);
+        }
+    }
+
+    if let Err(e) = sqlx::query("ALTER TABLE users ADD COLUMN timezone TEXT")
+        .execute(&pool)
+        .await
+    {
+        if !e.to_string().contains("duplicate column") {
+            tracing::warn!("Migration failed (add users.timezone): {}", e);
+        }
+    }
+
+    // Campaigns: long-running groups of sessions (polls) run by one DM
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS campaigns (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            description TEXT NOT NULL DEFAULT '',
+            dm_id TEXT NOT NULL,
+            default_location TEXT NOT NULL DEFAULT '',
+            default_time_slots TEXT NOT NULL DEFAULT '[]',
+            status TEXT NOT NULL DEFAULT 'active',
+            created_at INTEGER NOT NULL,
+            FOREIGN KEY (dm_id) REFERENCES users (id)
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS campaign_members (
+            campaign_id TEXT NOT NULL,
+            email TEXT NOT NULL,
+            user_id TEXT,
+            added_at INTEGER NOT NULL,
+            PRIMARY KEY (campaign_id, email),
+            FOREIGN KEY (campaign_id) REFERENCES campaigns (id) ON DELETE CASCADE
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    if let Err(e) = sqlx::query("ALTER TABLE polls ADD COLUMN campaign_id TEXT")
+        .execute(&pool)
+        .await
+    {
+        if !e.to_string().contains("duplicate column") {
+            tracing::warn!("Migration failed (add campaign_id): {}", e);
+        }
+    }
+
+    // Voting deadline: joins and votes are rejected once it has passed
+    if let Err(e) = sqlx::query("ALTER TABLE polls ADD COLUMN closes_at INTEGER")
+        .execute(&pool)
+        .await
+    {
+        if !e.to_string().contains("duplicate column") {
+            tracing::warn!("Migration failed (add closes_at): {}", e);
+        }
+    }
+
+    if let Err(e) = sqlx::query("ALTER TABLE polls ADD COLUMN cancel_reason TEXT")
+        .execute(&pool)
+        .await
+    {
+        if !e.to_string().contains("duplicate column") {
+            tracing::warn!("Migration failed (add cancel_reason): {}", e);
+        }
+    }
+
+    for column in ["finalized_start", "finalized_end", "session_minutes"] {
+        if let Err(e) = sqlx::query(&format!("ALTER TABLE polls ADD COLUMN {} INTEGER", column))
+            .execute(&pool)
+            .await
+        {
+            if !e.to_string().contains("duplicate column") {
+                tracing::warn!("Migration failed (add {}): {}", column, e);
+            }
+        }
+    }
+    backfill_finalized_sessions(&pool).await?;
+
+    // Recurring sessions of a campaign and their per-occurrence exceptions
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS session_series (
+            id TEXT PRIMARY KEY,
+            campaign_id TEXT NOT NULL,
+            first_date TEXT NOT NULL,
+            interval_weeks INTEGER NOT NULL DEFAULT 1,
+            start_time TEXT NOT NULL,
+            end_time TEXT NOT NULL,
+            timezone TEXT NOT NULL DEFAULT 'UTC',
+            mode TEXT NOT NULL DEFAULT 'poll',
+            lead_weeks INTEGER NOT NULL DEFAULT 2,
+            status TEXT NOT NULL DEFAULT 'active',
+            created_at INTEGER NOT NULL,
+            FOREIGN KEY (campaign_id) REFERENCES campaigns (id) ON DELETE CASCADE
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS series_exceptions (
+            series_id TEXT NOT NULL,
+            occurrence_date TEXT NOT NULL,
+            action TEXT NOT NULL,
+            moved_date TEXT,
+            moved_start_time TEXT,
+            created_at INTEGER NOT NULL,
+            PRIMARY KEY (series_id, occurrence_date),
+            FOREIGN KEY (series_id) REFERENCES session_series (id) ON DELETE CASCADE
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    // One row per generated occurrence, so the job never creates the same session twice
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS series_occurrences (
+            series_id TEXT NOT NULL,
+            occurrence_date TEXT NOT NULL,
+            poll_id TEXT,
+            created_at INTEGER NOT NULL,
+            PRIMARY KEY (series_id, occurrence_date),
+            FOREIGN KEY (series_id) REFERENCES session_series (id) ON DELETE CASCADE
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    // Reusable poll setups, instantiated with shifted dates
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS poll_templates (
+            id TEXT PRIMARY KEY,
+            owner_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT NOT NULL DEFAULT '',
+            location TEXT NOT NULL DEFAULT '',
+            dates TEXT NOT NULL DEFAULT '[]',
+            time_preferences TEXT NOT NULL DEFAULT '{}',
+            participants TEXT NOT NULL DEFAULT '[]',
+            required_participants TEXT NOT NULL DEFAULT '[]',
+            timezone TEXT NOT NULL DEFAULT 'UTC',
+            session_minutes INTEGER,
+            created_at INTEGER NOT NULL,
+            FOREIGN KEY (owner_id) REFERENCES users (id)
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
     Ok(pool)
 }
+
+/// Fills the structured start/end of polls finalized before they were stored, reading
+/// the old "date_HH:MM" key in the poll's zone. Unparseable keys are left alone.
+async fn backfill_finalized_sessions(pool: &DbPool) -> Result<(), sqlx::Error> {
+    use crate::core::scheduling::{attendance, session_time, timezone};
+
+    let rows: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT id, finalized_time, timezone FROM polls WHERE finalized_time IS NOT NULL AND finalized_start IS NULL",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for (id, finalized_time, poll_timezone) in rows {
+        let Some(session) = attendance::parse_slot_key(&finalized_time).and_then(|(date, slot)| {
+            let tz = timezone::parse_timezone(&poll_timezone).ok()?;
+            session_time::SessionTime::from_slot(
+                &date,
+                &slot,
+                tz,
+                chrono::Duration::minutes(session_time::DEFAULT_SESSION_MINUTES),
+            )
+        }) else {
+            continue;
+        };
+
+        sqlx::query("UPDATE polls SET finalized_start = ?, finalized_end = ? WHERE id = ?")
+            .bind(session.start.timestamp())
+            .bind(session.end.timestamp())
+            .bind(&id)
+            .execute(pool)
+            .await?;
+    }
+    Ok(())
+}
@@ -1,8 +1,9 @@
 use axum::{
     handler::Handler,
     routing::{delete, get, post, post_service, put},
-    Router,
+    Extension, Router,
 };
+use std::sync::Arc;
 use tower::Layer;
 use tower_governor::{
     governor::GovernorConfigBuilder, key_extractor::SmartIpKeyExtractor, GovernorLayer,
@@ -15,11 +16,16 @@ pub mod db;
 pub mod security;
 
 // Re-export / Alias modules
-use api::handlers::{activity as activity_handlers, admin as admin_stats, general as handlers};
+use crate::core::store::RedbEventStore;
+use api::handlers::{
+    activity as activity_handlers, admin as admin_stats, campaigns as campaign_handlers,
+    general as handlers, me as me_handlers, series as series_handlers,
+    templates as template_handlers,
+};
 use db::DbPool;
 use security::{audit, auth, authelia as authelia_auth, gdpr, headers as security_headers};
 
-pub fn create_router(pool: DbPool) -> Router {
+pub fn create_router(pool: DbPool, event_store: Arc<RedbEventStore>) -> Router {
     // Verify static assets directory exists
     let static_dir = match std::env::var("STATIC_DIR") {
         Ok(v) => v,
@@ -118,7 +124,7 @@ pub fn create_router(pool: DbPool) -> Router {
             "/polls",
             get(handlers::list_polls).post_service(
                 GovernorLayer {
-                    config: creation_governor_conf,
+                    config: creation_governor_conf.clone(),
                 }
                 .layer(handlers::create_poll.with_state(pool.clone())),
             ),
@@ -126,7 +132,23 @@ pub fn create_router(pool: DbPool) -> Router {
         // Story 1.6: Serve dynamic poll page with OG metadata (Short link)
         .route("/p/:id", get(handlers::serve_poll_page))
         .route("/polls/:id", get(handlers::get_poll))
+        .route(
+            "/polls/:id/recommendations",
+            get(handlers::get_poll_recommendations),
+        )
+        .route("/polls/:id/blocks", get(handlers::get_poll_blocks))
+        .route("/polls/:id/history", get(handlers::get_poll_history))
         .route("/polls/:id/join", post(handlers::join_poll))
+        .route(
+            "/polls/:id/recover",
+            post_service(
+                GovernorLayer {
+                    config: creation_governor_conf,
+                }
+                .layer(handlers::recover_link.with_state(pool.clone())),
+            ),
+        )
+        .route("/invites/:token", get(handlers::get_invite))
         .route(
             "/polls/:id/participants/:participant_id/availability",
             post_service(
@@ -139,7 +161,71 @@ pub fn create_router(pool: DbPool) -> Router {
         .route("/polls/:id", put(handlers::update_poll))
         .route("/polls/:id", delete(handlers::delete_poll))
         .route("/participants/:id", delete(handlers::delete_participant))
+        .route(
+            "/polls/:id/participants/:participant_id/attendance",
+            put(handlers::update_participant_attendance),
+        )
+        .route(
+            "/polls/:id/participants/:participant_id/invite",
+            post(handlers::resend_invite),
+        )
         .route("/polls/:id/finalize", put(handlers::finalize_poll))
+        .route("/polls/:id/quorum", put(handlers::update_poll_quorum))
+        .route("/polls/:id/close", put(handlers::close_poll))
+        .route("/polls/:id/reopen", put(handlers::reopen_poll))
+        .route("/polls/:id/cancel", put(handlers::cancel_poll))
+        .route("/polls/:id/unfinalize", put(handlers::unfinalize_poll))
+        .route("/polls/:id/reschedule", put(handlers::reschedule_poll))
+        .route(
+            "/polls/:id/duplicate",
+            post(template_handlers::duplicate_poll),
+        )
+        .route("/me/polls", get(me_handlers::list_my_polls))
+        .route(
+            "/me/participations/claimable",
+            get(me_handlers::list_claimable_participations),
+        )
+        .route(
+            "/me/participations/claim",
+            post(me_handlers::claim_participations),
+        )
+        // Poll Template Routes
+        .route(
+            "/templates",
+            get(template_handlers::list_templates).post(template_handlers::create_template),
+        )
+        .route(
+            "/templates/:id",
+            get(template_handlers::get_template).delete(template_handlers::delete_template),
+        )
+        .route(
+            "/polls/from-template/:template_id",
+            post(template_handlers::create_poll_from_template),
+        )
+        // Campaign Routes
+        .route(
+            "/campaigns",
+            get(campaign_handlers::list_campaigns).post(campaign_handlers::create_campaign),
+        )
+        .route(
+            "/campaigns/:id",
+            get(campaign_handlers::get_campaign)
+                .put(campaign_handlers::update_campaign)
+                .delete(campaign_handlers::delete_campaign),
+        )
+        .route(
+            "/campaigns/:id/series",
+            get(series_handlers::list_series).post(series_handlers::create_series),
+        )
+        .route(
+            "/series/:id",
+            put(series_handlers::update_series).delete(series_handlers::delete_series),
+        )
+        .route(
+            "/series/:id/exceptions/:date",
+            put(series_handlers::set_series_exception)
+                .delete(series_handlers::delete_series_exception),
+        )
         // Admin Routes
         .route("/admin/login", post(admin_stats::admin_login))
         .route("/admin/google-login", post(handlers::google_login))
@@ -184,7 +270,10 @@ pub fn create_router(pool: DbPool) -> Router {
         });
 
     // Combine auth and general routes
-    let api_routes = Router::new().merge(auth_routes).merge(general_routes);
+    let api_routes = Router::new()
+        .merge(auth_routes)
+        .merge(general_routes)
+        .layer(Extension(event_store));
 
     // Build main router
     Router::new()
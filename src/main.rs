@@ -38,6 +38,19 @@ async fn main() {
         }
     };
 
+    // Open the append-only vote history
+    let event_store_path = match std::env::var("EVENT_STORE_PATH") {
+        Ok(v) => v,
+        Err(_) => "dnd_events.redb".to_string(),
+    };
+    let event_store = match core::store::RedbEventStore::new(&event_store_path) {
+        Ok(store) => std::sync::Arc::new(store),
+        Err(e) => {
+            tracing::error!("Failed to open event store {}: {}", event_store_path, e);
+            std::process::exit(1);
+        }
+    };
+
     // Verify static assets directory exists
     let static_dir = match std::env::var("STATIC_DIR") {
         Ok(v) => v,
@@ -54,8 +67,22 @@ async fn main() {
         core::jobs::cleanup::run_cron(cleanup_pool).await;
     });
 
+    // Finalize polls whose quorum rule is satisfied
+    let auto_finalize_pool = pool.clone();
+    let auto_finalize_events = event_store.clone();
+    tokio::spawn(async move {
+        core::jobs::auto_finalize::run_cron(auto_finalize_pool, auto_finalize_events).await;
+    });
+
+    // Generate the upcoming sessions of recurring campaign series
+    let recurring_pool = pool.clone();
+    let recurring_events = event_store.clone();
+    tokio::spawn(async move {
+        core::jobs::recurring_sessions::run_cron(recurring_pool, recurring_events).await;
+    });
+
     // Create App Router using library function
-    let app = dnd_scheduler::create_router(pool);
+    let app = dnd_scheduler::create_router(pool, event_store);
 
     // Run server
     let port_str = match std::env::var("PORT") {
@@ -1,7 +1,10 @@
+use crate::api::handlers::general::record_vote_fates;
 use crate::core::models::*;
+use crate::core::scheduling::reconcile::VoteFate;
+use crate::core::store::RedbEventStore;
 use crate::db::DbPool;
 use axum::{
-    extract::{FromRef, Path, State},
+    extract::{Extension, FromRef, Path, State},
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
@@ -9,6 +12,7 @@ use axum::{
 use bcrypt::{hash, verify, DEFAULT_COST};
 use chrono::Utc;
 use serde::Serialize;
+use std::sync::Arc;
 use uuid::Uuid;
 
 // Error response for JSON
@@ -235,7 +239,7 @@ pub async fn register(
     let sanitized_name = sanitize_string(&payload.name);
     let default_role = "player";
 
-    if let Err(_) = sqlx::query(
+    if sqlx::query(
         "INSERT INTO users (id, email, password_hash, name, role, created_at, last_login) VALUES (?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(&user_id)
@@ -247,6 +251,7 @@ pub async fn register(
     .bind(now)
     .execute(&pool)
     .await
+    .is_err()
     {
         return Err(json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to create user"));
     }
@@ -332,6 +337,7 @@ pub async fn register(
         name: sanitized_name,
         role: default_role.to_string(),
         phone: payload.phone,
+        timezone: None,
         created_at: now,
     };
 
@@ -613,9 +619,15 @@ pub async fn login_google(
             created_at: now,
             last_login: Some(now),
             phone: None,
+            timezone: None,
         }
     };
 
+    // Google verified the address: link the participations made with it
+    if let Err(e) = crate::core::scheduling::claims::claim_by_email(&pool, &user, None).await {
+        tracing::error!("Failed to claim participations for {}: {}", user.email, e);
+    }
+
     // Return the SAME Google Token as the session token (Stateless)
     // The client will send this token back in headers
     Ok(Json(UserAuthResponse {
@@ -690,6 +702,7 @@ pub async fn get_current_session_user(auth_user: AuthUser) -> Json<UserPublic> {
 
 pub async fn delete_account(
     State(pool): State<DbPool>,
+    Extension(event_store): Extension<Arc<RedbEventStore>>,
     auth_user: AuthUser,
 ) -> Result<StatusCode, (StatusCode, String)> {
     let user = auth_user.0;
@@ -706,12 +719,24 @@ pub async fn delete_account(
             )
         })?;
 
-    // Delete user's availability entries
-    sqlx::query("DELETE FROM availability WHERE participant_id IN (SELECT id FROM participants WHERE user_id = ?)")
+    // Delete user's availability entries, clearing them in the vote history too
+    let votes: Vec<Availability> = sqlx::query_as("SELECT * FROM availability WHERE participant_id IN (SELECT id FROM participants WHERE user_id = ?) ORDER BY id")
+        .bind(&user.id)
+        .fetch_all(&pool)
+        .await
+        .unwrap_or_default();
+    let deleted = sqlx::query("DELETE FROM availability WHERE participant_id IN (SELECT id FROM participants WHERE user_id = ?)")
         .bind(&user.id)
         .execute(&pool)
         .await
-        .ok(); // Ignore if no entries
+        .is_ok(); // Ignore if no entries
+    if deleted {
+        let dropped: Vec<_> = votes
+            .into_iter()
+            .map(|vote| (vote, VoteFate::Dropped))
+            .collect();
+        record_vote_fates(&event_store, &dropped).await;
+    }
 
     // Delete user's participant entries
     sqlx::query("DELETE FROM participants WHERE user_id = ?")
@@ -756,6 +781,7 @@ pub struct UpdateProfileRequest {
     pub email: Option<String>,
     pub role: Option<String>,
     pub phone: Option<String>,
+    pub timezone: Option<String>, // IANA zone; empty string clears it
 }
 
 pub async fn update_profile(
@@ -801,27 +827,43 @@ pub async fn update_profile(
         }
     }
 
+    // Validate timezone if provided
+    if let Some(ref timezone) = payload.timezone {
+        if !timezone.is_empty() {
+            crate::core::scheduling::timezone::parse_timezone(timezone)
+                .map_err(|e| json_error(StatusCode::BAD_REQUEST, &e))?;
+        }
+    }
+
     // Build update values
     let new_name = payload.name.unwrap_or_else(|| user.name.clone());
     let new_email = payload.email.unwrap_or_else(|| user.email.clone());
     let new_role = payload.role.unwrap_or_else(|| user.role.clone());
     let new_phone = payload.phone.or(user.phone.clone());
+    let new_timezone = match payload.timezone {
+        Some(timezone) if timezone.is_empty() => None,
+        Some(timezone) => Some(timezone),
+        None => user.timezone.clone(),
+    };
     let role_changed = new_role != user.role;
 
-    sqlx::query("UPDATE users SET name = ?, email = ?, role = ?, phone = ? WHERE id = ?")
-        .bind(&new_name)
-        .bind(&new_email)
-        .bind(&new_role)
-        .bind(&new_phone)
-        .bind(&user.id)
-        .execute(&pool)
-        .await
-        .map_err(|_| {
-            json_error(
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to update profile",
-            )
-        })?;
+    sqlx::query(
+        "UPDATE users SET name = ?, email = ?, role = ?, phone = ?, timezone = ? WHERE id = ?",
+    )
+    .bind(&new_name)
+    .bind(&new_email)
+    .bind(&new_role)
+    .bind(&new_phone)
+    .bind(&new_timezone)
+    .bind(&user.id)
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        json_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to update profile",
+        )
+    })?;
 
     // Log audit - include role change if applicable
     let audit_details = if role_changed {
@@ -854,6 +896,7 @@ pub async fn update_profile(
         name: new_name,
         role: new_role,
         phone: new_phone,
+        timezone: new_timezone,
         created_at: user.created_at,
     }))
 }
@@ -1100,11 +1143,7 @@ where
         let token = match auth_header {
             Some(header) => {
                 let auth_str = header.to_str().unwrap_or("");
-                if auth_str.starts_with("Bearer ") {
-                    Some(&auth_str[7..])
-                } else {
-                    None
-                }
+                auth_str.strip_prefix("Bearer ")
             }
             None => None,
         };
@@ -1144,12 +1183,9 @@ where
             .and_then(|h| h.to_str().ok())
             .and_then(|s| {
                 s.split(';').find_map(|c| {
-                    let c = c.trim();
-                    if c.starts_with("admin_session=") {
-                        Some(c["admin_session=".len()..].to_string())
-                    } else {
-                        None
-                    }
+                    c.trim()
+                        .strip_prefix("admin_session=")
+                        .map(|t| t.to_string())
                 })
             });
 
@@ -1195,6 +1231,142 @@ where
     }
 }
 
+/// Header carrying the `adminToken` returned by `create_poll`
+pub const ADMIN_TOKEN_HEADER: &str = "x-admin-token";
+
+/// Someone allowed to manage a poll: whoever holds its admin token, its organizer's
+/// session, or a site admin. The poll is the `:id` path parameter, except on
+/// `/participants/:id` where it is the participant's poll.
+pub struct PollOrganizer {
+    pub poll_id: String,
+    /// The logged-in user, `None` when the admin token was used
+    pub user: Option<User>,
+}
+
+fn organizer_error(status: StatusCode, message: &str) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error: message.to_string(),
+        }),
+    )
+}
+
+#[axum::async_trait]
+impl<S> axum::extract::FromRequestParts<S> for PollOrganizer
+where
+    DbPool: axum::extract::FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let pool = DbPool::from_ref(state);
+        let db_error =
+            |_: sqlx::Error| organizer_error(StatusCode::INTERNAL_SERVER_ERROR, "Database error");
+
+        let params =
+            <axum::extract::RawPathParams as axum::extract::FromRequestParts<S>>::from_request_parts(
+                parts, state,
+            )
+            .await
+            .map_err(|_| organizer_error(StatusCode::BAD_REQUEST, "Missing poll id"))?;
+        let id = params
+            .iter()
+            .find(|(name, _)| *name == "id")
+            .map(|(_, value)| value.to_string())
+            .ok_or_else(|| organizer_error(StatusCode::BAD_REQUEST, "Missing poll id"))?;
+
+        let on_participant = parts
+            .extensions
+            .get::<axum::extract::MatchedPath>()
+            .is_some_and(|path| path.as_str().ends_with("/participants/:id"));
+        let poll_id = if on_participant {
+            sqlx::query_scalar::<_, String>("SELECT poll_id FROM participants WHERE id = ?")
+                .bind(&id)
+                .fetch_optional(&pool)
+                .await
+                .map_err(db_error)?
+                .ok_or_else(|| organizer_error(StatusCode::NOT_FOUND, "Participant not found"))?
+        } else {
+            id
+        };
+
+        let (admin_token, organizer_id): (Option<String>, Option<String>) =
+            sqlx::query_as("SELECT admin_token, organizer_id FROM polls WHERE id = ?")
+                .bind(&poll_id)
+                .fetch_optional(&pool)
+                .await
+                .map_err(db_error)?
+                .ok_or_else(|| organizer_error(StatusCode::NOT_FOUND, "Poll not found"))?;
+
+        // 1. The poll's admin token, for organizers without an account
+        let header_token = parts
+            .headers
+            .get(ADMIN_TOKEN_HEADER)
+            .and_then(|h| h.to_str().ok())
+            .map(str::trim);
+        if let (Some(given), Some(expected)) = (header_token, admin_token.as_deref()) {
+            if !given.is_empty() && given == expected {
+                return Ok(PollOrganizer {
+                    poll_id,
+                    user: None,
+                });
+            }
+        }
+
+        // 2. A session: the organizer's own, or a site admin's
+        let Some(token) = session_token(parts) else {
+            return Err(match header_token {
+                Some(_) => organizer_error(StatusCode::FORBIDDEN, "Invalid admin token"),
+                None => organizer_error(StatusCode::UNAUTHORIZED, "Missing authentication"),
+            });
+        };
+        let user = validate_session(&pool, &token)
+            .await
+            .map_err(|(status, msg)| (status, Json(ErrorResponse { error: msg })))?;
+
+        if user.role == "admin" || organizer_id.as_deref() == Some(user.id.as_str()) {
+            Ok(PollOrganizer {
+                poll_id,
+                user: Some(user),
+            })
+        } else {
+            Err(organizer_error(
+                StatusCode::FORBIDDEN,
+                "Only the poll organizer can manage this poll",
+            ))
+        }
+    }
+}
+
+/// Session token from the `admin_session` cookie or a Bearer header
+fn session_token(parts: &axum::http::request::Parts) -> Option<String> {
+    let cookie_token = parts
+        .headers
+        .get(axum::http::header::COOKIE)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| {
+            s.split(';').find_map(|c| {
+                c.trim()
+                    .strip_prefix("admin_session=")
+                    .map(|t| t.to_string())
+            })
+        });
+
+    cookie_token.or_else(|| {
+        parts
+            .headers
+            .get(axum::http::header::AUTHORIZATION)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|s| s.strip_prefix("Bearer "))
+            .map(|t| t.to_string())
+    })
+}
+
 pub async fn validate_admin_session(
     pool: &DbPool,
     token: &str,
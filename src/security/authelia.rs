@@ -200,6 +200,7 @@ pub async fn sync_authelia_user(pool: &DbPool, info: &AutheliaUserInfo) -> Resul
             created_at: now,
             last_login: Some(now),
             phone: None,
+            timezone: None,
         })
     }
 }
@@ -342,6 +343,11 @@ pub async fn get_authelia_session(
         )
     })?;
 
+    // The SSO provider vouches for the address: link the participations made with it
+    if let Err(e) = crate::core::scheduling::claims::claim_by_email(&pool, &user, None).await {
+        tracing::error!("Failed to claim participations for {}: {}", user.email, e);
+    }
+
     Ok(Json(user.into()))
 }
 
@@ -10,6 +10,19 @@ use axum::{
 use chrono::Utc;
 use serde_json::json;
 
+// Row shape for the user details query in `export_data`:
+// (id, email, name, role, phone, created_at, consent_marketing, consent_analytics)
+type UserDetailsRow = (
+    String,
+    String,
+    String,
+    String,
+    Option<String>,
+    i64,
+    bool,
+    bool,
+);
+
 // ============================================================================
 // GET CONSENT PREFERENCES
 // ============================================================================
@@ -199,7 +212,7 @@ pub async fn export_data(
     let now = Utc::now();
 
     // Get user details with consent
-    let user_details: Option<(String, String, String, String, Option<String>, i64, bool, bool)> = sqlx::query_as(
+    let user_details: Option<UserDetailsRow> = sqlx::query_as(
         "SELECT id, email, name, role, phone, created_at, consent_marketing, consent_analytics FROM users WHERE id = ?",
     )
     .bind(&user.id)
@@ -383,7 +396,9 @@ pub async fn delete_account_confirmed(
         .await
         .ok();
 
-    // Delete user's availability entries
+    // Delete user's availability entries. Unlike a plain account deletion the vote
+    // history is left alone on purpose: erasure must not write new records about the
+    // user, and the participants go too, so no poll leads to their streams anymore.
     sqlx::query("DELETE FROM availability WHERE participant_id IN (SELECT id FROM participants WHERE user_id = ?)")
         .bind(&user.id)
         .execute(&pool)
@@ -224,12 +224,12 @@ class AdminManager {
             }
 
             // Fetch all polls
-            const response = await fetch('/api/polls');
+            const response = await fetch('/api/polls?limit=200');
             if (!response.ok) {
                 throw new Error('Failed to fetch polls');
             }
 
-            const polls = await response.json();
+            const polls = (await response.json()).polls;
 
             // Fetch detailed data for each poll to get participants and availability
             const pollsWithDetails = await Promise.all(
@@ -514,12 +514,12 @@ class AdminManager {
 
         try {
             // Fetch all polls
-            const response = await fetch('/api/polls');
+            const response = await fetch('/api/polls?limit=200');
             if (!response.ok) {
                 throw new Error('Failed to fetch polls');
             }
 
-            const polls = await response.json();
+            const polls = (await response.json()).polls;
 
             if (polls.length === 0) {
                 tbody.innerHTML = '<tr><td colspan="6" class="text-center text-gray-400 py-8">No sessions found. Create a new session to get started.</td></tr>';
@@ -1042,7 +1042,9 @@ class AdminManager {
             document.getElementById('edit-title').value = data.poll.title;
             document.getElementById('edit-description').value = data.poll.description;
             document.getElementById('edit-location').value = data.poll.location;
-            document.getElementById('edit-timeRange').value = data.poll.time_range;
+            document.getElementById('edit-timeRange').value = typeof data.poll.time_range === 'string'
+                ? data.poll.time_range
+                : JSON.stringify(data.poll.time_range);
 
             // Parse and display dates
             this.editDates = JSON.parse(data.poll.dates);
@@ -1186,7 +1188,7 @@ class AdminManager {
             ]);
 
             const users = usersResponse.ok ? await usersResponse.json() : [];
-            const polls = pollsResponse.ok ? await pollsResponse.json() : [];
+            const polls = pollsResponse.ok ? (await pollsResponse.json()).polls : [];
 
             // Fetch poll details
             const pollDetails = await Promise.all(
@@ -1376,25 +1378,18 @@ END:VCALENDAR`;
         if (!sessionId) return;
 
         try {
-            // Fetch session details
-            const response = await fetch(`/api/polls/${sessionId}`);
-            if (!response.ok) throw new Error('Sessione non trovata');
-
-            const data = await response.json();
-            const poll = data.poll;
-
-            this.showSuccessMessage('Sessione Duplicata', `"${poll.title} (Copia)" sarà creata.`);
-
-            // Redirect to create poll with pre-filled data
-            const params = new URLSearchParams({
-                duplicate: sessionId,
-                title: `${poll.title} (Copia)`,
-                description: poll.description || '',
-                location: poll.location || ''
+            // Copies text, slots and invitees one week later
+            const response = await fetch(`/api/polls/${sessionId}/duplicate`, {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({ offsetDays: 7 })
             });
+            if (!response.ok) throw new Error(await response.text() || 'Sessione non trovata');
+
+            this.showSuccessMessage('Sessione Duplicata', 'La copia è stata creata per la settimana successiva.');
 
             setTimeout(() => {
-                window.location.href = `create-poll.html?${params.toString()}`;
+                window.location.reload();
             }, 1500);
         } catch (error) {
             this.showError('Errore', error.message || 'Impossibile duplicare sessione');
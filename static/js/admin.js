@@ -136,11 +136,11 @@ class AdminDashboard {
 
     async loadPolls() {
         try {
-            const response = await fetch('/api/polls');
-            const polls = await response.json();
+            const response = await fetch('/api/polls?limit=200');
+            const { polls, total } = await response.json();
 
             // Update stats
-            document.getElementById('total-polls').textContent = polls.length;
+            document.getElementById('total-polls').textContent = total;
 
             const tbody = document.getElementById('polls-table-body');
 
@@ -26,7 +26,7 @@ class DDSchedulerApp {
         try {
             const response = await fetch('/api/polls');
             if (!response.ok) throw new Error('Failed to fetch polls');
-            const basicPolls = await response.json();
+            const basicPolls = (await response.json()).polls;
 
             this.polls = await Promise.all(basicPolls.map(async (basicPoll) => {
                 const detailResponse = await fetch(`/api/polls/${basicPoll.id}`);
@@ -201,7 +201,13 @@ class DDSchedulerApp {
             poll.datesList = [];
         }
 
-        poll.timeSlots = poll.time_range ? JSON.parse(poll.time_range) : [];
+        // time_range: list of slots for every date, or per-day { date: [slots] }
+        const timeRange = typeof poll.time_range === 'string'
+            ? JSON.parse(poll.time_range || '[]')
+            : poll.time_range;
+        poll.timeSlots = Array.isArray(timeRange)
+            ? timeRange
+            : [...new Set(Object.values(timeRange || {}).flat())].sort();
 
         poll.participants = data.participants.map(p => ({
             id: p.id,
@@ -348,6 +348,8 @@ class AuthManager {
 
             console.log('💾 Token and user saved to localStorage');
 
+            await this.offerParticipationClaims(data.token);
+
             return data;
         } catch (error) {
             console.error('❌ Login error:', error);
@@ -377,6 +379,8 @@ class AuthManager {
             this.setToken(data.token);
             this.setUser(data.user);
 
+            await this.offerParticipationClaims(data.token);
+
             return data;
         } catch (error) {
             console.error('Registration error:', error);
@@ -384,6 +388,35 @@ class AuthManager {
         }
     }
 
+    /**
+     * Offer to link the polls joined with this email before signing in
+     */
+    async offerParticipationClaims(token) {
+        try {
+            const headers = { 'Authorization': `Bearer ${token}` };
+            const response = await fetch('/api/me/participations/claimable', { headers });
+            if (!response.ok) return;
+
+            const { participations } = await response.json();
+            if (!participations || participations.length === 0) return;
+
+            const titles = participations.map(p => `• ${p.pollTitle}`).join('\n');
+            if (!confirm(`Hai già partecipato a queste sessioni con la tua email:\n${titles}\n\nVuoi collegarle al tuo account? Ti invieremo un'email con un link di conferma per ciascuna.`)) {
+                return;
+            }
+
+            await fetch('/api/me/participations/claim', {
+                method: 'POST',
+                headers: { ...headers, 'Content-Type': 'application/json' },
+                body: JSON.stringify({})
+            });
+            alert('Controlla la tua email: apri i link ricevuti dopo aver effettuato l\'accesso per collegare le sessioni.');
+        } catch (error) {
+            // Linking is optional: never block the login on it
+            console.error('Participation claim error:', error);
+        }
+    }
+
     // Google Login
     async loginWithGoogle(credential) {
         try {
@@ -36,11 +36,57 @@ class AvailabilityManager {
             }
         }
 
+        // Personal link from an invitation email: vote as that participant
+        const invite = urlParams.get('invite');
+        if (invite) {
+            this.acceptInvite(invite, urlParams.get('claim') === '1').then(() => {
+                if (pollId) this.selectSession(pollId);
+            });
+            return;
+        }
+
         if (pollId) {
             this.selectSession(pollId);
         }
     }
 
+    async acceptInvite(token, claim = false) {
+        try {
+            const response = await fetch(`/api/invites/${token}`);
+            if (!response.ok) throw new Error('Invito non valido');
+
+            const data = await response.json();
+            this.currentUser = {
+                id: data.participantId,
+                participantId: data.participantId,
+                name: data.name,
+                email: data.email,
+                accessToken: token
+            };
+            localStorage.setItem('currentUser', JSON.stringify(this.currentUser));
+            this.updateUserDisplay();
+
+            // Confirmation link from the claim email: link the participation to the account
+            const authToken = localStorage.getItem('authToken');
+            if (claim && authToken) {
+                const claimResponse = await fetch('/api/me/participations/claim', {
+                    method: 'POST',
+                    headers: {
+                        'Authorization': `Bearer ${authToken}`,
+                        'Content-Type': 'application/json'
+                    },
+                    body: JSON.stringify({ accessTokens: [token] })
+                });
+                if (claimResponse.ok && (await claimResponse.json()).count > 0) {
+                    this.showNotification('Sessione collegata', 'La partecipazione è ora collegata al tuo account.');
+                }
+            }
+        } catch (error) {
+            console.error('Error accepting invite:', error);
+            this.showNotification('Errore', 'Il link di invito non è valido o è scaduto', 'error');
+        }
+    }
+
     updateUserDisplay() {
         const userInfoDisplay = document.getElementById('user-info-display');
         if (!userInfoDisplay) return;
@@ -106,7 +152,7 @@ class AvailabilityManager {
                     
                     <div class="mt-3 pt-3 border-t border-gray-100">
                         <div class="flex items-center justify-between">
-                            <span class="text-xs text-gray-500">${poll.status === 'active' ? 'Attiva' : 'Finalizzata'}</span>
+                            <span class="text-xs text-gray-500">${poll.status === 'active' ? 'Attiva' : poll.status === 'closed' ? 'Chiusa' : poll.status === 'cancelled' ? 'Annullata' : 'Finalizzata'}</span>
                             <div class="w-16 h-1 bg-gray-200 rounded-full">
                                 <div class="h-1 bg-emerald rounded-full" style="width: ${responseRate}%"></div>
                             </div>
@@ -131,8 +177,16 @@ class AvailabilityManager {
             });
 
             if (!response.ok) {
-                const errorData = await response.json().catch(() => ({}));
-                throw new Error(errorData.message || 'Impossibile unirsi alla sessione');
+                const message = await response.text().catch(() => '');
+                // The email is already on the poll: its owner gets a fresh link by email
+                if (response.status === 409 && message.startsWith('This email already joined')) {
+                    await this.requestNewLink(pollId, this.currentUser.email);
+                    this.currentUser = null;
+                    localStorage.removeItem('currentUser');
+                    this.updateUserDisplay();
+                    return;
+                }
+                throw new Error(message || 'Impossibile unirsi alla sessione');
             }
 
             const data = await response.json();
@@ -167,6 +221,20 @@ class AvailabilityManager {
         }
     }
 
+    async requestNewLink(pollId, email) {
+        const response = await fetch(`/api/polls/${pollId}/recover`, {
+            method: 'POST',
+            headers: {
+                'Content-Type': 'application/json'
+            },
+            body: JSON.stringify({ email })
+        });
+        if (!response.ok) {
+            throw new Error('Impossibile inviare un nuovo link');
+        }
+        this.showNotification('Controlla la tua email', 'Questa email partecipa già alla sessione: ti abbiamo inviato un nuovo link personale.');
+    }
+
     async identifyUser() {
         if (this.currentUser) return this.currentUser;
 
@@ -583,15 +651,7 @@ class AvailabilityManager {
         }
 
         // Parse time slots
-        let timeSlots = [];
-        try {
-            const timeSlotsData = typeof this.selectedSession.time_range === 'string'
-                ? JSON.parse(this.selectedSession.time_range)
-                : this.selectedSession.time_range;
-            timeSlots = Array.isArray(timeSlotsData) ? timeSlotsData : ['18:00', '19:00', '20:00', '21:00'];
-        } catch (e) {
-            timeSlots = ['18:00', '19:00', '20:00', '21:00'];
-        }
+        const { timeSlots } = this.getTimePreferences();
 
         if (dates.length === 0) {
             container.innerHTML = '<div class="col-span-full text-center text-gray-500 py-8">Nessuna data disponibile per questa sessione</div>';
@@ -631,6 +691,26 @@ class AvailabilityManager {
         }
     }
 
+    // time_range is {} (no slots declared), a list used for every date or { date: [slots] }
+    getTimePreferences() {
+        const defaults = ['18:00', '19:00', '20:00', '21:00'];
+        let data = this.selectedSession.time_range;
+        try {
+            if (typeof data === 'string') data = JSON.parse(data);
+        } catch (e) {
+            data = null;
+        }
+
+        if (Array.isArray(data) && data.length > 0) {
+            return { timeSlots: data, byDate: null };
+        }
+        if (data && typeof data === 'object' && !Array.isArray(data) && Object.keys(data).length > 0) {
+            const allSlots = [...new Set(Object.values(data).flat())].sort();
+            return { timeSlots: allSlots, byDate: data };
+        }
+        return { timeSlots: defaults, byDate: null };
+    }
+
     createGridCell(content, type, id = null) {
         const cell = document.createElement('div');
         // Map old types to new CSS classes
@@ -698,15 +778,7 @@ class AvailabilityManager {
 
         const chart = echarts.init(chartContainer);
 
-        let timeSlots = [];
-        try {
-            const timeSlotsData = typeof this.selectedSession.time_range === 'string'
-                ? JSON.parse(this.selectedSession.time_range)
-                : this.selectedSession.time_range;
-            timeSlots = Array.isArray(timeSlotsData) ? timeSlotsData : ['18:00', '19:00', '20:00', '21:00'];
-        } catch (e) {
-            timeSlots = ['18:00', '19:00', '20:00', '21:00'];
-        }
+        const { timeSlots } = this.getTimePreferences();
 
         let dates = [];
         try {
@@ -965,6 +1037,8 @@ class AvailabilityManager {
                 throw new Error('Access token missing');
             }
 
+            // The grid shows every slot on every day; only send the ones offered on that date
+            const { byDate } = this.getTimePreferences();
             const availabilityList = Object.entries(this.availabilityData).map(([key, status]) => {
                 const [date, time] = key.split('_');
                 return {
@@ -972,7 +1046,7 @@ class AvailabilityManager {
                     timeSlot: time,
                     status: status
                 };
-            });
+            }).filter(entry => !byDate || (byDate[entry.date] || []).includes(entry.timeSlot));
 
             const participantId = this.currentUser.participantId || this.currentUser.id;
 
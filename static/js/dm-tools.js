@@ -48,7 +48,7 @@ class DMToolsManager {
         try {
             const response = await fetch('/api/polls');
             if (response.ok) {
-                return await response.json();
+                return (await response.json()).polls;
             }
         } catch (error) {
             console.error('Error fetching sessions:', error);
@@ -228,17 +228,15 @@ class DMToolsManager {
             case 'duplicate':
                 if (window.adminManager) window.adminManager.duplicateSessioneForSession(sessionId); // Reuse if available, else reimplement
                 else {
-                    // Quick implementation of duplicate logic reusing create-poll page
-                    const response = await fetch(`/api/polls/${sessionId}`);
+                    // Server-side copy one week later, with invitees and slots
+                    const response = await fetch(`/api/polls/${sessionId}/duplicate`, {
+                        method: 'POST',
+                        headers: { 'Content-Type': 'application/json' },
+                        body: JSON.stringify({ offsetDays: 7 })
+                    });
                     if (response.ok) {
-                        const data = await response.json();
-                        const params = new URLSearchParams({
-                            duplicate: sessionId,
-                            title: `${data.poll.title} (Copia)`,
-                            description: data.poll.description || '',
-                            location: data.poll.location || ''
-                        });
-                        window.location.href = `/create-poll.html?${params.toString()}`;
+                        this.showNotification('Duplicata', 'Sessione copiata alla settimana successiva');
+                        this.loadSessionsPage(); // Refresh
                     }
                 }
                 break;
@@ -438,6 +438,7 @@ class EnhancedPollCreator {
                 location: "Online", // Default for now
                 dates: this.selectedDates,
                 timePreferences: this.timePreferences, // NEW: per-day time preferences
+                timezone: document.getElementById('timezone')?.value || undefined,
                 participants: emailList
             };
 
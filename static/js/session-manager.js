@@ -741,9 +741,9 @@ class SessionManager {
                                     ${availabilityPercent}% Disponibile
                                 </span>
                             ` : `
-                                <button onclick="sessionManager.sendIndividualReminder('${participantId}')" 
+                                <button onclick="sessionManager.resendInvite('${participantId.id || participantId}')" 
                                         class="text-sm text-amber hover:text-amber-600">
-                                    Invia Promemoria
+                                    Reinvia Invito
                                 </button>
                             `}
                         </div>
@@ -865,6 +865,19 @@ class SessionManager {
         });
     }
 
+    // Organizers are recognized by their session or by the poll's admin token
+    organizerHeaders(pollId) {
+        const headers = {
+            'Content-Type': 'application/json',
+            'Authorization': `Bearer ${window.authManager.getToken()}`
+        };
+        const adminToken = localStorage.getItem(`dnd_poll_admin_${pollId}`);
+        if (adminToken) {
+            headers['X-Admin-Token'] = adminToken;
+        }
+        return headers;
+    }
+
     async confirmFinalize() {
         const selectedTimeInput = document.querySelector('input[name="finalTime"]:checked');
         if (!selectedTimeInput) {
@@ -878,12 +891,9 @@ class SessionManager {
         try {
             const response = await fetch(`/api/polls/${this.selectedSession.id}/finalize`, {
                 method: 'PUT',
-                headers: {
-                    'Content-Type': 'application/json',
-                    'Authorization': `Bearer ${window.authManager.getToken()}`
-                },
+                headers: this.organizerHeaders(this.selectedSession.id),
                 body: JSON.stringify({
-                    finalized_time: finalizedTime,
+                    start: finalizedTime,
                     notes: sessionNotes
                 })
             });
@@ -939,6 +949,29 @@ class SessionManager {
         this.showSuccessMessage('Promemoria Inviato', `Inviato promemoria a ${user ? user.name : 'giocatore'}.`);
     }
 
+    async resendInvite(participantId) {
+        if (!this.selectedSession) return;
+
+        try {
+            // Emails the player their personal voting link again
+            const response = await fetch(`/api/polls/${this.selectedSession.id}/participants/${participantId}/invite`, {
+                method: 'POST',
+                headers: this.organizerHeaders(this.selectedSession.id)
+            });
+
+            if (!response.ok) {
+                const error = await response.text();
+                throw new Error(error || 'Failed to resend invite');
+            }
+
+            const result = await response.json();
+            this.showSuccessMessage('Invito Inviato', `Link personale inviato a ${result.email}.`);
+        } catch (error) {
+            console.error('Error resending invite:', error);
+            this.showNotification('Errore', 'Impossibile reinviare l\'invito: ' + error.message, 'error');
+        }
+    }
+
     exportToCalendar(sessionId = null) {
         const targetSession = sessionId ?
             window.DDSchedulerApp.getPollById(sessionId) : this.selectedSession;
@@ -994,37 +1027,40 @@ END:VEVENT
 END:VCALENDAR`;
     }
 
-    duplicateSession(sessionId = null) {
+    async duplicateSession(sessionId = null) {
         const targetSession = sessionId ?
             window.DDSchedulerApp.getPollById(sessionId) : this.selectedSession;
 
         if (!targetSession) return;
 
-        // Create a copy with new ID
-        const duplicatedSession = {
-            ...targetSession,
-            id: 'poll-' + Date.now(),
-            title: `${targetSession.title} (Copia)`,
-            created_at: Date.now() / 1000,
-            status: 'active',
-            responses: {}
-        };
+        try {
+            // Same setup, invitees and slots one week later
+            const response = await fetch(`/api/polls/${targetSession.id}/duplicate`, {
+                method: 'POST',
+                headers: this.organizerHeaders(targetSession.id),
+                body: JSON.stringify({ offsetDays: 7 })
+            });
 
-        // In a real implementation, this would call the backend
-        // For now, just show success message
-        this.showSuccessMessage('Sessione Duplicata', `"${duplicatedSession.title}" creata con successo!`);
+            if (!response.ok) {
+                const error = await response.text();
+                throw new Error(error || 'Failed to duplicate session');
+            }
 
-        // Redirect to create poll with pre-filled data
-        const params = new URLSearchParams({
-            duplicate: targetSession.id,
-            title: duplicatedSession.title,
-            description: targetSession.description || '',
-            location: targetSession.location || ''
-        });
+            const result = await response.json();
+            if (result.adminToken) {
+                localStorage.setItem(`dnd_poll_admin_${result.id}`, result.adminToken);
+            }
 
-        setTimeout(() => {
-            window.location.href = `create-poll.html?${params.toString()}`;
-        }, 1500);
+            this.showSuccessMessage('Sessione Duplicata', `"${targetSession.title}" copiata alla settimana successiva!`);
+
+            // Open the copy for editing
+            setTimeout(() => {
+                window.location.href = `/manage.html?poll=${result.id}&action=edit`;
+            }, 1500);
+        } catch (error) {
+            console.error('Error duplicating poll:', error);
+            alert('Impossibile duplicare la sessione: ' + error.message);
+        }
     }
 
     viewSessionHistory(sessionId = null) {
@@ -1460,7 +1496,9 @@ END:VCALENDAR`;
                 location: location,
                 dates: this.editSelectedDates,
                 timePreferences: this.editTimePreferences,
-                participants: participants
+                participants: participants,
+                // Players whose votes are moved or dropped by the edit get an email
+                notifyAffected: true
             };
 
             console.log('Updating poll:', payload);
@@ -1468,10 +1506,7 @@ END:VCALENDAR`;
             // Make API call
             const response = await fetch(`/api/polls/${this.selectedSession.id}`, {
                 method: 'PUT',
-                headers: {
-                    'Content-Type': 'application/json',
-                    'Authorization': `Bearer ${window.authManager.getToken()}`
-                },
+                headers: this.organizerHeaders(this.selectedSession.id),
                 body: JSON.stringify(payload)
             });
 
@@ -1486,8 +1521,12 @@ END:VCALENDAR`;
             // Close modal
             this.closeEditModal();
 
-            // Show success message
-            this.showSuccessMessage('Session Updated', 'Your session has been successfully updated!');
+            // Show success message, mentioning votes that no longer fit the new dates
+            const affected = result.affectedParticipants || [];
+            const message = affected.length > 0
+                ? `Your session has been updated. Votes changed for: ${affected.map(p => p.name).join(', ')}`
+                : 'Your session has been successfully updated!';
+            this.showSuccessMessage('Session Updated', message);
 
             // Reload data
             setTimeout(() => {
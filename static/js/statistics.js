@@ -39,9 +39,9 @@
     async function loadPolls() {
         // Carica solo da API - nessun mock data
         try {
-            const response = await fetch('/api/polls');
+            const response = await fetch('/api/polls?limit=200');
             if (response.ok) {
-                const polls = await response.json();
+                const polls = (await response.json()).polls;
                 // Carica dettagli per ogni poll per avere i partecipanti
                 const pollsWithDetails = await Promise.all(
                     polls.map(async (poll) => {
@@ -221,7 +221,7 @@ async fn test_authelia_user_update_existing() {
 /// TEST 8: Gruppo "admins" assegna ruolo admin
 #[tokio::test]
 async fn test_authelia_group_admin_role() {
-    let groups = vec!["players", "admins"];
+    let groups = ["players", "admins"];
 
     let is_admin = groups.iter().any(|g| {
         g.eq_ignore_ascii_case("admins")
@@ -235,7 +235,7 @@ async fn test_authelia_group_admin_role() {
 /// TEST 9: Gruppo "players" non assegna ruolo admin
 #[tokio::test]
 async fn test_authelia_group_player_role() {
-    let groups = vec!["players", "users"];
+    let groups = ["players", "users"];
 
     let is_admin = groups.iter().any(|g| {
         g.eq_ignore_ascii_case("admins")
@@ -410,6 +410,7 @@ async fn test_authelia_config_enabled_parsing() {
 
 /// TEST 17: Display name da Remote-Name header
 #[tokio::test]
+#[allow(clippy::unnecessary_literal_unwrap)]
 async fn test_display_name_from_header() {
     let name = Some("Mario Rossi".to_string());
     let email = "mario@cronachednd.it";
@@ -421,6 +422,7 @@ async fn test_display_name_from_header() {
 
 /// TEST 18: Display name fallback a email prefix
 #[tokio::test]
+#[allow(clippy::unnecessary_literal_unwrap)]
 async fn test_display_name_fallback_to_email() {
     let name: Option<String> = None;
     let email = "mario.rossi@cronachednd.it";
@@ -2,6 +2,7 @@
 // Test di integrazione per il servizio email con TDD
 
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod email_tests {
     // ============================================================================
     // EMAIL ADDRESS VALIDATION TESTS
@@ -2,6 +2,7 @@
 // Utilities per setup e teardown dei test
 
 use axum::Router;
+use dnd_scheduler::core::store::RedbEventStore;
 use dnd_scheduler::create_router;
 use once_cell::sync::Lazy;
 use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
@@ -14,7 +15,7 @@ pub static TEST_DB: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 /// Crea un database SQLite temporaneo per i test
 pub async fn setup_test_db() -> Pool<Sqlite> {
     // Usa un database in-memory per i test (più veloce e senza problemi di permessi)
-    let database_url = format!("sqlite::memory:");
+    let database_url = "sqlite::memory:".to_string();
 
     // Crea il database
     let pool = SqlitePoolOptions::new()
@@ -32,7 +33,8 @@ pub async fn setup_test_db() -> Pool<Sqlite> {
 /// Setup dell'applicazione per i test (restituisce router e pool)
 pub async fn setup_test_app() -> (Router, Pool<Sqlite>) {
     let pool = setup_test_db().await;
-    let app = create_router(pool.clone());
+    let event_store = RedbEventStore::in_memory().expect("Failed to create test event store");
+    let app = create_router(pool.clone(), std::sync::Arc::new(event_store));
     (app, pool)
 }
 
@@ -52,8 +54,20 @@ async fn setup_schema(pool: &Pool<Sqlite>) {
             status TEXT NOT NULL DEFAULT 'active',
             finalized_at INTEGER,
             finalized_time TEXT,
+            finalized_start INTEGER,
+            finalized_end INTEGER,
             notes TEXT,
-            admin_token TEXT
+            admin_token TEXT,
+            organizer_id TEXT,
+            tentative_weight REAL NOT NULL DEFAULT 0.5,
+            tie_breaker TEXT NOT NULL DEFAULT 'earliest',
+            quorum_min_available INTEGER,
+            quorum_deadline INTEGER,
+            timezone TEXT NOT NULL DEFAULT 'UTC',
+            campaign_id TEXT,
+            closes_at INTEGER,
+            cancel_reason TEXT,
+            session_minutes INTEGER
         );
         "#,
     )
@@ -71,7 +85,9 @@ async fn setup_schema(pool: &Pool<Sqlite>) {
             email TEXT,
             access_token TEXT UNIQUE,
             user_id TEXT,
-            FOREIGN KEY (poll_id) REFERENCES polls (id)
+            attendance TEXT NOT NULL DEFAULT 'optional',
+            FOREIGN KEY (poll_id) REFERENCES polls (id),
+            UNIQUE(poll_id, email)
         );
         "#,
     )
@@ -109,7 +125,8 @@ async fn setup_schema(pool: &Pool<Sqlite>) {
             role TEXT NOT NULL DEFAULT 'player',
             created_at INTEGER NOT NULL,
             last_login INTEGER,
-            phone TEXT
+            phone TEXT,
+            timezone TEXT
         );
         "#,
     )
@@ -234,6 +251,109 @@ async fn setup_schema(pool: &Pool<Sqlite>) {
     .execute(pool)
     .await
     .expect("Failed to create activities table");
+
+    // Tabella campaigns
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS campaigns (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            description TEXT NOT NULL DEFAULT '',
+            dm_id TEXT NOT NULL,
+            default_location TEXT NOT NULL DEFAULT '',
+            default_time_slots TEXT NOT NULL DEFAULT '[]',
+            status TEXT NOT NULL DEFAULT 'active',
+            created_at INTEGER NOT NULL
+        );
+        "#,
+    )
+    .execute(pool)
+    .await
+    .expect("Failed to create campaigns table");
+
+    // Tabella campaign_members
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS campaign_members (
+            campaign_id TEXT NOT NULL,
+            email TEXT NOT NULL,
+            user_id TEXT,
+            added_at INTEGER NOT NULL,
+            PRIMARY KEY (campaign_id, email)
+        );
+        "#,
+    )
+    .execute(pool)
+    .await
+    .expect("Failed to create campaign_members table");
+
+    // Tabelle delle sessioni ricorrenti
+    for table in [
+        r#"
+        CREATE TABLE IF NOT EXISTS session_series (
+            id TEXT PRIMARY KEY,
+            campaign_id TEXT NOT NULL,
+            first_date TEXT NOT NULL,
+            interval_weeks INTEGER NOT NULL DEFAULT 1,
+            start_time TEXT NOT NULL,
+            end_time TEXT NOT NULL,
+            timezone TEXT NOT NULL DEFAULT 'UTC',
+            mode TEXT NOT NULL DEFAULT 'poll',
+            lead_weeks INTEGER NOT NULL DEFAULT 2,
+            status TEXT NOT NULL DEFAULT 'active',
+            created_at INTEGER NOT NULL
+        );
+        "#,
+        r#"
+        CREATE TABLE IF NOT EXISTS series_exceptions (
+            series_id TEXT NOT NULL,
+            occurrence_date TEXT NOT NULL,
+            action TEXT NOT NULL,
+            moved_date TEXT,
+            moved_start_time TEXT,
+            created_at INTEGER NOT NULL,
+            PRIMARY KEY (series_id, occurrence_date)
+        );
+        "#,
+        r#"
+        CREATE TABLE IF NOT EXISTS series_occurrences (
+            series_id TEXT NOT NULL,
+            occurrence_date TEXT NOT NULL,
+            poll_id TEXT,
+            created_at INTEGER NOT NULL,
+            PRIMARY KEY (series_id, occurrence_date)
+        );
+        "#,
+    ] {
+        sqlx::query(table)
+            .execute(pool)
+            .await
+            .expect("Failed to create session series tables");
+    }
+
+    // Tabella poll_templates
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS poll_templates (
+            id TEXT PRIMARY KEY,
+            owner_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT NOT NULL DEFAULT '',
+            location TEXT NOT NULL DEFAULT '',
+            dates TEXT NOT NULL DEFAULT '[]',
+            time_preferences TEXT NOT NULL DEFAULT '{}',
+            participants TEXT NOT NULL DEFAULT '[]',
+            required_participants TEXT NOT NULL DEFAULT '[]',
+            timezone TEXT NOT NULL DEFAULT 'UTC',
+            session_minutes INTEGER,
+            created_at INTEGER NOT NULL
+        );
+        "#,
+    )
+    .execute(pool)
+    .await
+    .expect("Failed to create poll_templates table");
 }
 
 /// Crea un utente test nel database
@@ -493,6 +613,96 @@ pub async fn cleanup_test_db(pool: &Pool<Sqlite>) {
     }
 }
 
+// Invia una richiesta JSON autenticata e restituisce status e body
+pub async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: &str,
+    body: serde_json::Value,
+) -> (axum::http::StatusCode, serde_json::Value) {
+    let bearer = format!("Bearer {}", token);
+    send_with_headers(app, method, uri, &[("Authorization", &bearer)], body).await
+}
+
+// Like `send`, with explicit headers instead of a Bearer token
+pub async fn send_with_headers(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    headers: &[(&str, &str)],
+    body: serde_json::Value,
+) -> (axum::http::StatusCode, serde_json::Value) {
+    use tower::util::ServiceExt;
+
+    let mut request = axum::http::Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .header("X-Forwarded-For", "127.0.0.1");
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+
+    let response = app
+        .clone()
+        .oneshot(
+            request
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null),
+    )
+}
+
+// Helper per creare un sondaggio di test
+pub async fn create_test_poll(_app: &Router) -> String {
+    // Implementazione semplificata: crea un sondaggio direttamente nel DB o via API
+    // Per semplicità, usiamo una chiamata API se possibile, o helper DB se abbiamo accesso al pool qui
+    // Ma setup_test_app ritorna (app, pool), quindi nel test abbiamo il pool.
+    // Questo helper dovrebbe accettare App? No, meglio se il test usa create_poll endpoint.
+    // Ma per autenticazione serve token.
+
+    // Placeholder - i test devono implementare la logica specifica o passare il pool a questo helper if needed.
+    // Modifichiamo la firma o assumiamo che il test lo faccia.
+    // In auth_tests.rs non usano questo helper.
+    // In test_anonymous.rs lo usiamo.
+    "admin_token".to_string()
+}
+
+// NOTE: create_test_poll above is a placeholder.
+// Real implementation should likely take &Pool and insert a poll directly.
+pub async fn create_test_poll_db(pool: &Pool<Sqlite>) -> String {
+    let poll_id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().timestamp();
+
+    sqlx::query(
+        "INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(&poll_id)
+    .bind("Test Poll")
+    .bind("Description")
+    .bind("Remote")
+    .bind(now)
+    .bind("[\"2023-10-10\"]")
+    .bind("[]")
+    .bind("active")
+    .execute(pool)
+    .await
+    .unwrap();
+
+    poll_id
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -543,41 +753,3 @@ mod tests {
         assert_eq!(result.0, 1);
     }
 }
-// Helper per creare un sondaggio di test
-pub async fn create_test_poll(_app: &Router) -> String {
-    // Implementazione semplificata: crea un sondaggio direttamente nel DB o via API
-    // Per semplicità, usiamo una chiamata API se possibile, o helper DB se abbiamo accesso al pool qui
-    // Ma setup_test_app ritorna (app, pool), quindi nel test abbiamo il pool.
-    // Questo helper dovrebbe accettare App? No, meglio se il test usa create_poll endpoint.
-    // Ma per autenticazione serve token.
-
-    // Placeholder - i test devono implementare la logica specifica o passare il pool a questo helper if needed.
-    // Modifichiamo la firma o assumiamo che il test lo faccia.
-    // In auth_tests.rs non usano questo helper.
-    // In test_anonymous.rs lo usiamo.
-    "admin_token".to_string()
-}
-
-// NOTE: create_test_poll above is a placeholder.
-// Real implementation should likely take &Pool and insert a poll directly.
-pub async fn create_test_poll_db(pool: &Pool<Sqlite>) -> String {
-    let poll_id = Uuid::new_v4().to_string();
-    let now = chrono::Utc::now().timestamp();
-
-    sqlx::query(
-        "INSERT INTO polls (id, title, description, location, created_at, dates, time_range, status) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
-    )
-    .bind(&poll_id)
-    .bind("Test Poll")
-    .bind("Description")
-    .bind("Remote")
-    .bind(now)
-    .bind("[\"2023-10-10\"]")
-    .bind("[]")
-    .bind("active")
-    .execute(pool)
-    .await
-    .unwrap();
-
-    poll_id
-}
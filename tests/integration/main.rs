@@ -10,6 +10,13 @@ mod email_tests;
 mod rbac_tests;
 mod test_anonymous;
 mod test_availability;
+mod test_campaigns;
+mod test_claims;
+mod test_history;
+mod test_invitations;
+mod test_my_polls;
+mod test_organizer_auth;
+mod test_templates;
 
 // Re-export helper functions for use in test modules
 pub use helpers::*;
@@ -0,0 +1,222 @@
+use crate::helpers::{create_test_user_with_session, send, send_with_headers, setup_test_app};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn test_campaign_sessions_inherit_defaults() {
+    let (app, pool) = setup_test_app().await;
+    let (_, dm_token) =
+        create_test_user_with_session(&pool, "dm@test.com", "SecurePass123!@#", "dm").await;
+    let (_, player_token) =
+        create_test_user_with_session(&pool, "player@test.com", "SecurePass123!@#", "player").await;
+    let (_, outsider_token) =
+        create_test_user_with_session(&pool, "outsider@test.com", "SecurePass123!@#", "player")
+            .await;
+
+    // 1. The DM creates the campaign with its table defaults
+    let (status, created) = send(
+        &app,
+        "POST",
+        "/api/campaigns",
+        &dm_token,
+        json!({
+            "name": "Curse of Strahd",
+            "description": "Gothic horror in Barovia",
+            "defaultLocation": "Discord",
+            "defaultTimeSlots": ["21:00", "20:00"],
+            "members": ["Player@test.com", "friend@test.com"]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", created);
+    let campaign_id = created["id"].as_str().unwrap().to_string();
+
+    // 2. A session scheduled under the campaign only needs title and dates
+    let (status, poll) = send(
+        &app,
+        "POST",
+        "/api/polls",
+        &dm_token,
+        json!({
+            "title": "Session 12",
+            "description": "",
+            "location": "",
+            "dates": ["2030-01-10"],
+            "participants": [],
+            "campaignId": campaign_id
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", poll);
+    let poll_id = poll["id"].as_str().unwrap().to_string();
+
+    let (location, time_range, linked): (String, String, Option<String>) =
+        sqlx::query_as("SELECT location, time_range, campaign_id FROM polls WHERE id = ?")
+            .bind(&poll_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(location, "Discord");
+    assert_eq!(time_range, r#"["20:00","21:00"]"#);
+    assert_eq!(linked.as_deref(), Some(campaign_id.as_str()));
+
+    let invited: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM participants WHERE poll_id = ?")
+        .bind(&poll_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(invited, 2);
+
+    // 3. Members see the campaign and its open poll, outsiders don't
+    let uri = format!("/api/campaigns/{}", campaign_id);
+    let (status, detail) = send(&app, "GET", &uri, &player_token, Value::Null).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(detail["openPolls"][0]["id"], poll_id.as_str());
+    assert_eq!(detail["members"].as_array().unwrap().len(), 2);
+
+    let (status, _) = send(&app, "GET", &uri, &outsider_token, Value::Null).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 4. Only the DM can schedule sessions for the campaign
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/polls",
+        &player_token,
+        json!({
+            "title": "Rogue session",
+            "description": "",
+            "location": "",
+            "dates": ["2030-01-11"],
+            "participants": [],
+            "campaignId": campaign_id
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // ...nor move an existing poll into it, with a session or the poll's admin token
+    let (status, own_poll) = send(
+        &app,
+        "POST",
+        "/api/polls",
+        &outsider_token,
+        json!({
+            "title": "Side quest",
+            "description": "One-shot",
+            "location": "Table",
+            "dates": ["2030-01-12"],
+            "participants": []
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", own_poll);
+    let own_uri = format!("/api/polls/{}", own_poll["id"].as_str().unwrap());
+    let admin_token = own_poll["adminToken"].as_str().unwrap().to_string();
+    let edit = json!({
+        "title": "Side quest",
+        "description": "One-shot",
+        "location": "Table",
+        "dates": ["2030-01-12"],
+        "participants": [],
+        "campaignId": campaign_id
+    });
+    let (status, _) = send(&app, "PUT", &own_uri, &outsider_token, edit.clone()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send_with_headers(
+        &app,
+        "PUT",
+        &own_uri,
+        &[("X-Admin-Token", &admin_token)],
+        edit,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let linked: Option<String> = sqlx::query_scalar("SELECT campaign_id FROM polls WHERE id = ?")
+        .bind(own_poll["id"].as_str().unwrap())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(linked, None);
+}
+
+#[tokio::test]
+async fn test_campaign_lists_closed_and_cancelled_polls() {
+    let (app, pool) = setup_test_app().await;
+    let (_, dm_token) =
+        create_test_user_with_session(&pool, "dm@test.com", "SecurePass123!@#", "dm").await;
+
+    let (status, created) = send(
+        &app,
+        "POST",
+        "/api/campaigns",
+        &dm_token,
+        json!({
+            "name": "Tomb of Annihilation",
+            "description": "Jungle crawl in Chult",
+            "defaultLocation": "Discord",
+            "defaultTimeSlots": ["20:00"],
+            "members": []
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", created);
+    let campaign_id = created["id"].as_str().unwrap().to_string();
+
+    let mut poll_ids = Vec::new();
+    for title in ["Session 1", "Session 2"] {
+        let (status, poll) = send(
+            &app,
+            "POST",
+            "/api/polls",
+            &dm_token,
+            json!({
+                "title": title,
+                "description": "",
+                "location": "",
+                "dates": ["2030-01-10"],
+                "participants": [],
+                "campaignId": campaign_id
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{:?}", poll);
+        poll_ids.push(poll["id"].as_str().unwrap().to_string());
+    }
+    let (closed_id, cancelled_id) = (&poll_ids[0], &poll_ids[1]);
+
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &format!("/api/polls/{}/close", closed_id),
+        &dm_token,
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &format!("/api/polls/{}/cancel", cancelled_id),
+        &dm_token,
+        json!({ "reason": "Half the table is away" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // A closed poll is still waiting for a slot; a cancelled one is no past session
+    let uri = format!("/api/campaigns/{}", campaign_id);
+    let (status, detail) = send(&app, "GET", &uri, &dm_token, Value::Null).await;
+    assert_eq!(status, StatusCode::OK, "{:?}", detail);
+    assert_eq!(detail["openPolls"].as_array().unwrap().len(), 1);
+    assert_eq!(detail["openPolls"][0]["id"], closed_id.as_str());
+    assert_eq!(detail["openPolls"][0]["status"], "closed");
+    assert_eq!(detail["cancelled"][0]["id"], cancelled_id.as_str());
+    assert_eq!(
+        detail["cancelled"][0]["cancelReason"],
+        "Half the table is away"
+    );
+    assert_eq!(detail["past"], json!([]));
+    assert_eq!(detail["upcoming"], json!([]));
+}
@@ -0,0 +1,205 @@
+use crate::helpers::{create_test_user_with_session, send, send_with_headers, setup_test_app};
+use axum::http::StatusCode;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_claim_participations_into_account() {
+    std::env::set_var("MOCK_EMAIL", "true");
+    let (app, pool) = setup_test_app().await;
+    let (_, dm_token) =
+        create_test_user_with_session(&pool, "dm@test.com", "SecurePass123!@#", "dm").await;
+
+    let poll_body = |title: &str, participants: Vec<&str>| {
+        json!({
+            "title": title,
+            "description": "Weekly game",
+            "location": "Discord",
+            "dates": ["2030-01-10"],
+            "timePreferences": ["20:00"],
+            "participants": participants
+        })
+    };
+    let (_, invited) = send(
+        &app,
+        "POST",
+        "/api/polls",
+        &dm_token,
+        poll_body("Invited", vec!["alice@test.com"]),
+    )
+    .await;
+    let invited_id = invited["id"].as_str().unwrap().to_string();
+    let (_, open) = send(
+        &app,
+        "POST",
+        "/api/polls",
+        &dm_token,
+        poll_body("Open", vec![]),
+    )
+    .await;
+    let open_id = open["id"].as_str().unwrap().to_string();
+
+    // Alice votes anonymously on the open poll before having an account
+    let (status, guest) = send_with_headers(
+        &app,
+        "POST",
+        &format!("/api/polls/{}/join", open_id),
+        &[],
+        json!({ "name": "Alice", "email": null }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", guest);
+    let guest_token = guest["access_token"].as_str().unwrap().to_string();
+
+    let (alice_id, alice_token) =
+        create_test_user_with_session(&pool, "alice@test.com", "SecurePass123!@#", "player").await;
+
+    // 1. The invitation carrying her email is offered, then linked through the emailed link
+    let (status, claimable) = send(
+        &app,
+        "GET",
+        "/api/me/participations/claimable",
+        &alice_token,
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let offered = claimable["participations"].as_array().unwrap();
+    assert_eq!(offered.len(), 1);
+    assert_eq!(offered[0]["pollTitle"], "Invited");
+    let participant_id = offered[0]["participantId"].as_str().unwrap().to_string();
+
+    let claim_uri = "/api/me/participations/claim";
+    let (status, claimed) = send(&app, "POST", claim_uri, &alice_token, json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(claimed["count"], 0);
+    assert_eq!(claimed["confirmationSent"], 1);
+
+    let (owner, emailed_token): (Option<String>, String) =
+        sqlx::query_as("SELECT user_id, access_token FROM participants WHERE id = ?")
+            .bind(&participant_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(owner, None);
+    let (status, claimed) = send(
+        &app,
+        "POST",
+        claim_uri,
+        &alice_token,
+        json!({ "accessTokens": [emailed_token] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(claimed["claimed"], json!([participant_id]));
+    let owner: Option<String> = sqlx::query_scalar("SELECT user_id FROM participants WHERE id = ?")
+        .bind(&participant_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(owner.as_deref(), Some(alice_id.as_str()));
+
+    // 2. Her session alone now lets her vote
+    let (status, body) = send(
+        &app,
+        "POST",
+        &format!(
+            "/api/polls/{}/participants/{}/availability",
+            invited_id, participant_id
+        ),
+        &alice_token,
+        json!({ "availability": [{ "date": "2030-01-10", "timeSlot": "20:00", "status": "available" }] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", body);
+
+    // 3. Anonymous votes are claimed with their link token, once
+    let by_token = json!({ "accessTokens": [guest_token] });
+    let (_, claimed) = send(&app, "POST", claim_uri, &alice_token, by_token.clone()).await;
+    assert_eq!(claimed["claimed"], json!([guest["id"]]));
+    let (_, claimed) = send(&app, "POST", claim_uri, &alice_token, by_token).await;
+    assert_eq!(claimed["count"], 0);
+
+    let (_, claimable) = send(
+        &app,
+        "GET",
+        "/api/me/participations/claimable",
+        &alice_token,
+        json!({}),
+    )
+    .await;
+    assert!(claimable["participations"].as_array().unwrap().is_empty());
+
+    // 4. Other accounts can't take them over
+    let (_, bob_token) =
+        create_test_user_with_session(&pool, "bob@test.com", "SecurePass123!@#", "player").await;
+    let (_, claimed) = send(
+        &app,
+        "POST",
+        claim_uri,
+        &bob_token,
+        json!({ "participantIds": [participant_id] }),
+    )
+    .await;
+    assert_eq!(claimed["count"], 0);
+}
+
+#[tokio::test]
+async fn test_unverified_account_cannot_claim_by_email() {
+    std::env::set_var("MOCK_EMAIL", "true");
+    let (app, pool) = setup_test_app().await;
+    let (_, dm_token) =
+        create_test_user_with_session(&pool, "dm@test.com", "SecurePass123!@#", "dm").await;
+    let (_, poll) = send(
+        &app,
+        "POST",
+        "/api/polls",
+        &dm_token,
+        json!({
+            "title": "Invited",
+            "description": "Weekly game",
+            "location": "Discord",
+            "dates": ["2030-01-10"],
+            "timePreferences": ["20:00"],
+            "participants": ["victim@test.com"]
+        }),
+    )
+    .await;
+    let poll_id = poll["id"].as_str().unwrap().to_string();
+    let participant_id: String =
+        sqlx::query_scalar("SELECT id FROM participants WHERE poll_id = ? AND email = ?")
+            .bind(&poll_id)
+            .bind("victim@test.com")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+    // A password account registered with someone else's address never proved owning it
+    let (_, mallory_token) =
+        create_test_user_with_session(&pool, "victim@test.com", "SecurePass123!@#", "player").await;
+
+    let claim_uri = "/api/me/participations/claim";
+    for body in [json!({}), json!({ "participantIds": [participant_id] })] {
+        let (status, claimed) = send(&app, "POST", claim_uri, &mallory_token, body).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(claimed["count"], 0);
+    }
+
+    // Joining with the address doesn't hand over the personal link either
+    let (status, body) = send(
+        &app,
+        "POST",
+        &format!("/api/polls/{}/join", poll_id),
+        &mallory_token,
+        json!({ "name": "Mallory", "email": "victim@test.com" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(body.get("access_token").is_none());
+
+    let owner: Option<String> = sqlx::query_scalar("SELECT user_id FROM participants WHERE id = ?")
+        .bind(&participant_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(owner, None);
+}
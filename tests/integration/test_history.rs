@@ -0,0 +1,129 @@
+use crate::helpers::{create_test_user_with_session, send, send_with_headers, setup_test_app};
+use axum::http::StatusCode;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_vote_history_and_time_travel() {
+    let (app, pool) = setup_test_app().await;
+    let (_, dm_token) =
+        create_test_user_with_session(&pool, "dm@test.com", "SecurePass123!@#", "dm").await;
+
+    let (status, poll) = send(
+        &app,
+        "POST",
+        "/api/polls",
+        &dm_token,
+        json!({
+            "title": "Friday game",
+            "description": "Weekly game",
+            "location": "Discord",
+            "dates": ["2030-01-10"],
+            "timePreferences": ["20:00", "21:00"],
+            "participants": []
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", poll);
+    let poll_id = poll["id"].as_str().unwrap().to_string();
+
+    let (_, player) = send_with_headers(
+        &app,
+        "POST",
+        &format!("/api/polls/{}/join", poll_id),
+        &[],
+        json!({ "name": "Alice", "email": null }),
+    )
+    .await;
+    let participant_id = player["id"].as_str().unwrap().to_string();
+    let access_token = player["access_token"].as_str().unwrap().to_string();
+
+    let before_votes = chrono::Utc::now().timestamp() - 1;
+    let votes_uri = format!(
+        "/api/polls/{}/participants/{}/availability",
+        poll_id, participant_id
+    );
+    for availability in [
+        json!([
+            { "date": "2030-01-10", "timeSlot": "20:00", "status": "available" },
+            { "date": "2030-01-10", "timeSlot": "21:00", "status": "tentative" }
+        ]),
+        json!([{ "date": "2030-01-10", "timeSlot": "20:00", "status": "busy" }]),
+    ] {
+        let (status, body) = send_with_headers(
+            &app,
+            "POST",
+            &votes_uri,
+            &[],
+            json!({ "availability": availability, "access_token": access_token }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{:?}", body);
+    }
+
+    // 1. Every change is listed, oldest first
+    let (status, history) = send_with_headers(
+        &app,
+        "GET",
+        &format!("/api/polls/{}/history", poll_id),
+        &[],
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", history);
+    let participants = history["participants"].as_array().unwrap();
+    assert_eq!(participants.len(), 1);
+    assert_eq!(participants[0]["name"], "Alice");
+    let changes: Vec<(String, String)> = participants[0]["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| {
+            assert!(c["recordedAt"].as_i64().unwrap() > before_votes);
+            (
+                c["timeSlot"].as_str().unwrap().to_string(),
+                c["status"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    let expected = [
+        ("20:00", "available"),
+        ("21:00", "tentative"),
+        ("20:00", "busy"),
+        ("21:00", "cleared"),
+    ];
+    assert_eq!(
+        changes,
+        expected
+            .iter()
+            .map(|(slot, status)| (slot.to_string(), status.to_string()))
+            .collect::<Vec<_>>()
+    );
+
+    // 2. The poll as it was before any vote, and as it is now
+    let poll_at = |as_of: i64| {
+        let app = app.clone();
+        let uri = format!("/api/polls/{}?as_of={}", poll_id, as_of);
+        async move { send_with_headers(&app, "GET", &uri, &[], json!({})).await }
+    };
+    let (status, past) = poll_at(before_votes).await;
+    assert_eq!(status, StatusCode::OK, "{:?}", past);
+    assert_eq!(past["asOf"], before_votes);
+    assert_eq!(past["availability"], json!([]));
+
+    let (_, now) = poll_at(chrono::Utc::now().timestamp() + 60).await;
+    let availability = now["availability"].as_array().unwrap();
+    assert_eq!(availability.len(), 1);
+    assert_eq!(availability[0]["time_slot"], "20:00");
+    assert_eq!(availability[0]["status"], "busy");
+
+    // Unknown polls have no history
+    let (status, _) = send_with_headers(
+        &app,
+        "GET",
+        &format!("/api/polls/{}/history", uuid::Uuid::new_v4()),
+        &[],
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,212 @@
+use crate::helpers::{create_test_user_with_session, send, send_with_headers, setup_test_app};
+use axum::http::StatusCode;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_invitations_and_resend() {
+    // Emails are only logged
+    std::env::set_var("MOCK_EMAIL", "true");
+    let (app, pool) = setup_test_app().await;
+    let (_, dm_token) =
+        create_test_user_with_session(&pool, "dm@test.com", "SecurePass123!@#", "dm").await;
+    let (_, other_token) =
+        create_test_user_with_session(&pool, "other@test.com", "SecurePass123!@#", "dm").await;
+
+    // 1. Creating the poll queues one invitation per invitee
+    let (status, poll) = send(
+        &app,
+        "POST",
+        "/api/polls",
+        &dm_token,
+        json!({
+            "title": "Session 1",
+            "description": "Weekly game",
+            "location": "Discord",
+            "dates": ["2030-01-10"],
+            "timePreferences": ["20:00"],
+            "participants": ["alice@test.com", "bob@test.com"]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", poll);
+    assert_eq!(poll["invitationsQueued"], 2);
+    let poll_id = poll["id"].as_str().unwrap().to_string();
+
+    // 2. The personal link identifies the invitee
+    let (participant_id, access_token): (String, String) = sqlx::query_as(
+        "SELECT id, access_token FROM participants WHERE poll_id = ? AND email = 'alice@test.com'",
+    )
+    .bind(&poll_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let (status, invite) = send_with_headers(
+        &app,
+        "GET",
+        &format!("/api/invites/{}", access_token),
+        &[],
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(invite["pollId"], poll_id.as_str());
+    assert_eq!(invite["participantId"], participant_id.as_str());
+    assert_eq!(invite["email"], "alice@test.com");
+    let (status, _) = send_with_headers(
+        &app,
+        "GET",
+        &format!("/api/invites/{}", uuid::Uuid::new_v4()),
+        &[],
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // 3. Only the organizer can resend it
+    let resend_uri = format!(
+        "/api/polls/{}/participants/{}/invite",
+        poll_id, participant_id
+    );
+    let (status, _) = send(&app, "POST", &resend_uri, &other_token, json!({})).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, body) = send(&app, "POST", &resend_uri, &dm_token, json!({})).await;
+    assert_eq!(status, StatusCode::OK, "{:?}", body);
+    assert_eq!(body["email"], "alice@test.com");
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        &format!(
+            "/api/polls/{}/participants/{}/invite",
+            poll_id,
+            uuid::Uuid::new_v4()
+        ),
+        &dm_token,
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_recover_link_rotates_token() {
+    std::env::set_var("MOCK_EMAIL", "true");
+    let (app, pool) = setup_test_app().await;
+    let (_, dm_token) =
+        create_test_user_with_session(&pool, "dm@test.com", "SecurePass123!@#", "dm").await;
+    let (_, alice_session) =
+        create_test_user_with_session(&pool, "alice@test.com", "SecurePass123!@#", "player").await;
+
+    let (status, poll) = send(
+        &app,
+        "POST",
+        "/api/polls",
+        &dm_token,
+        json!({
+            "title": "Session 1",
+            "description": "Weekly game",
+            "location": "Discord",
+            "dates": ["2030-01-10"],
+            "timePreferences": ["20:00"],
+            "participants": ["alice@test.com"]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", poll);
+    let poll_id = poll["id"].as_str().unwrap().to_string();
+    let token_of = |pool: sqlx::SqlitePool| {
+        let poll_id = poll_id.clone();
+        async move {
+            sqlx::query_scalar::<_, String>(
+                "SELECT access_token FROM participants WHERE poll_id = ? AND email = 'alice@test.com'",
+            )
+            .bind(&poll_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+        }
+    };
+    let original = token_of(pool.clone()).await;
+
+    // 1. Knowing the address is no longer enough to get the token
+    let join_uri = format!("/api/polls/{}/join", poll_id);
+    let join_body = json!({ "name": "Mallory", "email": "alice@test.com" });
+    let (status, body) = send_with_headers(&app, "POST", &join_uri, &[], join_body.clone()).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(body.get("access_token").is_none());
+
+    // 2. Nor is an account registered with it...
+    let (status, _) = send(&app, "POST", &join_uri, &alice_session, join_body.clone()).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // ...but once the emailed link tied the row to her account, she still gets it
+    let (_, claimed) = send(
+        &app,
+        "POST",
+        "/api/me/participations/claim",
+        &alice_session,
+        json!({ "accessTokens": [original] }),
+    )
+    .await;
+    assert_eq!(claimed["count"], 1);
+    let (status, body) = send(&app, "POST", &join_uri, &alice_session, join_body.clone()).await;
+    assert_eq!(status, StatusCode::OK, "{:?}", body);
+    assert_eq!(body["access_token"], original.as_str());
+
+    // ...and one is made up for rows that never had a token
+    sqlx::query("UPDATE participants SET access_token = NULL WHERE poll_id = ?")
+        .bind(&poll_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (status, body) = send(&app, "POST", &join_uri, &alice_session, join_body).await;
+    assert_eq!(status, StatusCode::OK, "{:?}", body);
+    let original = token_of(pool.clone()).await;
+    assert_eq!(body["access_token"], original.as_str());
+
+    // 3. Recovery rotates the token without revealing it
+    let recover_uri = format!("/api/polls/{}/recover", poll_id);
+    let (status, body) = send_with_headers(
+        &app,
+        "POST",
+        &recover_uri,
+        &[],
+        json!({ "email": "Alice@Test.com" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.get("access_token").is_none());
+    let rotated = token_of(pool.clone()).await;
+    assert_ne!(rotated, original);
+
+    let (status, _) = send_with_headers(
+        &app,
+        "GET",
+        &format!("/api/invites/{}", original),
+        &[],
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send_with_headers(
+        &app,
+        "GET",
+        &format!("/api/invites/{}", rotated),
+        &[],
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 4. Unknown addresses get the same answer
+    let (status, unknown) = send_with_headers(
+        &app,
+        "POST",
+        &recover_uri,
+        &[],
+        json!({ "email": "nobody@test.com" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(unknown["message"], body["message"]);
+}
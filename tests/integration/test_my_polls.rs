@@ -0,0 +1,115 @@
+use crate::helpers::{create_test_user_with_session, send, setup_test_app};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn test_my_polls_lists_organized_and_invited() {
+    let (app, pool) = setup_test_app().await;
+    let (_, dm_token) =
+        create_test_user_with_session(&pool, "dm@test.com", "SecurePass123!@#", "dm").await;
+    let (_, player_token) =
+        create_test_user_with_session(&pool, "player@test.com", "SecurePass123!@#", "player").await;
+    let (_, outsider_token) =
+        create_test_user_with_session(&pool, "outsider@test.com", "SecurePass123!@#", "player")
+            .await;
+
+    // 1. The DM invites the player (by email) to two sessions
+    let mut poll_ids = Vec::new();
+    for title in ["Session 1", "Session 2"] {
+        let (status, poll) = send(
+            &app,
+            "POST",
+            "/api/polls",
+            &dm_token,
+            json!({
+                "title": title,
+                "description": "Weekly game",
+                "location": "Discord",
+                "dates": ["2030-01-10"],
+                "timePreferences": ["20:00", "21:00"],
+                "participants": ["Player@test.com"]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{:?}", poll);
+        poll_ids.push(poll["id"].as_str().unwrap().to_string());
+    }
+
+    // 2. An invitation to the account's (unverified) email isn't enough to list the polls:
+    //    the player claims them with their links first
+    let (_, unclaimed) = send(&app, "GET", "/api/me/polls", &player_token, Value::Null).await;
+    assert_eq!(unclaimed["total"], 0);
+    let tokens: Vec<String> =
+        sqlx::query_scalar("SELECT access_token FROM participants WHERE email = 'Player@test.com'")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    let (status, claimed) = send(
+        &app,
+        "POST",
+        "/api/me/participations/claim",
+        &player_token,
+        json!({ "accessTokens": tokens }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", claimed);
+    assert_eq!(claimed["count"], 2);
+
+    // 3. The player answers the first one
+    let (participant_id, access_token): (String, String) = sqlx::query_as(
+        "SELECT id, access_token FROM participants WHERE poll_id = ? AND email = 'Player@test.com'",
+    )
+    .bind(&poll_ids[0])
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let (status, _) = send(
+        &app,
+        "POST",
+        &format!(
+            "/api/polls/{}/participants/{}/availability",
+            poll_ids[0], participant_id
+        ),
+        &player_token,
+        json!({
+            "availability": [
+                {"date": "2030-01-10", "timeSlot": "20:00", "status": "available"},
+                {"date": "2030-01-10", "timeSlot": "21:00", "status": "busy"}
+            ],
+            "access_token": access_token
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 4. Each side sees the polls from its own point of view
+    let (status, mine) = send(&app, "GET", "/api/me/polls", &player_token, Value::Null).await;
+    assert_eq!(status, StatusCode::OK, "{:?}", mine);
+    assert_eq!(mine["total"], 2);
+    assert_eq!(mine["awaitingResponse"], 1);
+    let polls = mine["polls"].as_array().unwrap();
+    let answered = polls
+        .iter()
+        .find(|p| p["id"] == poll_ids[0].as_str())
+        .unwrap();
+    assert_eq!(answered["isOrganizer"], false);
+    assert_eq!(answered["myResponse"]["status"], "responded");
+    assert_eq!(answered["myResponse"]["available"], 1);
+    assert_eq!(answered["myResponse"]["busy"], 1);
+    let unanswered = polls
+        .iter()
+        .find(|p| p["id"] == poll_ids[1].as_str())
+        .unwrap();
+    assert_eq!(unanswered["myResponse"]["status"], "pending");
+
+    let (_, organized) = send(&app, "GET", "/api/me/polls", &dm_token, Value::Null).await;
+    assert_eq!(organized["organized"], 2);
+    assert_eq!(organized["polls"][0]["isOrganizer"], true);
+    assert_eq!(organized["polls"][0]["myResponse"], Value::Null);
+
+    let (_, none) = send(&app, "GET", "/api/me/polls", &outsider_token, Value::Null).await;
+    assert_eq!(none["total"], 0);
+
+    let (status, _) = send(&app, "GET", "/api/me/polls", "bogus", Value::Null).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
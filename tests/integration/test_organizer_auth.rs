@@ -0,0 +1,88 @@
+use crate::helpers::{create_test_user_with_session, send, send_with_headers, setup_test_app};
+use axum::http::StatusCode;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_organizer_can_manage_own_poll() {
+    let (app, pool) = setup_test_app().await;
+    let (_, dm_token) =
+        create_test_user_with_session(&pool, "dm@test.com", "SecurePass123!@#", "dm").await;
+    let (_, other_dm_token) =
+        create_test_user_with_session(&pool, "other-dm@test.com", "SecurePass123!@#", "dm").await;
+    let (_, admin_token) =
+        create_test_user_with_session(&pool, "admin@test.com", "SecurePass123!@#", "admin").await;
+
+    let poll_body = json!({
+        "title": "Session 1",
+        "description": "Weekly game",
+        "location": "Discord",
+        "dates": ["2030-01-10"],
+        "timePreferences": ["20:00", "21:00"],
+        "participants": ["player@test.com"]
+    });
+    let (status, poll) = send(&app, "POST", "/api/polls", &dm_token, poll_body.clone()).await;
+    assert_eq!(status, StatusCode::OK, "{:?}", poll);
+    let poll_id = poll["id"].as_str().unwrap().to_string();
+    let poll_admin_token = poll["adminToken"].as_str().unwrap().to_string();
+    let poll_uri = format!("/api/polls/{}", poll_id);
+
+    // 1. Nobody else can edit, finalize or delete it
+    let (status, _) = send_with_headers(&app, "PUT", &poll_uri, &[], poll_body.clone()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&app, "PUT", &poll_uri, &other_dm_token, poll_body.clone()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &format!("{}/finalize", poll_uri),
+        &other_dm_token,
+        json!({ "start": "2030-01-10 20:00" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send_with_headers(
+        &app,
+        "DELETE",
+        &poll_uri,
+        &[("X-Admin-Token", "not-the-token")],
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 2. The organizer edits it with their session
+    let mut edited = poll_body.clone();
+    edited["title"] = json!("Session 1 (moved online)");
+    let (status, body) = send(&app, "PUT", &poll_uri, &dm_token, edited).await;
+    assert_eq!(status, StatusCode::OK, "{:?}", body);
+
+    // 3. The admin token alone is enough to close it, no account needed
+    let (status, body) = send_with_headers(
+        &app,
+        "PUT",
+        &format!("{}/close", poll_uri),
+        &[("X-Admin-Token", &poll_admin_token)],
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", body);
+
+    // 4. Participant removal is checked against the participant's poll
+    let participant_id: String =
+        sqlx::query_scalar("SELECT id FROM participants WHERE poll_id = ?")
+            .bind(&poll_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    let participant_uri = format!("/api/participants/{}", participant_id);
+    let (status, _) = send(&app, "DELETE", &participant_uri, &other_dm_token, json!({})).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(&app, "DELETE", &participant_uri, &dm_token, json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 5. Site admins keep access to every poll
+    let (status, _) = send(&app, "DELETE", &poll_uri, &admin_token, json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, "DELETE", &poll_uri, &dm_token, json!({})).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
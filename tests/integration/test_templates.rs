@@ -0,0 +1,190 @@
+use crate::helpers::{create_test_user_with_session, send, setup_test_app};
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+
+fn day(offset: i64) -> String {
+    (Utc::now().date_naive() + Duration::days(offset)).to_string()
+}
+
+async fn stored_poll(pool: &sqlx::SqlitePool, poll_id: &str) -> (String, Vec<String>, Value) {
+    let (title, dates, time_range): (String, String, String) =
+        sqlx::query_as("SELECT title, dates, time_range FROM polls WHERE id = ?")
+            .bind(poll_id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+    (
+        title,
+        serde_json::from_str(&dates).unwrap(),
+        serde_json::from_str(&time_range).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_duplicate_poll_and_templates() {
+    let (app, pool) = setup_test_app().await;
+    let (_, dm_token) =
+        create_test_user_with_session(&pool, "dm@test.com", "SecurePass123!@#", "dm").await;
+    let (_, other_token) =
+        create_test_user_with_session(&pool, "other@test.com", "SecurePass123!@#", "dm").await;
+
+    let (status, poll) = send(
+        &app,
+        "POST",
+        "/api/polls",
+        &dm_token,
+        json!({
+            "title": "D&D night",
+            "description": "Weekly game",
+            "location": "Discord",
+            "dates": [day(1), day(2)],
+            "timePreferences": { day(1): ["20:00"], day(2): ["21:00"] },
+            "participants": ["alice@test.com", "bob@test.com"],
+            "requiredParticipants": ["alice@test.com"]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", poll);
+    let poll_id = poll["id"].as_str().unwrap().to_string();
+    let (original_title, _, _) = stored_poll(&pool, &poll_id).await;
+
+    // 1. Duplicate a week later: dates, per-day slots and invitees follow
+    let duplicate_uri = format!("/api/polls/{}/duplicate", poll_id);
+    let (status, copy) = send(
+        &app,
+        "POST",
+        &duplicate_uri,
+        &dm_token,
+        json!({ "offsetDays": 7 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", copy);
+    assert_eq!(copy["sourcePollId"], poll_id.as_str());
+    assert!(copy["adminToken"].is_string());
+
+    let copy_id = copy["id"].as_str().unwrap();
+    let (title, dates, time_range) = stored_poll(&pool, copy_id).await;
+    assert_eq!(title, original_title);
+    assert_eq!(dates, vec![day(8), day(9)]);
+    assert_eq!(time_range, json!({ day(8): ["20:00"], day(9): ["21:00"] }));
+
+    let invitees: Vec<(String, String)> = sqlx::query_as(
+        "SELECT email, attendance FROM participants WHERE poll_id = ? ORDER BY email",
+    )
+    .bind(copy_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        invitees,
+        vec![
+            ("alice@test.com".to_string(), "required".to_string()),
+            ("bob@test.com".to_string(), "optional".to_string())
+        ]
+    );
+
+    let (status, _) = send(&app, "POST", &duplicate_uri, &dm_token, json!({})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(
+        &app,
+        "POST",
+        &duplicate_uri,
+        &other_token,
+        json!({ "offsetDays": 7 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 2. Save the poll as a template; only its owner sees it
+    let (status, saved) = send(
+        &app,
+        "POST",
+        "/api/templates",
+        &dm_token,
+        json!({ "name": "Weekly", "pollId": poll_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", saved);
+    let template_id = saved["id"].as_str().unwrap().to_string();
+    assert_eq!(
+        saved["template"]["participants"].as_array().unwrap().len(),
+        2
+    );
+
+    let (_, list) = send(&app, "GET", "/api/templates", &dm_token, json!({})).await;
+    assert_eq!(list["templates"].as_array().unwrap().len(), 1);
+    let (_, list) = send(&app, "GET", "/api/templates", &other_token, json!({})).await;
+    assert!(list["templates"].as_array().unwrap().is_empty());
+    let template_uri = format!("/api/templates/{}", template_id);
+    let (status, _) = send(&app, "GET", &template_uri, &other_token, json!({})).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 3. New poll from the template, starting two weeks later
+    let from_template_uri = format!("/api/polls/from-template/{}", template_id);
+    let (status, created) = send(
+        &app,
+        "POST",
+        &from_template_uri,
+        &dm_token,
+        json!({ "startDate": day(15), "title": "Session 3" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", created);
+    assert_eq!(created["templateId"], template_id.as_str());
+    let (title, dates, _) = stored_poll(&pool, created["id"].as_str().unwrap()).await;
+    assert_eq!(title, "Session 3");
+    assert_eq!(dates, vec![day(15), day(16)]);
+
+    // 4. Templates built from fields keep their dates; copies must still be in the future
+    let (status, saved) = send(
+        &app,
+        "POST",
+        "/api/templates",
+        &dm_token,
+        json!({
+            "name": "One-shot",
+            "title": "One-shot",
+            "description": "Tomb of Horrors",
+            "location": "Table",
+            "dates": ["2020-01-03"],
+            "timePreferences": ["20:00"]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", saved);
+    let one_shot_uri = format!("/api/polls/from-template/{}", saved["id"].as_str().unwrap());
+    let (status, _) = send(
+        &app,
+        "POST",
+        &one_shot_uri,
+        &dm_token,
+        json!({ "offsetDays": 0 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, created) = send(
+        &app,
+        "POST",
+        &one_shot_uri,
+        &dm_token,
+        json!({ "startDate": day(3) }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", created);
+
+    // 5. Deleting the template keeps the polls made from it
+    let (status, _) = send(&app, "DELETE", &template_uri, &dm_token, json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, "GET", &template_uri, &dm_token, json!({})).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(
+        &app,
+        "GET",
+        &format!("/api/polls/{}", created["id"].as_str().unwrap()),
+        &dm_token,
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}